@@ -0,0 +1,67 @@
+use {
+    crate::{cli::Auth as AuthCmd, config::Settings, credentials::Credential, prompt},
+    anyhow::{anyhow, Context, Result},
+    std::io::{stdin, Read},
+};
+
+pub async fn run(cmd: &AuthCmd, settings: Settings) -> Result<()> {
+    let Settings {
+        mut profile,
+        source,
+    } = settings;
+    let host = profile
+        .credential_key()
+        .map(str::to_string)
+        .ok_or_else(|| {
+            anyhow!("No Jira host configured, use --host, JIRA_HOST or a config profile")
+        })?;
+
+    match cmd {
+        AuthCmd::Login { user, with_token } => {
+            let user = match user.as_ref().or(profile.user.as_ref()) {
+                Some(user) => user.clone(),
+                None => prompt::line("User: ")?,
+            };
+            let token = match with_token {
+                true => {
+                    let mut token = String::new();
+                    stdin().read_to_string(&mut token)?;
+                    token.trim().to_string()
+                }
+                false => rpassword::prompt_password("API token: ")?,
+            };
+
+            profile.user = Some(user.clone());
+            profile.token = Some(token.clone());
+
+            let myself = profile.client()?.users().myself().await?;
+            profile
+                .credential_store()?
+                .store(&host, &Credential { user, token })?;
+
+            eprintln!("Logged in to {} as {}", host, myself.data().display_name);
+        }
+        AuthCmd::Logout => {
+            profile.credential_store()?.erase(&host)?;
+
+            eprintln!("Logged out of {}", host);
+        }
+        AuthCmd::Status => {
+            let source = source.ok_or_else(|| anyhow!("Not logged in to {}", host))?;
+
+            let myself = profile.client()?.users().myself().await.with_context(|| {
+                format!("Credentials from {} were rejected by {}", source, host)
+            })?;
+
+            eprintln!(
+                "Logged in to {} as {} ({}), using credentials from {}",
+                host,
+                myself.data().display_name,
+                myself.data().name,
+                source
+            );
+        }
+    }
+
+    Ok(())
+}
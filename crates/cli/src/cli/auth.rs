@@ -0,0 +1,24 @@
+use super::*;
+
+/// Manage the credentials of the selected profile's host
+///
+/// Credentials are kept in the profile's credential store, see the
+/// 'credentials' and 'credential-helper' config keys
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub enum Auth {
+    /// Verify credentials against the host and save them
+    Login {
+        /// The user to log in as, defaults to the profile's user
+        #[structopt(short, long, value_name = "USER")]
+        user: Option<String>,
+
+        /// Read the token from stdin instead of prompting for it
+        #[structopt(long)]
+        with_token: bool,
+    },
+    /// Remove the saved credentials for the host
+    Logout,
+    /// Show which credentials are in use and verify them
+    Status,
+}
@@ -0,0 +1,37 @@
+use {super::*, crate::complete::Kind, structopt::clap::Shell};
+
+/// Generate a shell completion script
+///
+/// For example, in bash: source <(jira completions bash)
+///
+/// Bash, zsh and fish also complete issue keys, project keys, issue
+/// types and field ids by querying (and caching) the selected
+/// profile's instance
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub struct Completions {
+    /// The shell to generate completions for
+    #[structopt(value_name = "SHELL", possible_values = &Shell::variants(), case_insensitive = true)]
+    pub shell: Shell,
+}
+
+/// Print dynamic completion candidates, one per line, used by the
+/// scripts from 'jira completions'
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub struct Complete {
+    /// The kind of value to complete
+    ///
+    /// Possible values
+    /// 'issues', 'projects', 'issue-types', 'fields'
+    #[structopt(value_name = "KIND")]
+    pub kind: Kind,
+
+    /// Only print candidates starting with this prefix
+    #[structopt(value_name = "PREFIX", default_value = "")]
+    pub prefix: String,
+
+    /// Ignore any cached candidates, querying the instance
+    #[structopt(long)]
+    pub refresh: bool,
+}
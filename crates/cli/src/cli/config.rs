@@ -0,0 +1,36 @@
+use {super::*, crate::config::Key};
+
+/// Inspect or change the CLI's configuration file
+///
+/// Commands act on the profile selected by --profile or JIRA_PROFILE,
+/// falling back to the default profile
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub enum Config {
+    /// List every profile and its settings
+    List,
+    /// Print a single setting of the selected profile
+    Get {
+        /// One of: host, auth, user, token, credentials, credential-helper,
+        /// project, fields, output, columns, page-size
+        #[structopt(value_name = "KEY")]
+        key: Key,
+    },
+    /// Change a setting of the selected profile, creating the profile if needed
+    ///
+    /// An empty value unsets the setting
+    Set {
+        /// One of: host, auth, user, token, credentials, credential-helper,
+        /// project, fields, output, columns, page-size
+        #[structopt(value_name = "KEY")]
+        key: Key,
+
+        #[structopt(value_name = "VALUE")]
+        value: String,
+    },
+    /// Make the given profile the default
+    Use {
+        #[structopt(value_name = "PROFILE")]
+        profile: String,
+    },
+}
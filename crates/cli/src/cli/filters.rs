@@ -0,0 +1,103 @@
+use {super::*, jira_rs::filter::payload::Share, std::path::PathBuf};
+
+/// Manage saved filters
+///
+/// Filters are given by id, or by name when not a number
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub enum Filters {
+    /// List filters, by default the user's favourites
+    List {
+        /// List the filters owned by the user instead
+        #[structopt(long, conflicts_with = "name")]
+        mine: bool,
+
+        /// Search every visible filter whose name contains this instead
+        #[structopt(long, value_name = "TEXT")]
+        name: Option<String>,
+
+        /// Maximum number of filters to return, when searching
+        #[structopt(short, long, value_name = "uint", requires = "name")]
+        max_results: Option<u32>,
+
+        /// Return filters starting from, when searching
+        #[structopt(short, long, value_name = "uint", requires = "name")]
+        start_at: Option<u32>,
+    },
+    /// Get a single filter
+    Get {
+        #[structopt(value_name = "ID/NAME")]
+        filter: String,
+    },
+    /// Create a new filter
+    Create {
+        /// The name of the filter
+        #[structopt(value_name = "NAME")]
+        name: String,
+
+        /// The JQL query the filter saves
+        #[structopt(short, long, value_name = "JQL")]
+        jql: String,
+
+        #[structopt(short, long, value_name = "TEXT")]
+        description: Option<String>,
+
+        /// Who to share the filter with, by default no one
+        ///
+        /// One of: global, loggedin, group:<name>, project:<id>,
+        /// project-role:<project id>:<role id>, user:<account id>
+        #[structopt(long, value_name = "SHARE", number_of_values = 1)]
+        share: Vec<Share>,
+
+        /// Add the filter to the user's favourites
+        #[structopt(long)]
+        favourite: bool,
+    },
+    /// Create or update the user's filters to match a manifest
+    ///
+    /// The manifest is a YAML file listing filters by name, each with
+    /// its JQL and optionally a description, share permissions (as
+    /// for 'create --share') and whether it is a favourite:
+    ///
+    /// filters:
+    ///   - name: Open bugs
+    ///     jql: type = Bug AND resolution IS EMPTY
+    ///     share: [group:jira-developers]
+    ///
+    /// Filters are matched by name, and listed filters are made to match
+    /// the manifest exactly. The user's other filters are reported as
+    /// unmanaged, but left untouched
+    Apply {
+        /// The manifest to apply
+        #[structopt(value_name = "PATH")]
+        manifest: PathBuf,
+
+        /// Print the changes which would be made, without making them
+        #[structopt(long)]
+        dry_run: bool,
+    },
+    /// Search for the issues matched by a filter
+    Run {
+        #[structopt(value_name = "ID/NAME")]
+        filter: String,
+
+        /// List of fields from the issue to return
+        ///
+        /// By default, the profile's fields or all fields
+        #[structopt(
+            short,
+            long = "field",
+            number_of_values = 1,
+            set = ArgSettings::AllowLeadingHyphen
+        )]
+        fields: Option<Vec<String>>,
+
+        /// Maximum number of issues to return
+        #[structopt(short, long, value_name = "uint")]
+        max_results: Option<u32>,
+
+        /// Return results starting from
+        #[structopt(short, long, value_name = "uint")]
+        start_at: Option<u32>,
+    },
+}
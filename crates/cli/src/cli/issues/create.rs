@@ -1,4 +1,4 @@
-use {super::*, IssueOptions::Create};
+use {super::*, crate::templates::parse_var, IssueOptions::Create};
 
 #[derive(Debug, StructOpt)]
 #[structopt(rename_all = "kebab")]
@@ -9,11 +9,51 @@ pub struct IssueCreate {
     /// user's recently viewed Projects
     #[structopt(short, long)]
     pub update_history: bool,
+
+    /// Validate the data against the issue type's create metadata
+    /// before creating the issue
+    #[structopt(long)]
+    pub validate: bool,
+
+    /// Prompt for each of the issue type's required fields, rather than
+    /// providing --data
+    #[structopt(short, long, conflicts_with_all = &["data", "template"])]
+    pub interactive: bool,
+
+    /// The project to create the issue in, defaults to the profile's
+    /// project
+    #[structopt(short, long, value_name = "KEY", requires = "interactive")]
+    pub project: Option<String>,
+
+    /// The name of the issue type to create, prompted for if not given
+    #[structopt(
+        short = "T",
+        long = "type",
+        value_name = "NAME",
+        requires = "interactive"
+    )]
+    pub issue_type: Option<String>,
+
+    /// Create the issue, and any children, described by this template
+    ///
+    /// Templates are TOML files in the 'templates' directory next to
+    /// the config file, e.g ~/.config/jira/templates/<NAME>.toml
+    #[structopt(long, value_name = "NAME", conflicts_with = "data")]
+    pub template: Option<String>,
+
+    /// Set a template variable, overriding the template's default
+    #[structopt(
+        long = "var",
+        value_name = "NAME=VALUE",
+        number_of_values = 1,
+        parse(try_from_str = parse_var),
+        requires = "template"
+    )]
+    pub vars: Vec<(String, String)>,
 }
 
-impl Into<Create> for &IssueCreate {
-    fn into(self) -> Create {
-        let cli = &self;
+impl From<&IssueCreate> for Create {
+    fn from(cli: &IssueCreate) -> Self {
         Create::new().with(|this| this.update_history(cli.update_history))
     }
 }
@@ -0,0 +1,23 @@
+use super::*;
+
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub struct IssueEdit {
+    /// Validate the data against the issue's edit metadata
+    /// before editing the issue
+    #[structopt(long)]
+    pub validate: bool,
+
+    /// Edit the issue's fields in $EDITOR, rather than providing --data
+    ///
+    /// The editable fields are written to a temporary file as YAML front
+    /// matter, followed by the description. Only the fields changed in the
+    /// editor are sent to Jira
+    #[structopt(short, long, conflicts_with = "data")]
+    pub interactive: bool,
+
+    /// Apply the changes made with --interactive without asking for
+    /// confirmation
+    #[structopt(short, long, requires = "interactive")]
+    pub yes: bool,
+}
@@ -1,4 +1,4 @@
-use {super::*, IssueOptions::Get};
+use {super::*, std::path::PathBuf, IssueOptions::Get};
 
 #[derive(Debug, StructOpt)]
 #[structopt(rename_all = "kebab")]
@@ -53,11 +53,25 @@ pub struct IssuesGet {
         set = ArgSettings::AllowLeadingHyphen
     )]
     pub properties: Option<Vec<String>>,
+
+    /// Answer from the local mirror instead of the server
+    ///
+    /// See 'jira sync' for populating the mirror
+    #[structopt(
+        long,
+        conflicts_with_all = &["fields-by-key", "update-history", "expand", "properties"]
+    )]
+    pub offline: bool,
+
+    /// The mirror database used by --offline
+    ///
+    /// Defaults to <cache dir>/mirror/<host>.sqlite
+    #[structopt(long, env = "JIRA_MIRROR", value_name = "PATH")]
+    pub db: Option<PathBuf>,
 }
 
-impl<'a> Into<Get> for &'a IssuesGet {
-    fn into(self) -> Get {
-        let cli = &self;
+impl From<&IssuesGet> for Get {
+    fn from(cli: &IssuesGet) -> Self {
         Get::new().with(|this| {
             this.fields_by_key(cli.fields_by_key)
                 .update_history(cli.update_history)
@@ -24,8 +24,8 @@ pub struct IssueMetadata {
     /// of matching issue schema(s)
     #[structopt(
         short = "E", long, value_name = "KEY/ID",
-        required_unless_one = &["projects", "issue_types"],
-        conflicts_with_all = &["projects", "issue_types"]
+        required_unless_one = &["projects", "issue-types"],
+        conflicts_with_all = &["projects", "issue-types"]
     )]
     pub edit: Option<String>,
 
@@ -50,9 +50,8 @@ impl<'a> From<&'a IssueMetadata> for MetaKind {
     }
 }
 
-impl<'a> Into<MetaCreate> for &'a IssueMetadata {
-    fn into(self) -> MetaCreate {
-        let cli = &self;
+impl From<&IssueMetadata> for MetaCreate {
+    fn from(cli: &IssueMetadata) -> Self {
         let mut meta = MetaCreate::new();
 
         if let Some(ref projects) = cli.projects {
@@ -3,9 +3,11 @@ use {super::*, jira_rs::issue::options as IssueOptions};
 use grab::Input;
 
 pub mod create;
+pub mod edit;
 pub mod get;
 pub mod meta;
 pub mod search;
+pub mod watch;
 
 /// Interact with Jira issues
 #[derive(Debug, StructOpt)]
@@ -44,8 +46,13 @@ pub enum Issues {
         /// This option is aware of two special values
         /// '-' will be treated as stdin
         /// '@<pathspec>' will be treated as a filename to read the data from
-        #[structopt(short, long, value_name = "DATA")]
-        data: Input,
+        #[structopt(
+            short,
+            long,
+            value_name = "DATA",
+            required_unless_one = &["interactive", "template"]
+        )]
+        data: Option<Input>,
 
         #[structopt(flatten)]
         opts: create::IssueCreate,
@@ -60,8 +67,24 @@ pub enum Issues {
         /// This option is aware of two special values
         /// '-' will be treated as stdin
         /// '@<pathspec>' will be treated as a filename to read the data from
-        #[structopt(short, long, value_name = "DATA")]
-        data: Input,
+        #[structopt(short, long, value_name = "DATA", required_unless = "interactive")]
+        data: Option<Input>,
+
+        #[structopt(flatten)]
+        opts: edit::IssueEdit,
     },
-}
+    /// Watch the issues matched by a JQL query for changes
+    ///
+    /// Polls the query for issues updated since the last poll, and prints
+    /// each change to them, or runs --exec for it. The first poll only
+    /// records the matching issues, so changes made before the watch
+    /// started are not reported
+    Watch {
+        /// JQL query string of the issues to watch
+        #[structopt(value_name = "JQL")]
+        jql: Input,
 
+        #[structopt(flatten)]
+        opts: watch::IssuesWatch,
+    },
+}
@@ -1,5 +1,7 @@
 use {
     super::*,
+    jira_sync::LocalOptions,
+    std::path::PathBuf,
     IssueOptions::{Search, ValidateQuery},
 };
 
@@ -67,17 +69,42 @@ pub struct IssuesSearch {
     /// ['strict'], 'warn', 'none'
     #[structopt(short, long, value_name = "mode", parse(try_from_str = try_into_validate))]
     pub validate: Option<ValidateQuery>,
+
+    /// Answer from the local mirror instead of the server
+    ///
+    /// The JQL is evaluated locally, which supports AND, OR, NOT,
+    /// the =, !=, ~, !~, >, >=, <, <=, IN and IS operators, ORDER BY and
+    /// the currentUser(), now(), startOfDay() and endOfDay() functions.
+    /// See 'jira sync' for populating the mirror
+    #[structopt(
+        long,
+        conflicts_with_all = &["fields-by-key", "expand", "properties", "validate"]
+    )]
+    pub offline: bool,
+
+    /// The mirror database used by --offline
+    ///
+    /// Defaults to <cache dir>/mirror/<host>.sqlite
+    #[structopt(long, env = "JIRA_MIRROR", value_name = "PATH")]
+    pub db: Option<PathBuf>,
 }
 
 impl IssuesSearch {
     pub fn as_options(&self) -> Search {
         self.into()
     }
+
+    pub fn as_local_options(&self) -> LocalOptions {
+        LocalOptions::new().with(|this| {
+            this.fields(self.fields.iter().flatten())
+                .max_results(self.max_results)
+                .start_at(self.start_at)
+        })
+    }
 }
 
-impl<'a> Into<Search> for &'a IssuesSearch {
-    fn into(self) -> Search {
-        let cli = &self;
+impl From<&IssuesSearch> for Search {
+    fn from(cli: &IssuesSearch) -> Self {
         Search::new().with(|this| {
             this.fields_by_key(cli.fields_by_key)
                 .expand(cli.expand.as_ref().into_iter().flatten())
@@ -0,0 +1,29 @@
+use super::*;
+
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub struct IssuesWatch {
+    /// Seconds to wait between polls
+    #[structopt(short, long, value_name = "SECS", default_value = "30")]
+    pub interval: u64,
+
+    /// Command to run for each change, instead of printing it
+    ///
+    /// The command is run with 'sh -c', with the change in its environment
+    /// JIRA_ISSUE_KEY, JIRA_ISSUE_ID the changed issue
+    /// JIRA_CHANGE one of created, matched, field_changed, commented, transitioned
+    /// JIRA_FIELD the changed field, if any
+    /// JIRA_FROM, JIRA_TO the field's or status's old and new values, if any
+    /// JIRA_CHANGE_JSON the whole change
+    ///
+    /// e.g --exec 'notify-send "$JIRA_ISSUE_KEY" "$JIRA_CHANGE $JIRA_FIELD"'
+    #[structopt(short = "x", long, value_name = "CMD")]
+    pub exec: Option<String>,
+
+    /// List of fields to watch for changes
+    ///
+    /// By default, all navigable fields are watched. The status and
+    /// comments are always watched
+    #[structopt(short, long = "field", number_of_values = 1)]
+    pub fields: Option<Vec<String>>,
+}
@@ -0,0 +1,74 @@
+use {
+    super::*,
+    anyhow::{anyhow, Result},
+    serde::Serialize,
+    serde_json::{self as json, Value},
+};
+
+/// Look up the values Jira accepts for issue fields
+///
+/// Useful for validating names before submitting edits, e.g
+/// 'jira meta priorities --name High' fails unless the priority exists
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub enum Meta {
+    /// List every status, across all workflows
+    Statuses(Lookup),
+    /// List the status categories, i.e To Do, In Progress and Done
+    StatusCategories(Lookup),
+    /// List every priority, highest first
+    Priorities(Lookup),
+    /// List every resolution
+    Resolutions(Lookup),
+    /// List the issue types visible to the user
+    IssueTypes {
+        /// Only list the issue types of this project (Jira Cloud only)
+        #[structopt(short, long, value_name = "KEY/ID")]
+        project: Option<String>,
+
+        #[structopt(flatten)]
+        lookup: Lookup,
+    },
+    /// List every label in use (Jira Cloud only)
+    Labels(Lookup),
+    /// Show the instance's version and deployment type
+    ServerInfo,
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub struct Lookup {
+    /// Only return the value with this name, ignoring case, and fail
+    /// if there is none
+    #[structopt(long, value_name = "NAME")]
+    pub name: Option<String>,
+}
+
+impl Lookup {
+    /// Every value, or only the value with the given name. Values are
+    /// either objects with a name, or plain strings (e.g labels)
+    pub fn find<T>(&self, kind: &str, values: &T) -> Result<Value>
+    where
+        T: Serialize,
+    {
+        let values = json::to_value(values)?;
+        let name = match self.name {
+            Some(ref name) => name,
+            None => return Ok(values),
+        };
+
+        values
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|value| {
+                value
+                    .get("name")
+                    .unwrap_or(value)
+                    .as_str()
+                    .is_some_and(|n| n.eq_ignore_ascii_case(name))
+            })
+            .cloned()
+            .ok_or_else(|| anyhow!("No {} named '{}'", kind, name))
+    }
+}
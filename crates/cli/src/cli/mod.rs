@@ -1,11 +1,32 @@
 use structopt::{clap::ArgSettings, StructOpt};
 
 pub use {
+    auth::Auth,
+    completions::{Complete, Completions},
+    config::Config,
+    filters::Filters,
+    issues::create::IssueCreate,
     issues::meta::MetaKind,
+    issues::watch::IssuesWatch,
     issues::Issues,
+    meta::Meta,
+    release_notes::ReleaseNotes,
     root::{CliOptions, Command},
+    sync::Sync,
+    versions::Versions,
+    webhooks::Webhooks,
+    workflow::Workflow,
 };
 
+mod auth;
+mod completions;
+mod config;
+mod filters;
 mod issues;
+mod meta;
+mod release_notes;
 mod root;
-
+mod sync;
+mod versions;
+mod webhooks;
+mod workflow;
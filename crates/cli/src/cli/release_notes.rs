@@ -0,0 +1,45 @@
+use {
+    super::*,
+    crate::notes::{Grouping, NotesFormat},
+};
+
+/// Generate the release notes of a version
+///
+/// Lists the issues fixed in the version, grouped by issue type,
+/// component or label, with links to each issue.
+///
+/// --output-template and --output-template-file replace the built in formats, and are
+/// rendered once against the whole release: project, version, url,
+/// group_by, groups (each with a name and its issues) and issues. Each
+/// issue has a key, url, summary, type, status, components, labels and
+/// its raw fields
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub struct ReleaseNotes {
+    /// The project, defaults to the profile's project
+    #[structopt(short, long, value_name = "KEY")]
+    pub project: Option<String>,
+
+    /// The version to generate notes for, by name or id
+    #[structopt(long, value_name = "NAME/ID")]
+    pub version: String,
+
+    /// How issues are grouped
+    ///
+    /// Possible values
+    /// ['type'], 'component', 'label'
+    #[structopt(long, value_name = "GROUP", default_value = "type")]
+    pub group_by: Grouping,
+
+    /// The built in format to render
+    ///
+    /// Possible values
+    /// ['markdown'], 'html', 'text'
+    #[structopt(long, value_name = "FORMAT", default_value = "markdown")]
+    pub format: NotesFormat,
+
+    /// Only include the version's issues which also match this JQL,
+    /// e.g 'type != Sub-task'
+    #[structopt(long, value_name = "JQL")]
+    pub jql: Option<String>,
+}
@@ -1,19 +1,107 @@
-use {super::*, jira_rs::client::Authentication};
+use {
+    super::*,
+    crate::{
+        config::{AuthScheme, Config as ConfigFile, Profile, Settings},
+        credentials::Source,
+        output::{Format, Output, Scope, Template},
+    },
+    anyhow::{Context, Result},
+    std::{env, ffi::OsString, fs, path::PathBuf},
+};
+
+/// The hidden subcommand used by completion scripts
+const COMPLETE: &str = "__complete";
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "jira", rename_all = "kebab")]
 pub struct CliOptions {
     /// Invoke requests against this host
+    ///
+    /// Overrides the host of the selected profile
     #[structopt(env = "JIRA_HOST", short = "H", long)]
-    host: String,
+    host: Option<String>,
     /// Use these authentication credentials for command invocations
-    #[structopt(env = "JIRA_AUTH", short = "A", long, hide_env_values = true)]
-    auth: String,
+    ///
+    /// In the form <user>:<token>, overrides the credentials of the
+    /// selected profile. Prefer 'jira auth login', as this may leak
+    /// into shell history and process listings
+    #[structopt(
+        env = "JIRA_AUTH",
+        short = "A",
+        long,
+        value_name = "USER:TOKEN",
+        hide_env_values = true,
+        parse(try_from_str = parse_auth)
+    )]
+    auth: Option<(String, String)>,
+    /// Use this profile from the config file
+    ///
+    /// Defaults to the profile set by 'jira config use'
+    #[structopt(env = "JIRA_PROFILE", long, value_name = "NAME")]
+    pub profile: Option<String>,
+    /// Format of command results
+    ///
+    /// Possible values
+    /// ['json'], 'json-compact', 'ndjson', 'yaml', 'table', 'csv', 'tsv'
+    #[structopt(short, long, value_name = "FORMAT", global = true)]
+    output: Option<Format>,
+    /// Columns shown by the table, csv and tsv formats
+    ///
+    /// 'key' and 'id' refer to the issue itself, anything else
+    /// is an issue field id, e.g: key,status,assignee,summary
+    #[structopt(
+        short,
+        long,
+        value_name = "COLUMNS",
+        use_delimiter = true,
+        global = true
+    )]
+    columns: Option<Vec<String>>,
+    /// Render results with this template instead of --output
+    ///
+    /// Templates use Jinja2 syntax, e.g: '{{key}} {{fields.summary}}',
+    /// with the extra filters: date, user, truncate and color.
+    /// Within 'issues create', --template names an issue template instead
+    #[structopt(
+        short = "t",
+        long,
+        visible_alias = "template",
+        value_name = "TEMPLATE",
+        global = true,
+        conflicts_with = "output-template-file"
+    )]
+    output_template: Option<String>,
+    /// Render results with the template in this file instead of --output
+    #[structopt(
+        long,
+        visible_alias = "template-file",
+        value_name = "PATH",
+        global = true
+    )]
+    output_template_file: Option<PathBuf>,
+    /// What templates are rendered against when printing issues
+    ///
+    /// Possible values
+    /// ['issue'] render once per issue
+    /// 'response' render once, with the whole response (e.g a search)
+    #[structopt(long, value_name = "SCOPE", default_value = "issue", global = true)]
+    template_scope: Scope,
     /// Increase log verbosity
     ///
-    /// Repeat the command for higher log levels
-    #[structopt(short = "v", long = "verbosity", parse(from_occurrences = get_level))]
-    debug_level: u8,
+    /// Repeat the command for higher log levels, logs are written to stderr
+    /// -v      one line per request, with its status, latency and any retries
+    /// -vv     the above, plus request and response headers
+    /// -vvv    the above, plus request and response bodies
+    /// -vvvv   everything, including the HTTP client's internals
+    ///
+    /// RUST_LOG takes precedence when set
+    #[structopt(
+        short = "v",
+        long = "verbosity",
+        parse(from_occurrences = get_level),
+        verbatim_doc_comment
+    )]
+    pub debug_level: u8,
 
     #[structopt(subcommand)]
     pub command: Command,
@@ -21,27 +109,89 @@ pub struct CliOptions {
 
 impl CliOptions {
     pub fn new() -> Self {
-        Self::from_args()
+        Self::from_iter(remap_complete(env::args_os()))
     }
 
-    pub(crate) fn auth_user(&self) -> Option<&str> {
-        self.auth.split(':').nth(0)
+    /// The renderer for command results, given the effective settings
+    pub(crate) fn output(&self, profile: &Profile) -> Result<Output> {
+        let mut output = Output::new(
+            profile.output.unwrap_or_default(),
+            profile.columns.clone().unwrap_or_default(),
+        );
+
+        let source = match (&self.output_template, &self.output_template_file) {
+            (Some(template), _) => Some(template.clone()),
+            (None, Some(path)) => Some(
+                fs::read_to_string(path)
+                    .with_context(|| format!("Unable to read {}", path.display()))?,
+            ),
+            (None, None) => None,
+        };
+
+        if let Some(source) = source {
+            output.template(Template::new(source, self.template_scope)?);
+        }
+
+        Ok(output)
     }
 
-    pub(crate) fn auth_password(&self) -> Option<&str> {
-        self.auth.split(':').nth(1)
+    /// Resolve the effective settings for this invocation, in order of
+    /// precedence: flag > env > profile > credential store > default
+    pub(crate) fn settings(&self, config: &ConfigFile) -> Result<Settings> {
+        let mut profile = config.profile(self.profile.as_deref())?;
+        let mut source = None;
+
+        if let Some(ref host) = self.host {
+            profile.host = Some(host.clone());
+        }
+
+        if let Some(output) = self.output {
+            profile.output = Some(output);
+        }
+
+        if let Some(ref columns) = self.columns {
+            profile.columns = Some(columns.clone());
+        }
+
+        if let Some((ref user, ref token)) = self.auth {
+            profile.auth = Some(AuthScheme::Basic);
+            profile.user = Some(user.clone());
+            profile.token = Some(token.clone());
+            source = Some(Source::Flag);
+        } else if profile.token.is_some() {
+            source = Some(Source::Profile);
+        } else if let Some(host) = profile.credential_key() {
+            if let Some(credential) = profile.credential_store()?.get(host)? {
+                profile.user = Some(credential.user);
+                profile.token = Some(credential.token);
+                source = Some(Source::Store(profile.credentials.unwrap_or_default()));
+            }
+        }
+
+        Ok(Settings { profile, source })
     }
+}
 
-    pub(crate) fn authentication(&self) -> Option<Authentication> {
-        self.auth_user().and_then(|user| {
-            self.auth_password()
-                .map(|pw| Authentication::basic(user, pw))
+/// clap's completion generators split subcommand paths on '__', so the
+/// hidden '__complete' subcommand is registered as 'complete'. Only the
+/// subcommand itself is renamed, never a later argument
+fn remap_complete<I>(args: I) -> impl Iterator<Item = OsString>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    args.into_iter()
+        .enumerate()
+        .map(|(i, arg)| match arg.to_str() {
+            Some(COMPLETE) if i == 1 => OsString::from("complete"),
+            _ => arg,
         })
-    }
+}
 
-    pub(crate) fn host(&self) -> &str {
-        self.host.as_str()
-    }
+/// Parse --auth, without echoing the token on failure
+fn parse_auth(arg: &str) -> Result<(String, String), &'static str> {
+    arg.split_once(':')
+        .map(|(user, token)| (user.to_string(), token.to_string()))
+        .ok_or("expected USER:TOKEN")
 }
 
 fn get_level(o: u64) -> u8 {
@@ -55,5 +205,150 @@ fn get_level(o: u64) -> u8 {
 pub enum Command {
     #[structopt(bin_name = "jira issues")]
     Issues(Issues),
+    #[structopt(bin_name = "jira filters")]
+    Filters(Filters),
+    #[structopt(bin_name = "jira versions")]
+    Versions(Versions),
+    ReleaseNotes(ReleaseNotes),
+    #[structopt(bin_name = "jira meta")]
+    Meta(Meta),
+    #[structopt(bin_name = "jira workflow")]
+    Workflow(Workflow),
+    #[structopt(bin_name = "jira webhooks")]
+    Webhooks(Webhooks),
+    #[structopt(bin_name = "jira config")]
+    Config(Config),
+    #[structopt(bin_name = "jira auth")]
+    Auth(Auth),
+    Sync(Sync),
+    Completions(Completions),
+    #[structopt(setting = structopt::clap::AppSettings::Hidden)]
+    Complete(Complete),
 }
 
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{complete::Kind, credentials::Backend},
+    };
+
+    /// A credential helper which finds the same credential for any host
+    const STORED_HELPER: &str = "echo username=stored; echo password=s; true";
+
+    fn settings(args: &[&str], profile: &str) -> Result<Settings> {
+        let config: ConfigFile =
+            toml::from_str(&format!("default-profile = 'p'\n[profiles.p]\n{}", profile))?;
+        let args = ["jira"]
+            .iter()
+            .chain(args)
+            .chain(["issues", "get", "FOO-1"].iter());
+
+        CliOptions::from_iter_safe(args)?.settings(&config)
+    }
+
+    fn credentials(settings: &Settings) -> (Option<&str>, Option<&str>, Option<Source>) {
+        (
+            settings.profile.user.as_deref(),
+            settings.profile.token.as_deref(),
+            settings.source,
+        )
+    }
+
+    #[test]
+    fn complete_is_remapped() {
+        let remap = |args: &[&str]| -> Vec<OsString> {
+            remap_complete(args.iter().map(OsString::from)).collect()
+        };
+
+        assert_eq!(
+            remap(&["jira", "__complete", "issues", "get", ""]),
+            ["jira", "complete", "issues", "get", ""]
+        );
+        assert_eq!(
+            remap(&["jira", "issues", "search", "__complete"]),
+            ["jira", "issues", "search", "__complete"]
+        );
+        assert_eq!(
+            remap(&["jira", "__complete", "issues", "search", "__complete"]),
+            ["jira", "complete", "issues", "search", "__complete"]
+        );
+
+        // As invoked by the completion scripts
+        let opts = CliOptions::from_iter_safe(remap(&["jira", "__complete", "issues", "FOO"]));
+        assert!(matches!(
+            opts.unwrap().command,
+            Command::Complete(Complete { kind: Kind::Issues, ref prefix, refresh: false })
+                if prefix == "FOO"
+        ));
+        assert!(CliOptions::from_iter_safe(remap(&["jira", "__complete", "users"])).is_err());
+    }
+
+    #[test]
+    fn template_aliases() {
+        let parse = |args: &[&str]| CliOptions::from_iter_safe(args).unwrap();
+
+        let opts = parse(&["jira", "--template", "{{key}}", "issues", "get", "FOO-1"]);
+        assert_eq!(opts.output_template.as_deref(), Some("{{key}}"));
+
+        let opts = parse(&[
+            "jira",
+            "issues",
+            "search",
+            "--template-file",
+            "notes.j2",
+            "JQL",
+        ]);
+        assert_eq!(opts.output_template_file, Some(PathBuf::from("notes.j2")));
+
+        let opts = parse(&["jira", "issues", "create", "--template", "release"]);
+        assert!(opts.output_template.is_none());
+        assert!(matches!(
+            opts.command,
+            Command::Issues(Issues::Create { ref opts, .. })
+                if opts.template.as_deref() == Some("release")
+        ));
+    }
+
+    // JIRA_AUTH is process wide, so every case which reads it is run here
+    #[test]
+    fn credential_precedence() {
+        let profile = "host = 'https://jira.example.com'\nuser = 'profile'\ntoken = 'p'";
+        let stored = format!(
+            "host = 'https://jira.example.com'\ncredentials = 'helper'\ncredential-helper = '{}'",
+            STORED_HELPER
+        );
+
+        env::set_var("JIRA_AUTH", "env:e");
+        assert_eq!(
+            credentials(&settings(&["-A", "flag:f"], profile).unwrap()),
+            (Some("flag"), Some("f"), Some(Source::Flag))
+        );
+        assert_eq!(
+            credentials(&settings(&[], profile).unwrap()),
+            (Some("env"), Some("e"), Some(Source::Flag))
+        );
+
+        env::set_var("JIRA_AUTH", "no-separator");
+        assert!(settings(&[], profile).is_err());
+        env::remove_var("JIRA_AUTH");
+
+        assert!(settings(&["-A", "no-separator"], profile).is_err());
+        assert_eq!(
+            credentials(&settings(&[], profile).unwrap()),
+            (Some("profile"), Some("p"), Some(Source::Profile))
+        );
+        assert_eq!(
+            credentials(&settings(&[], &stored).unwrap()),
+            (
+                Some("stored"),
+                Some("s"),
+                Some(Source::Store(Backend::Helper))
+            )
+        );
+        assert_eq!(
+            credentials(&settings(&[], &stored.replace(STORED_HELPER, "true")).unwrap()),
+            (None, None, None)
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use {super::*, std::path::PathBuf};
+
+/// Mirror issues into a local SQLite database
+///
+/// The first sync of a query fetches every matching issue, later syncs
+/// only fetch the issues updated since. Deleted issues, and issues which
+/// no longer match the query, are removed by a daily reconciliation of
+/// the mirrored keys.
+///
+/// Each issue is stored as raw JSON, plus columns for the standard
+/// fields, in the 'issues' table
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub struct Sync {
+    /// The JQL query to mirror
+    ///
+    /// By default, every query previously synced into the mirror
+    #[structopt(value_name = "JQL")]
+    pub jql: Option<String>,
+
+    /// The mirror database
+    ///
+    /// Defaults to <cache dir>/mirror/<host>.sqlite
+    #[structopt(long, env = "JIRA_MIRROR", value_name = "PATH")]
+    pub db: Option<PathBuf>,
+
+    /// List of fields to mirror, by default all navigable fields
+    #[structopt(
+        short,
+        long = "field",
+        number_of_values = 1,
+        set = ArgSettings::AllowLeadingHyphen
+    )]
+    pub fields: Option<Vec<String>>,
+
+    /// Fetch every matching issue, not just those updated since the last sync
+    #[structopt(long)]
+    pub full: bool,
+
+    /// Reconcile the mirrored keys with the server's, finding deleted issues
+    #[structopt(long)]
+    pub reconcile: bool,
+
+    /// List the mirrored queries instead of syncing
+    #[structopt(long, conflicts_with_all = &["jql", "full", "reconcile", "forget"])]
+    pub list: bool,
+
+    /// Stop mirroring the query, removing issues no other query matches
+    #[structopt(long, requires = "jql", conflicts_with_all = &["full", "reconcile"])]
+    pub forget: bool,
+}
@@ -0,0 +1,59 @@
+use super::*;
+
+/// Manage a project's versions, i.e releases
+///
+/// Versions are given by name, or by id when no version has that name
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub enum Versions {
+    /// List the project's versions
+    List {
+        /// The project, defaults to the profile's project
+        #[structopt(short, long, value_name = "KEY")]
+        project: Option<String>,
+
+        /// Only list versions which are yet to be released
+        #[structopt(long)]
+        unreleased: bool,
+    },
+    /// Create a new version
+    Create {
+        /// The project, defaults to the profile's project
+        #[structopt(short, long, value_name = "KEY")]
+        project: Option<String>,
+
+        /// The name of the version, e.g 1.4.0
+        #[structopt(value_name = "NAME")]
+        name: String,
+
+        #[structopt(short, long, value_name = "TEXT")]
+        description: Option<String>,
+
+        #[structopt(long, value_name = "YYYY-MM-DD")]
+        start_date: Option<String>,
+
+        /// The planned release date
+        #[structopt(long, value_name = "YYYY-MM-DD")]
+        release_date: Option<String>,
+    },
+    /// Mark a version as released
+    ///
+    /// Versions with unresolved issues are not released unless
+    /// --allow-unresolved is given
+    Release {
+        /// The project, defaults to the profile's project
+        #[structopt(short, long, value_name = "KEY")]
+        project: Option<String>,
+
+        #[structopt(value_name = "NAME/ID")]
+        version: String,
+
+        /// The release date, defaults to today
+        #[structopt(long, value_name = "YYYY-MM-DD")]
+        date: Option<String>,
+
+        /// Release the version even if some of its issues are unresolved
+        #[structopt(long)]
+        allow_unresolved: bool,
+    },
+}
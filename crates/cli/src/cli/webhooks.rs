@@ -0,0 +1,43 @@
+use super::*;
+
+/// Work with Jira webhooks
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub enum Webhooks {
+    /// Receive webhook events locally, printing each as a line of JSON
+    ///
+    /// Intended for developing against webhooks, e.g behind a tunnel
+    /// such as ngrok. Point a webhook at this server, then inspect the
+    /// events with jq or similar:
+    ///
+    /// jira webhooks listen --event jira:issue_updated | jq .changelog
+    Listen {
+        /// The port to listen on
+        #[structopt(short, long, default_value = "8080")]
+        port: u16,
+
+        /// The address to listen on, use 0.0.0.0 for every interface
+        #[structopt(long, value_name = "ADDRESS", default_value = "127.0.0.1")]
+        bind: std::net::IpAddr,
+
+        /// The webhook's secret, events without a valid signature are
+        /// rejected when set
+        #[structopt(
+            long,
+            env = "JIRA_WEBHOOK_SECRET",
+            hide_env_values = true,
+            value_name = "SECRET"
+        )]
+        secret: Option<String>,
+
+        /// Reject requests with a larger body than this many bytes
+        #[structopt(long, value_name = "BYTES", default_value = "1048576")]
+        max_body: usize,
+
+        /// Only print events of this kind, e.g jira:issue_created
+        ///
+        /// May be given multiple times
+        #[structopt(short, long = "event", value_name = "EVENT", number_of_values = 1)]
+        events: Vec<String>,
+    },
+}
@@ -0,0 +1,32 @@
+use {super::*, crate::graph::GraphFormat};
+
+/// Inspect workflows
+#[derive(Debug, StructOpt)]
+#[structopt(rename_all = "kebab")]
+pub enum Workflow {
+    /// Show the workflow used by an issue type in a project
+    ///
+    /// By default the workflow is rendered in the --output format,
+    /// including each transition's conditions, validators and post
+    /// functions. --format renders it as a graph instead, e.g:
+    ///
+    /// jira workflow show FOO Bug --format dot | dot -Tsvg > bug.svg
+    ///
+    /// Requires Jira Cloud
+    Show {
+        /// The project, by key or id
+        #[structopt(value_name = "PROJECT")]
+        project: String,
+
+        /// The issue type, by name or id
+        #[structopt(value_name = "TYPE")]
+        issue_type: String,
+
+        /// Render the workflow as a graph
+        ///
+        /// Possible values
+        /// 'dot' (Graphviz), 'mermaid'
+        #[structopt(long, value_name = "FORMAT")]
+        format: Option<GraphFormat>,
+    },
+}
@@ -0,0 +1,468 @@
+use {
+    crate::{cli::CliOptions, config::Profile},
+    anyhow::{bail, Result},
+    jira_rs::{
+        client::Jira,
+        issue::options::{MetaCreate, Search},
+    },
+    serde::{Deserialize, Serialize},
+    serde_json as json,
+    std::{
+        collections::BTreeMap,
+        env, fmt, fs,
+        io::Write,
+        path::{Path, PathBuf},
+        str::FromStr,
+        time::{Duration, SystemTime},
+    },
+    structopt::{clap::Shell, StructOpt},
+};
+
+const BIN: &str = "jira";
+const CACHE_DIR: &str = "jira";
+const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+const RECENT_ISSUES: &str = "issuekey in issueHistory() ORDER BY lastViewed DESC";
+const RECENT_LIMIT: u32 = 50;
+const EXPAND_FIELDS: &str = "projects.issuetypes.fields";
+
+/// A kind of value which is completed by querying the instance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    /// Recently viewed issue keys
+    Issues,
+    Projects,
+    IssueTypes,
+    /// Field ids
+    Fields,
+}
+
+/// A completion, and an optional description of it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Candidate {
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Write the completion script for the given shell, including the hooks
+/// for dynamic completions where the shell supports them
+pub fn script<W>(shell: Shell, out: &mut W) -> Result<()>
+where
+    W: Write,
+{
+    let mut generated = Vec::new();
+    CliOptions::clap().gen_completions_to(BIN, shell, &mut generated);
+    let generated = String::from_utf8(generated)?;
+
+    match shell {
+        Shell::Bash => write!(out, "{}{}", generated, BASH)?,
+        Shell::Fish => write!(out, "{}{}", generated, FISH)?,
+        // The generated script ends by invoking itself, which must happen
+        // after the dynamic completions are hooked in
+        Shell::Zsh => {
+            let generated = generated.trim_end();
+            let generated = generated.strip_suffix(ZSH_INVOKE).unwrap_or(generated);
+            write!(out, "{}{}", generated, ZSH)?
+        }
+        Shell::PowerShell | Shell::Elvish => out.write_all(generated.as_bytes())?,
+    }
+
+    Ok(())
+}
+
+/// Find the candidates of the given kind starting with `prefix`, from the
+/// cache if it is fresh enough
+pub async fn candidates(
+    profile: &Profile,
+    kind: Kind,
+    prefix: &str,
+    refresh: bool,
+) -> Result<Vec<Candidate>> {
+    Cache::new(&cache_dir()?, profile, kind)
+        .candidates(profile, prefix, refresh)
+        .await
+}
+
+async fn query(client: &Jira, profile: &Profile, kind: Kind) -> Result<Vec<Candidate>> {
+    let mut candidates = BTreeMap::new();
+
+    match kind {
+        Kind::Issues => {
+            let options = Search::new().with(|this| {
+                this.jql(RECENT_ISSUES)
+                    .fields(Some("summary"))
+                    .max_results(RECENT_LIMIT)
+            });
+            let search = client.issues().search(Some(&options)).await?;
+
+            // Keep the order of the search, most recently viewed first
+            return Ok(search
+                .data()
+                .issues
+                .iter()
+                .map(|issue| Candidate {
+                    value: issue.key.to_string(),
+                    description: issue
+                        .fields
+                        .get("summary")
+                        .and_then(|raw| json::from_str(raw.get()).ok()),
+                })
+                .collect());
+        }
+        Kind::Projects => {
+            let meta = client.issues().meta_create(None).await?;
+
+            for project in meta.data().projects.iter() {
+                candidates.insert(
+                    project.project.key.to_string(),
+                    project.project.name.to_string(),
+                );
+            }
+        }
+        Kind::IssueTypes | Kind::Fields => {
+            let mut options = MetaCreate::new();
+            if let Some(ref project) = profile.project {
+                options.project_keys(Some(project));
+            }
+            if kind == Kind::Fields {
+                options.expand(Some(EXPAND_FIELDS));
+            }
+            let meta = client.issues().meta_create(Some(&options)).await?;
+
+            for issue_type in meta
+                .data()
+                .projects
+                .iter()
+                .flat_map(|p| p.issue_types.iter())
+            {
+                match kind {
+                    Kind::IssueTypes => {
+                        let name = &issue_type.issue_type;
+                        candidates.insert(name.name.to_string(), name.description.to_string());
+                    }
+                    _ => {
+                        for field in issue_type.fields.iter().flat_map(|f| f.values()) {
+                            candidates.insert(field.field_id.to_string(), field.name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(candidates
+        .into_iter()
+        .map(|(value, description)| Candidate {
+            value,
+            description: Some(description).filter(|d| !d.is_empty()),
+        })
+        .collect())
+}
+
+/// Candidates cached per host and kind, under $XDG_CACHE_HOME/jira
+struct Cache {
+    path: PathBuf,
+    kind: Kind,
+}
+
+impl Cache {
+    fn new(dir: &Path, profile: &Profile, kind: Kind) -> Self {
+        let path = dir
+            .join("completions")
+            .join(profile.host_slug())
+            .join(kind.as_str())
+            .with_extension("json");
+
+        Self { path, kind }
+    }
+
+    async fn candidates(
+        &self,
+        profile: &Profile,
+        prefix: &str,
+        refresh: bool,
+    ) -> Result<Vec<Candidate>> {
+        let candidates = match self.load().filter(|_| !refresh) {
+            Some(candidates) => candidates,
+            None => {
+                let candidates = query(&profile.client()?, profile, self.kind).await?;
+                // Completions still work without a cache, so ignore failures
+                let _ = self.store(&candidates);
+                candidates
+            }
+        };
+
+        let prefix = prefix.to_lowercase();
+        Ok(candidates
+            .into_iter()
+            .filter(|c| c.value.to_lowercase().starts_with(&prefix))
+            .collect())
+    }
+
+    fn load(&self) -> Option<Vec<Candidate>> {
+        let modified = fs::metadata(&self.path).and_then(|m| m.modified()).ok()?;
+        let age = SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default();
+
+        if age > CACHE_TTL {
+            return None;
+        }
+
+        json::from_slice(&fs::read(&self.path).ok()?).ok()
+    }
+
+    fn store(&self, candidates: &[Candidate]) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        Ok(fs::write(&self.path, json::to_vec(candidates)?)?)
+    }
+}
+
+/// The CLI's cache directory, $XDG_CACHE_HOME/jira or ~/.cache/jira
+pub fn cache_dir() -> Result<PathBuf> {
+    let base = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")));
+
+    match base {
+        Some(base) => Ok(base.join(CACHE_DIR)),
+        None => bail!("Unable to locate a cache directory, set XDG_CACHE_HOME"),
+    }
+}
+
+impl Kind {
+    pub const ALL: [Kind; 4] = [Kind::Issues, Kind::Projects, Kind::IssueTypes, Kind::Fields];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Kind::Issues => "issues",
+            Kind::Projects => "projects",
+            Kind::IssueTypes => "issue-types",
+            Kind::Fields => "fields",
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Kind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Kind::ALL
+            .iter()
+            .copied()
+            .find(|k| k.as_str() == s)
+            .ok_or_else(|| {
+                let kinds: Vec<_> = Kind::ALL.iter().map(Kind::as_str).collect();
+                format!("expected one of [{}], got '{}'", kinds.join(", "), s)
+            })
+    }
+}
+
+const ZSH_INVOKE: &str = r#"_jira "$@""#;
+
+const BASH: &str = r#"
+_jira_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}"
+    local sub="" kind="" i
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        [[ ${COMP_WORDS[i-1]} == issues ]] && sub="${COMP_WORDS[i]}"
+    done
+
+    case "${sub}:${prev}" in
+        get:--field|get:-f|search:--field|search:-f) kind=fields ;;
+        meta:--projects|meta:-P|create:--project|create:-p) kind=projects ;;
+        meta:--issue-types|meta:-I|create:--type|create:-T) kind=issue-types ;;
+        meta:--edit|meta:-E|get:get|edit:edit) kind=issues ;;
+    esac
+
+    if [[ -n ${kind} ]]; then
+        local IFS=$'\n'
+        COMPREPLY=( $(jira __complete "${kind}" "${cur}" 2>/dev/null | cut -f1) )
+        return 0
+    fi
+
+    _jira "$@"
+}
+
+complete -F _jira_dynamic -o bashdefault -o default jira
+"#;
+
+const FISH: &str = r#"
+function __jira_complete
+    jira __complete $argv (commandline -ct) 2>/dev/null
+end
+
+complete -c jira -n "__fish_seen_subcommand_from get search" -s f -l field -x -a "(__jira_complete fields)"
+complete -c jira -n "__fish_seen_subcommand_from meta" -s P -l projects -x -a "(__jira_complete projects)"
+complete -c jira -n "__fish_seen_subcommand_from meta" -s I -l issue-types -x -a "(__jira_complete issue-types)"
+complete -c jira -n "__fish_seen_subcommand_from meta" -s E -l edit -x -a "(__jira_complete issues)"
+complete -c jira -n "__fish_seen_subcommand_from create" -s p -l project -x -a "(__jira_complete projects)"
+complete -c jira -n "__fish_seen_subcommand_from create" -s T -l type -x -a "(__jira_complete issue-types)"
+complete -c jira -n "__fish_seen_subcommand_from issues; and __fish_seen_subcommand_from get edit" -f -a "(__jira_complete issues)"
+"#;
+
+const ZSH: &str = r#"
+
+_jira_dynamic() {
+    local sub="" kind="" i
+    for ((i = 2; i < CURRENT; i++)); do
+        [[ ${words[i-1]} == issues ]] && sub=${words[i]}
+    done
+
+    case "${sub}:${words[CURRENT-1]}" in
+        (get:--field|get:-f|search:--field|search:-f) kind=fields ;;
+        (meta:--projects|meta:-P|create:--project|create:-p) kind=projects ;;
+        (meta:--issue-types|meta:-I|create:--type|create:-T) kind=issue-types ;;
+        (meta:--edit|meta:-E|get:get|edit:edit) kind=issues ;;
+    esac
+
+    if [[ -n ${kind} ]]; then
+        local -a candidates
+        candidates=(${(f)"$(jira __complete ${kind} ${words[CURRENT]} 2>/dev/null | sed -e 's/:/\\:/g' -e 's/\t/:/')"})
+        _describe -t ${kind} ${kind} candidates
+        return
+    fi
+
+    _jira "$@"
+}
+
+compdef _jira_dynamic jira
+_jira_dynamic "$@"
+"#;
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{fs::File, process::Command},
+        tempfile::TempDir,
+    };
+
+    const SHELLS: [Shell; 3] = [Shell::Bash, Shell::Zsh, Shell::Fish];
+
+    fn generate(shell: Shell) -> String {
+        let mut out = Vec::new();
+        script(shell, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn issue(key: &str, summary: &str) -> Candidate {
+        Candidate {
+            value: key.to_string(),
+            description: Some(summary.to_string()),
+        }
+    }
+
+    fn values(candidates: &[Candidate]) -> Vec<&str> {
+        candidates.iter().map(|c| c.value.as_str()).collect()
+    }
+
+    #[test]
+    fn scripts_complete_subcommands() {
+        for shell in SHELLS.iter().copied() {
+            let script = generate(shell);
+
+            for subcommand in ["issues", "filters", "completions", "release-notes"].iter() {
+                assert!(
+                    script.contains(subcommand),
+                    "{} lacks {}",
+                    shell,
+                    subcommand
+                );
+            }
+            assert!(
+                script.contains("jira __complete"),
+                "{} lacks the hook",
+                shell
+            );
+        }
+
+        // The generated invocation is replaced by the dynamic one
+        let zsh = generate(Shell::Zsh);
+        assert!(zsh.starts_with("#compdef jira"));
+        assert!(!zsh.lines().any(|line| line == ZSH_INVOKE));
+        assert_eq!(zsh.lines().last(), Some(r#"_jira_dynamic "$@""#));
+    }
+
+    #[test]
+    fn scripts_parse() {
+        let dir = TempDir::new().unwrap();
+
+        for shell in SHELLS.iter().copied() {
+            let path = dir.path().join(format!("jira.{}", shell));
+            fs::write(&path, generate(shell)).unwrap();
+
+            // Only checked where the shell is installed
+            match Command::new(shell.to_string())
+                .arg("-n")
+                .arg(&path)
+                .output()
+            {
+                Ok(out) => assert!(
+                    out.status.success(),
+                    "{} -n failed: {}",
+                    shell,
+                    String::from_utf8_lossy(&out.stderr)
+                ),
+                Err(e) => eprintln!("skipping {}: {}", shell, e),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn cached_candidates() {
+        let dir = TempDir::new().unwrap();
+        // Without credentials, any query fails
+        let profile = Profile {
+            host: Some("https://jira.example.com".into()),
+            ..Profile::default()
+        };
+        let cache = Cache::new(dir.path(), &profile, Kind::Issues);
+        cache
+            .store(&[
+                issue("FOO-12", "Recently viewed"),
+                issue("FOO-1", "Viewed before"),
+                issue("BAR-3", "Another project"),
+            ])
+            .unwrap();
+
+        let find = |prefix| cache.candidates(&profile, prefix, false);
+        assert_eq!(
+            values(&find("").await.unwrap()),
+            ["FOO-12", "FOO-1", "BAR-3"]
+        );
+        assert_eq!(values(&find("foo-1").await.unwrap()), ["FOO-12", "FOO-1"]);
+        assert_eq!(values(&find("BAR").await.unwrap()), ["BAR-3"]);
+        assert!(find("BAZ").await.unwrap().is_empty());
+
+        // Refreshing, or a stale cache, queries the instance
+        assert!(cache.candidates(&profile, "", true).await.is_err());
+
+        let stale = SystemTime::now() - CACHE_TTL - Duration::from_secs(60);
+        File::options()
+            .write(true)
+            .open(&cache.path)
+            .and_then(|file| file.set_modified(stale))
+            .unwrap();
+        assert!(find("").await.is_err());
+    }
+
+    #[test]
+    fn unknown_kind() {
+        assert_eq!("issue-types".parse(), Ok(Kind::IssueTypes));
+        assert_eq!(
+            "users".parse::<Kind>(),
+            Err("expected one of [issues, projects, issue-types, fields], got 'users'".into())
+        );
+    }
+}
@@ -0,0 +1,408 @@
+use {
+    crate::{
+        cli::Config as ConfigCmd,
+        complete::cache_dir,
+        credentials::{write_private, Backend, CredentialStore, Source},
+        output::Format,
+    },
+    anyhow::{anyhow, bail, Context, Result},
+    jira_rs::{
+        cache::ResponseCache,
+        client::{Authentication, Jira},
+    },
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::BTreeMap,
+        env, fmt, fs,
+        path::{Path, PathBuf},
+        str::FromStr,
+    },
+};
+
+const CONFIG_ENV: &str = "JIRA_CONFIG";
+const CONFIG_DIR: &str = "jira";
+const CONFIG_FILE: &str = "config.toml";
+const RESPONSE_CACHE: &str = "responses";
+
+/// The CLI's configuration file, typically ~/.config/jira/config.toml
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Config {
+    /// The profile used when none is selected via --profile or JIRA_PROFILE
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+/// A named set of defaults for a single Jira instance
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Profile {
+    /// The Jira instance to invoke requests against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// The authentication scheme to use, currently only 'basic'
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthScheme>,
+    /// The user to authenticate as
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// The password or API token to authenticate with, prefer
+    /// storing this in one of the credential stores
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Where the credentials for this profile's host are stored,
+    /// defaults to 'file'
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<Backend>,
+    /// The command run by the 'helper' credential store
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_helper: Option<String>,
+    /// The project used when a command needs one and none was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// The fields returned when none are given via --field
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<String>>,
+    /// The output format used when none is given via --output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<Format>,
+    /// The columns used by table and CSV output when none are
+    /// given via --columns
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Vec<String>>,
+    /// The number of results to request per page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+    /// Cache rarely changing responses, like create and edit metadata,
+    /// on disk between invocations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuthScheme {
+    Basic,
+}
+
+/// The effective settings of an invocation
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub profile: Profile,
+    /// Where the profile's credentials came from, if any were found
+    pub source: Option<Source>,
+}
+
+/// A settable key in a profile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Key {
+    Host,
+    Auth,
+    User,
+    Token,
+    Credentials,
+    CredentialHelper,
+    Project,
+    Fields,
+    Output,
+    Columns,
+    PageSize,
+    Cache,
+}
+
+impl Config {
+    /// Load the config file, returning an empty config if it does not exist
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+
+        let mut config: Self = match fs::read_to_string(&path) {
+            Ok(data) => toml::from_str(&data)
+                .with_context(|| format!("Invalid config file: {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e).with_context(|| format!("Unable to read {}", path.display())),
+        };
+        config.path = path;
+
+        Ok(config)
+    }
+
+    /// Write the config back to the file it was loaded from
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Unable to create {}", parent.display()))?;
+        }
+
+        // Profiles may hold an API token
+        let data = toml::to_string_pretty(self)?;
+        write_private(&self.path, data.as_bytes())
+            .with_context(|| format!("Unable to write {}", self.path.display()))
+    }
+
+    /// The location of the config file, in order of preference:
+    /// $JIRA_CONFIG, $XDG_CONFIG_HOME/jira/config.toml, ~/.config/jira/config.toml
+    pub fn path() -> Result<PathBuf> {
+        if let Some(path) = env::var_os(CONFIG_ENV) {
+            return Ok(path.into());
+        }
+
+        let base = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .ok_or_else(|| anyhow!("Unable to locate a config directory, set {}", CONFIG_ENV))?;
+
+        Ok(base.join(CONFIG_DIR).join(CONFIG_FILE))
+    }
+
+    pub fn location(&self) -> &Path {
+        &self.path
+    }
+
+    /// The name of the active profile, if any
+    pub fn active<'a>(&'a self, selected: Option<&'a str>) -> Option<&'a str> {
+        selected.or(self.default_profile.as_deref())
+    }
+
+    /// Lookup the active profile, failing only if a profile was explicitly
+    /// selected but does not exist
+    pub fn profile(&self, selected: Option<&str>) -> Result<Profile> {
+        match self.active(selected) {
+            Some(name) => self
+                .profiles
+                .get(name)
+                .cloned()
+                .ok_or_else(|| anyhow!("No such profile '{}' in {}", name, self.path.display())),
+            None => Ok(Profile::default()),
+        }
+    }
+
+    pub fn profile_mut(&mut self, name: &str) -> &mut Profile {
+        self.profiles.entry(name.to_string()).or_default()
+    }
+}
+
+pub fn run(cmd: &ConfigCmd, config: &mut Config, selected: Option<&str>) -> Result<()> {
+    let active = config.active(selected).map(str::to_string);
+    let no_profile = || anyhow!("No profile selected, use --profile or 'jira config use'");
+
+    match cmd {
+        ConfigCmd::List => {
+            println!("# {}", config.location().display());
+            for (name, profile) in config.profiles.iter() {
+                let marker = if Some(name) == active.as_ref() {
+                    "*"
+                } else {
+                    " "
+                };
+                println!("{} {}", marker, name);
+
+                for key in Key::ALL.iter() {
+                    if let Some(value) = profile.get(*key) {
+                        println!("    {} = {}", key.as_str(), value)
+                    }
+                }
+            }
+        }
+        ConfigCmd::Get { key } => {
+            let profile = config.profile(Some(active.as_deref().ok_or_else(no_profile)?))?;
+
+            if let Some(value) = profile.get(*key) {
+                println!("{}", value)
+            }
+        }
+        ConfigCmd::Set { key, value } => {
+            let name = active.ok_or_else(no_profile)?;
+
+            config.profile_mut(&name).set(*key, value)?;
+            config.save()?;
+        }
+        ConfigCmd::Use { profile } => {
+            if !config.profiles.contains_key(profile) {
+                return Err(anyhow!(
+                    "No such profile '{}', create it with 'jira --profile {} config set host <HOST>'",
+                    profile,
+                    profile
+                ));
+            }
+
+            config.default_profile = Some(profile.clone());
+            config.save()?;
+        }
+    }
+
+    Ok(())
+}
+
+impl Profile {
+    /// Build a client from this profile's host and credentials
+    pub fn client(&self) -> Result<Jira> {
+        let host = self.host.as_deref().ok_or_else(|| {
+            anyhow!("No Jira host configured, use --host, JIRA_HOST or a config profile")
+        })?;
+
+        let auth = match (
+            self.auth.unwrap_or(AuthScheme::Basic),
+            &self.user,
+            &self.token,
+        ) {
+            (AuthScheme::Basic, Some(user), Some(token)) => Authentication::basic(user, token),
+            _ => bail!("Unable to locate authentication"),
+        };
+
+        let mut client = Jira::new(host, auth)?;
+        if self.cache.unwrap_or(false) {
+            client.cache(ResponseCache::disk(cache_dir()?.join(RESPONSE_CACHE)));
+        }
+
+        Ok(client)
+    }
+
+    /// Open the credential store configured for this profile
+    pub fn credential_store(&self) -> Result<Box<dyn CredentialStore>> {
+        self.credentials
+            .unwrap_or_default()
+            .open(self.credential_helper.as_deref())
+    }
+
+    /// The host credentials are stored under
+    pub fn credential_key(&self) -> Option<&str> {
+        self.host.as_deref().map(|host| host.trim_end_matches('/'))
+    }
+
+    /// This profile's host, made safe for use in a file name
+    pub fn host_slug(&self) -> String {
+        self.credential_key()
+            .unwrap_or_default()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
+    pub fn get(&self, key: Key) -> Option<String> {
+        match key {
+            Key::Host => self.host.clone(),
+            Key::Auth => self.auth.map(|a| a.to_string()),
+            Key::User => self.user.clone(),
+            Key::Token => self.token.as_ref().map(|_| "<hidden>".to_string()),
+            Key::Credentials => self.credentials.map(|c| c.to_string()),
+            Key::CredentialHelper => self.credential_helper.clone(),
+            Key::Project => self.project.clone(),
+            Key::Fields => self.fields.as_ref().map(|f| f.join(",")),
+            Key::Output => self.output.map(|o| o.to_string()),
+            Key::Columns => self.columns.as_ref().map(|c| c.join(",")),
+            Key::PageSize => self.page_size.map(|p| p.to_string()),
+            Key::Cache => self.cache.map(|c| c.to_string()),
+        }
+    }
+
+    /// Set the given key, an empty value unsets the key
+    pub fn set(&mut self, key: Key, value: &str) -> Result<()> {
+        let value = Some(value).filter(|v| !v.is_empty());
+
+        match key {
+            Key::Host => self.host = value.map(Into::into),
+            Key::Auth => self.auth = value.map(str::parse).transpose()?,
+            Key::User => self.user = value.map(Into::into),
+            Key::Token => self.token = value.map(Into::into),
+            Key::Credentials => self.credentials = value.map(str::parse).transpose()?,
+            Key::CredentialHelper => self.credential_helper = value.map(Into::into),
+            Key::Project => self.project = value.map(Into::into),
+            Key::Fields => self.fields = value.map(split_list),
+            Key::Output => self.output = value.map(str::parse).transpose()?,
+            Key::Columns => self.columns = value.map(split_list),
+            Key::PageSize => {
+                self.page_size = value
+                    .map(u32::from_str)
+                    .transpose()
+                    .context("page-size must be a positive integer")?
+            }
+            Key::Cache => {
+                self.cache = value
+                    .map(bool::from_str)
+                    .transpose()
+                    .context("cache must be 'true' or 'false'")?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Key {
+    pub const ALL: [Key; 12] = [
+        Key::Host,
+        Key::Auth,
+        Key::User,
+        Key::Token,
+        Key::Credentials,
+        Key::CredentialHelper,
+        Key::Project,
+        Key::Fields,
+        Key::Output,
+        Key::Columns,
+        Key::PageSize,
+        Key::Cache,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Key::Host => "host",
+            Key::Auth => "auth",
+            Key::User => "user",
+            Key::Token => "token",
+            Key::Credentials => "credentials",
+            Key::CredentialHelper => "credential-helper",
+            Key::Project => "project",
+            Key::Fields => "fields",
+            Key::Output => "output",
+            Key::Columns => "columns",
+            Key::PageSize => "page-size",
+            Key::Cache => "cache",
+        }
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value.split(',').map(|v| v.trim().to_string()).collect()
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Key::ALL
+            .iter()
+            .copied()
+            .find(|k| k.as_str() == s)
+            .ok_or_else(|| {
+                let keys: Vec<_> = Key::ALL.iter().map(Key::as_str).collect();
+                format!("expected one of [{}], got '{}'", keys.join(", "), s)
+            })
+    }
+}
+
+impl fmt::Display for AuthScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthScheme::Basic => f.write_str("basic"),
+        }
+    }
+}
+
+impl FromStr for AuthScheme {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "basic" => Ok(AuthScheme::Basic),
+            _ => bail!("expected one of [basic], got '{}'", s),
+        }
+    }
+}
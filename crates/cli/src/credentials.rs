@@ -0,0 +1,538 @@
+use {
+    crate::config::Config,
+    anyhow::{anyhow, bail, Context, Result},
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::BTreeMap,
+        env, fmt, fs,
+        io::{self, ErrorKind, Write},
+        path::{Path, PathBuf},
+        process::{Command, Stdio},
+    },
+};
+
+const SERVICE: &str = "jira-cli";
+const SECRET_TOOL_ENV: &str = "JIRA_SECRET_TOOL";
+const SECRET_TOOL: &str = "secret-tool";
+const CREDENTIALS_FILE: &str = "credentials.toml";
+
+/// A user and the password or API token they authenticate with
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Credential {
+    pub user: String,
+    pub token: String,
+}
+
+/// Where a profile's credentials are stored
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Backend {
+    /// The desktop keyring, via libsecret's Secret Service
+    Keyring,
+    /// A file next to the config file, readable only by its owner
+    #[default]
+    File,
+    /// An external command, speaking git's credential helper protocol
+    Helper,
+}
+
+/// Where the credentials of an invocation came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    Flag,
+    Profile,
+    Store(Backend),
+}
+
+/// A place credentials can be retrieved from, saved to and erased from,
+/// keyed by the Jira host they belong to
+pub trait CredentialStore {
+    fn get(&self, host: &str) -> Result<Option<Credential>>;
+
+    fn store(&self, host: &str, credential: &Credential) -> Result<()>;
+
+    fn erase(&self, host: &str) -> Result<()>;
+}
+
+impl Backend {
+    /// Open this backend, `helper` is the command used by `Backend::Helper`
+    pub fn open(self, helper: Option<&str>) -> Result<Box<dyn CredentialStore>> {
+        Ok(match self {
+            Backend::Keyring => Box::new(Keyring::new()),
+            Backend::File => Box::new(FileStore::new()?),
+            Backend::Helper => {
+                let command = helper.ok_or_else(|| {
+                    anyhow!("The 'helper' credential store requires credential-helper to be set")
+                })?;
+                Box::new(Helper::new(command))
+            }
+        })
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Backend::Keyring => "keyring",
+            Backend::File => "file",
+            Backend::Helper => "helper",
+        })
+    }
+}
+
+impl std::str::FromStr for Backend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "keyring" => Ok(Backend::Keyring),
+            "file" => Ok(Backend::File),
+            "helper" => Ok(Backend::Helper),
+            _ => bail!("expected one of [keyring, file, helper], got '{}'", s),
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Flag => f.write_str("--auth / JIRA_AUTH"),
+            Source::Profile => f.write_str("config profile"),
+            Source::Store(Backend::Keyring) => f.write_str("keyring"),
+            Source::Store(Backend::File) => f.write_str("credentials file"),
+            Source::Store(Backend::Helper) => f.write_str("credential helper"),
+        }
+    }
+}
+
+/// The Secret Service keyring, accessed through libsecret's `secret-tool`.
+///
+/// The program may be overridden with JIRA_SECRET_TOOL, e.g to use a
+/// stand-in that speaks the same command line interface
+pub struct Keyring {
+    program: PathBuf,
+}
+
+impl Keyring {
+    pub fn new() -> Self {
+        let program = env::var_os(SECRET_TOOL_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| SECRET_TOOL.into());
+
+        Self::with_program(program)
+    }
+
+    /// Use this program in place of secret-tool
+    fn with_program(program: impl Into<PathBuf>) -> Self {
+        Self {
+            program: program.into(),
+        }
+    }
+
+    fn run(&self, args: &[&str], input: Option<&str>) -> Result<Option<String>> {
+        let mut child = Command::new(&self.program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| {
+                format!(
+                    "Unable to run {}, is libsecret installed?",
+                    self.program.display()
+                )
+            })?;
+
+        if let Some(input) = input {
+            child
+                .stdin
+                .take()
+                .expect("stdin is piped")
+                .write_all(input.as_bytes())?;
+        }
+
+        let output = child.wait_with_output()?;
+
+        // secret-tool exits with 1 and no output at all if nothing matched,
+        // anything else (e.g no D-Bus session, a locked keyring) is a failure
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        match output.status.code() {
+            Some(0) => Ok(Some(String::from_utf8(output.stdout)?)),
+            Some(1) if output.stdout.is_empty() && stderr.trim().is_empty() => Ok(None),
+            _ if stderr.trim().is_empty() => {
+                bail!("{} failed: {}", self.program.display(), output.status)
+            }
+            _ => bail!(
+                "{} failed: {}: {}",
+                self.program.display(),
+                output.status,
+                stderr.trim()
+            ),
+        }
+    }
+}
+
+impl CredentialStore for Keyring {
+    fn get(&self, host: &str) -> Result<Option<Credential>> {
+        let secret = self.run(&["lookup", "service", SERVICE, "host", host], None)?;
+
+        Ok(secret.as_deref().and_then(|secret| {
+            secret.split_once(':').map(|(user, token)| Credential {
+                user: user.to_string(),
+                token: token.trim_end_matches('\n').to_string(),
+            })
+        }))
+    }
+
+    fn store(&self, host: &str, credential: &Credential) -> Result<()> {
+        let label = format!("Jira credentials for {}", host);
+        let secret = format!("{}:{}", credential.user, credential.token);
+
+        self.run(
+            &["store", "--label", &label, "service", SERVICE, "host", host],
+            Some(&secret),
+        )
+        .map(drop)
+    }
+
+    fn erase(&self, host: &str) -> Result<()> {
+        self.run(&["clear", "service", SERVICE, "host", host], None)
+            .map(drop)
+    }
+}
+
+/// A TOML file of host -> credential, which must only be accessible
+/// by its owner
+pub struct FileStore {
+    path: PathBuf,
+}
+
+impl FileStore {
+    pub fn new() -> Result<Self> {
+        let path = Config::path()?.with_file_name(CREDENTIALS_FILE);
+
+        Ok(Self::at(path))
+    }
+
+    fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn read(&self) -> Result<BTreeMap<String, Credential>> {
+        let data = match fs::read_to_string(&self.path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Unable to read {}", self.path.display()))
+            }
+        };
+
+        self.check_permissions()?;
+
+        toml::from_str(&data)
+            .with_context(|| format!("Invalid credentials file: {}", self.path.display()))
+    }
+
+    fn write(&self, credentials: &BTreeMap<String, Credential>) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let data = toml::to_string_pretty(credentials)?;
+
+        write_private(&self.path, data.as_bytes())
+            .with_context(|| format!("Unable to write {}", self.path.display()))
+    }
+
+    #[cfg(unix)]
+    fn check_permissions(&self) -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(&self.path)?.permissions().mode();
+        if mode & 0o077 != 0 {
+            bail!(
+                "{} is accessible by other users (mode {:o}), run: chmod 600 {}",
+                self.path.display(),
+                mode & 0o777,
+                self.path.display()
+            )
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn check_permissions(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl CredentialStore for FileStore {
+    fn get(&self, host: &str) -> Result<Option<Credential>> {
+        Ok(self.read()?.remove(host))
+    }
+
+    fn store(&self, host: &str, credential: &Credential) -> Result<()> {
+        let mut credentials = self.read()?;
+        credentials.insert(host.to_string(), credential.clone());
+
+        self.write(&credentials)
+    }
+
+    fn erase(&self, host: &str) -> Result<()> {
+        let mut credentials = self.read()?;
+
+        match credentials.remove(host) {
+            Some(_) => self.write(&credentials),
+            None => Ok(()),
+        }
+    }
+}
+
+/// An external command in the style of git's `credential.helper`.
+///
+/// The command is run through the shell with one of `get`, `store` or
+/// `erase` appended, and is sent `key=value` lines describing the host
+/// (and credential, when storing) on stdin. For `get` it should reply
+/// with `username=<user>` and `password=<token>` lines on stdout
+pub struct Helper {
+    command: String,
+}
+
+impl Helper {
+    pub fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+        }
+    }
+
+    fn run(&self, action: &str, input: &str) -> Result<String> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(format!("{} {}", self.command, action))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Unable to run credential helper '{}'", self.command))?;
+
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        match stdin.write_all(input.as_bytes()) {
+            // Helpers are free to ignore their input
+            Err(e) if e.kind() == ErrorKind::BrokenPipe => {}
+            result => result?,
+        }
+        drop(stdin);
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!(
+                "Credential helper '{}' failed: {}",
+                self.command,
+                output.status
+            )
+        }
+
+        Ok(String::from_utf8(output.stdout)?)
+    }
+
+    fn describe(host: &str) -> String {
+        let (protocol, host) = host.split_once("://").unwrap_or(("https", host));
+
+        format!(
+            "protocol={}\nhost={}\n",
+            protocol,
+            host.trim_end_matches('/')
+        )
+    }
+}
+
+impl CredentialStore for Helper {
+    fn get(&self, host: &str) -> Result<Option<Credential>> {
+        let output = self.run("get", &format!("{}\n", Self::describe(host)))?;
+
+        let mut user = None;
+        let mut token = None;
+        for line in output.lines() {
+            match line.split_once('=') {
+                Some(("username", value)) => user = Some(value.to_string()),
+                Some(("password", value)) => token = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        Ok(user
+            .zip(token)
+            .map(|(user, token)| Credential { user, token }))
+    }
+
+    fn store(&self, host: &str, credential: &Credential) -> Result<()> {
+        let input = format!(
+            "{}username={}\npassword={}\n\n",
+            Self::describe(host),
+            credential.user,
+            credential.token
+        );
+
+        self.run("store", &input).map(drop)
+    }
+
+    fn erase(&self, host: &str) -> Result<()> {
+        self.run("erase", &format!("{}\n", Self::describe(host)))
+            .map(drop)
+    }
+}
+
+/// Write a file readable only by the current user, replacing its contents
+pub(crate) fn write_private(path: &Path, data: &[u8]) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(path)?;
+
+    // The mode above only applies to newly created files
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+
+    file.write_all(data)
+}
+
+// The stand-in stores are shell scripts
+#[cfg(all(test, unix))]
+mod tests {
+    use {super::*, std::os::unix::fs::PermissionsExt, tempfile::TempDir};
+
+    const HOST: &str = "https://jira.example.com";
+
+    fn credential() -> Credential {
+        Credential {
+            user: "ann".into(),
+            token: "s3cret".into(),
+        }
+    }
+
+    /// Write an executable shell script, standing in for a real store
+    fn script(dir: &TempDir, body: &str) -> PathBuf {
+        let path = dir.path().join("stub");
+        fs::write(
+            &path,
+            format!("#!/bin/sh\ncd \"$(dirname \"$0\")\"\n{}", body),
+        )
+        .unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        path
+    }
+
+    /// Store, get and erase a credential, checking other hosts are unaffected
+    fn round_trip(store: &dyn CredentialStore) {
+        assert_eq!(store.get(HOST).unwrap(), None);
+
+        store.store(HOST, &credential()).unwrap();
+        assert_eq!(store.get(HOST).unwrap(), Some(credential()));
+
+        store.erase(HOST).unwrap();
+        assert_eq!(store.get(HOST).unwrap(), None);
+    }
+
+    #[test]
+    fn file_store() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("jira").join(CREDENTIALS_FILE);
+        let store = FileStore::at(path.clone());
+
+        store
+            .store("https://other.example.com", &credential())
+            .unwrap();
+        round_trip(&store);
+        assert!(store.get("https://other.example.com").unwrap().is_some());
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let error = store.get(HOST).unwrap_err();
+        assert!(error.to_string().contains("chmod 600"), "{}", error);
+    }
+
+    #[test]
+    fn keyring() {
+        let dir = TempDir::new().unwrap();
+        // secret-tool's interface: store reads the secret from stdin, and
+        // lookup exits 1 with no output when nothing matches
+        let program = script(
+            &dir,
+            r#"echo "$@" >> args
+case "$1" in
+    store) cat > secret ;;
+    lookup) cat secret 2>/dev/null || exit 1 ;;
+    clear) rm -f secret ;;
+esac"#,
+        );
+
+        round_trip(&Keyring::with_program(program));
+
+        let args = fs::read_to_string(dir.path().join("args")).unwrap();
+        let stored = format!(
+            "store --label Jira credentials for {0} service jira-cli host {0}",
+            HOST
+        );
+        assert!(args.contains(&stored), "{}", args);
+
+        // Failures are not mistaken for a missing credential
+        let locked = script(
+            &dir,
+            "echo 'secret-tool: Cannot create an item in a locked collection' >&2; exit 1",
+        );
+        let error = Keyring::with_program(locked).get(HOST).unwrap_err();
+        assert!(error.to_string().contains("locked collection"), "{}", error);
+
+        let crashed = script(&dir, "exit 2");
+        assert!(Keyring::with_program(crashed).get(HOST).is_err());
+    }
+
+    #[test]
+    fn helper() {
+        let dir = TempDir::new().unwrap();
+        let program = script(
+            &dir,
+            r#"cat >> requests
+case "$1" in
+    store) grep -E '^(username|password)=' requests > credential ;;
+    get) cat credential 2>/dev/null || true ;;
+    erase) rm -f credential ;;
+esac
+: > requests"#,
+        );
+
+        round_trip(&Helper::new(&program.display().to_string()));
+
+        fs::write(dir.path().join("credential"), "username=ann\n").unwrap();
+        assert_eq!(
+            Helper::new(&program.display().to_string())
+                .get(HOST)
+                .unwrap(),
+            None
+        );
+
+        let failing = Helper::new("false");
+        assert!(failing.get(HOST).is_err());
+    }
+
+    #[test]
+    fn describe_host() {
+        assert_eq!(
+            Helper::describe("http://jira.example.com:8080/"),
+            "protocol=http\nhost=jira.example.com:8080\n"
+        );
+        assert_eq!(
+            Helper::describe("jira.example.com"),
+            "protocol=https\nhost=jira.example.com\n"
+        );
+    }
+}
@@ -0,0 +1,503 @@
+use {
+    anyhow::{anyhow, bail, Context, Result},
+    jira_rs::issue::{
+        models::{Issue, IssueFieldsMeta, Operations},
+        payload::IssueUpdate,
+    },
+    serde_json::{self as json, Map, Value},
+    std::{collections::BTreeMap, env, fs, io::Write, process::Command},
+};
+
+const DESCRIPTION: &str = "description";
+const SEPARATOR: &str = "---";
+const DEFAULT_EDITOR: &str = "vi";
+
+/// Field types whose values can be shown as plain YAML and turned back
+/// into a payload value without losing information
+const SUPPORTED: &[&str] = &[
+    "string",
+    "number",
+    "date",
+    "datetime",
+    "user",
+    "option",
+    "priority",
+    "version",
+    "component",
+    "resolution",
+    "securitylevel",
+    "array",
+];
+
+/// A front-matter document of an issue's editable fields: a YAML block of
+/// field id -> value, followed by the description as a Markdown body.
+///
+/// Jira's objects (users, options, versions, etc) are shown by their name or
+/// value, and converted back to objects when building the payload
+#[derive(Debug, Clone)]
+pub struct Document {
+    title: String,
+    fields: BTreeMap<String, Field>,
+    description: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    name: String,
+    required: bool,
+    field_type: String,
+    items: Option<String>,
+    value: Value,
+}
+
+/// A field whose value was changed in the editor
+#[derive(Debug, Clone)]
+pub struct Change {
+    pub id: String,
+    pub name: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+impl Document {
+    /// Build a document of every field of the given issue which its edit
+    /// metadata allows to be set
+    pub fn from_issue<'m, 'a: 'm, I>(issue: &Issue<'_>, meta: I) -> Self
+    where
+        I: IntoIterator<Item = &'m IssueFieldsMeta<'a>>,
+    {
+        let mut document = Self {
+            title: format!("Editing {}", issue.key),
+            fields: BTreeMap::new(),
+            description: None,
+        };
+
+        for field in meta {
+            let value = issue
+                .fields
+                .get(field.field_id.as_ref())
+                .and_then(|raw| json::from_str(raw.get()).ok())
+                .unwrap_or(Value::Null);
+
+            document.insert(field, simplify(&value));
+        }
+
+        document
+    }
+
+    fn insert(&mut self, meta: &IssueFieldsMeta<'_>, value: Value) {
+        let schema = match meta.schema {
+            Some(ref schema) => schema,
+            None => return,
+        };
+        let settable = meta.operations.is_empty() || meta.operations.contains(&Operations::Set);
+
+        if !settable || !SUPPORTED.contains(&schema.field_type.as_ref()) {
+            return;
+        }
+
+        if meta.field_id == DESCRIPTION && schema.field_type == "string" {
+            self.description = Some(value.as_str().unwrap_or_default().to_string());
+            return;
+        }
+
+        self.fields.insert(
+            meta.field_id.to_string(),
+            Field {
+                name: meta.name.to_string(),
+                required: meta.required,
+                field_type: schema.field_type.to_string(),
+                items: schema.items.as_deref().map(str::to_string),
+                value,
+            },
+        );
+    }
+
+    /// Render the document, as it should be presented to the user
+    pub fn render(&self) -> Result<String> {
+        let mut out = String::new();
+
+        out.push_str(SEPARATOR);
+        out.push('\n');
+        out.push_str(&format!(
+            "# {}, lines starting with '#' are ignored\n",
+            self.title
+        ));
+        out.push_str("# Field values may be cleared with '~', and objects are\n");
+        out.push_str("# given by their name, e.g 'priority: High'\n");
+
+        for (id, field) in self.fields.iter() {
+            let mut entry = Map::new();
+            entry.insert(id.clone(), field.value.clone());
+            let yaml = serde_yaml::to_string(&entry)?;
+
+            out.push_str(&format!(
+                "\n# {} ({}{})\n",
+                field.name,
+                field.kind(),
+                if field.required { ", required" } else { "" }
+            ));
+            out.push_str(yaml.trim_start_matches("---\n"));
+            if !yaml.ends_with('\n') {
+                out.push('\n');
+            }
+        }
+
+        out.push_str(SEPARATOR);
+        out.push('\n');
+        if let Some(ref description) = self.description {
+            out.push_str(description);
+            out.push('\n');
+        }
+
+        Ok(out)
+    }
+
+    /// Parse an edited document, returning the fields which were changed.
+    /// Fields removed from the document are treated as cleared
+    pub fn changes(&self, edited: &str) -> Result<Vec<Change>> {
+        let (front, body) = split(edited)?;
+
+        let mut values: Map<String, Value> = match front.trim().is_empty() {
+            true => Map::new(),
+            false => serde_yaml::from_str::<Option<Map<String, Value>>>(&front)
+                .context("Invalid YAML in the document's front matter")?
+                .unwrap_or_default(),
+        };
+
+        if let Some(unknown) = values.keys().find(|k| !self.fields.contains_key(*k)) {
+            bail!(
+                "Unknown field '{}', only the fields present in the document can be edited",
+                unknown
+            )
+        }
+
+        let mut changes: Vec<Change> = self
+            .fields
+            .iter()
+            .filter_map(|(id, field)| {
+                let new = values.remove(id).unwrap_or(Value::Null);
+
+                (new != field.value).then(|| Change {
+                    id: id.clone(),
+                    name: field.name.clone(),
+                    old: field.value.clone(),
+                    new,
+                })
+            })
+            .collect();
+
+        if let Some(ref description) = self.description {
+            let body = body.trim_end();
+
+            if body != description.trim_end() {
+                changes.push(Change {
+                    id: DESCRIPTION.to_string(),
+                    name: "Description".to_string(),
+                    old: Value::String(description.clone()),
+                    new: Value::String(body.to_string()),
+                })
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// Build the payload setting each of the given changes
+    pub fn update(&self, changes: &[Change]) -> IssueUpdate {
+        let mut update = IssueUpdate::new();
+
+        for change in changes {
+            let value = match self.fields.get(&change.id) {
+                Some(field) => field.expand(&change.new),
+                // The description, which is always a string
+                None => change.new.clone(),
+            };
+
+            update.set(change.id.as_str(), value);
+        }
+
+        update
+    }
+}
+
+impl Field {
+    /// A short description of the field's type, for the document's comments
+    fn kind(&self) -> String {
+        match self.items {
+            Some(ref items) => format!("list of {}", items),
+            None => self.field_type.clone(),
+        }
+    }
+
+    /// Convert a simplified value back into the shape Jira expects
+    fn expand(&self, value: &Value) -> Value {
+        match (self.field_type.as_str(), value) {
+            ("array", Value::Array(values)) => {
+                let items = self.items.as_deref().unwrap_or("string");
+                Value::Array(values.iter().map(|v| expand(items, v)).collect())
+            }
+            // A single value given for a list
+            ("array", Value::Null) => Value::Array(Vec::new()),
+            ("array", other) => {
+                let items = self.items.as_deref().unwrap_or("string");
+                Value::Array(vec![expand(items, other)])
+            }
+            (field_type, other) => expand(field_type, other),
+        }
+    }
+}
+
+/// Reduce Jira's objects to the name a user would recognize them by
+pub fn simplify(value: &Value) -> Value {
+    const NAMES: [&str; 4] = ["value", "name", "key", "id"];
+
+    match value {
+        Value::Array(values) => Value::Array(values.iter().map(simplify).collect()),
+        Value::Object(map) => NAMES
+            .iter()
+            .find_map(|k| map.get(*k))
+            .cloned()
+            .unwrap_or_else(|| value.clone()),
+        other => other.clone(),
+    }
+}
+
+/// The inverse of `simplify`, objects given by the user are passed as is
+pub fn expand(field_type: &str, value: &Value) -> Value {
+    match (field_type, value) {
+        (_, Value::Null) | (_, Value::Object(_)) => value.clone(),
+        ("string" | "number" | "date" | "datetime" | "any", _) => value.clone(),
+        ("option", _) => json::json!({ "value": scalar(value) }),
+        (_, _) => json::json!({ "name": scalar(value) }),
+    }
+}
+
+fn scalar(value: &Value) -> Value {
+    match value {
+        Value::String(_) => value.clone(),
+        other => Value::String(other.to_string()),
+    }
+}
+
+/// Split a document into its front matter and body, ignoring comments
+fn split(document: &str) -> Result<(String, &str)> {
+    let invalid = || anyhow!("The document must start with front matter between '---' lines");
+    let mut lines = document.split_inclusive('\n');
+    let mut consumed = 0;
+
+    // Everything up to the opening separator must be blank or a comment
+    loop {
+        let line = lines.next().ok_or_else(invalid)?;
+        consumed += line.len();
+
+        match line.trim() {
+            SEPARATOR => break,
+            l if l.is_empty() || l.starts_with('#') => continue,
+            _ => return Err(invalid()),
+        }
+    }
+
+    let mut front = String::new();
+    loop {
+        let line = lines.next().ok_or_else(invalid)?;
+        consumed += line.len();
+
+        match line.trim_end() {
+            SEPARATOR => break,
+            l if l.trim_start().starts_with('#') => continue,
+            _ => front.push_str(line),
+        }
+    }
+
+    Ok((front, &document[consumed..]))
+}
+
+/// Open the given contents in the user's editor ($VISUAL, $EDITOR or vi),
+/// returning the contents once the editor exits
+pub fn edit(contents: &str) -> Result<String> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+
+    let mut file = tempfile::Builder::new()
+        .prefix("jira-")
+        .suffix(".md")
+        .tempfile()?;
+    file.write_all(contents.as_bytes())?;
+    file.flush()?;
+
+    // Run through the shell, as editors are commonly configured with
+    // arguments, e.g 'code --wait'
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$@\"", editor))
+        .arg(&editor)
+        .arg(file.path())
+        .status()
+        .with_context(|| format!("Unable to run editor '{}'", editor))?;
+
+    if !status.success() {
+        bail!("Editor '{}' exited unsuccessfully: {}", editor, status)
+    }
+
+    Ok(fs::read_to_string(file.path())?)
+}
+
+/// A one line rendering of a change's value, for previews
+pub fn preview(value: &Value) -> String {
+    const MAX: usize = 60;
+
+    let text = match value {
+        Value::Null => "<empty>".to_string(),
+        Value::String(s) if s.contains('\n') => format!("{} lines", s.lines().count()),
+        other => other.to_string(),
+    };
+
+    match text.chars().count() > MAX {
+        true => text.chars().take(MAX - 1).chain(Some('…')).collect(),
+        false => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        jira_rs::issue::models::IssueHandle,
+        serde_json::{json, value::RawValue as RawJson},
+    };
+
+    fn meta(id: &str, name: &str, schema: Value) -> Value {
+        json!({
+            "required": id == "summary",
+            "name": name,
+            "fieldId": id,
+            "schema": schema,
+            "operations": ["set"],
+        })
+    }
+
+    fn document() -> Document {
+        let issue = json!({
+            "self": "https://jira.example.com/rest/api/2/issue/10000",
+            "id": "10000",
+            "key": "FOO-1",
+            "fields": {
+                "summary": "Fix the login page",
+                "description": "Steps:\n\n1. Log in",
+                "priority": { "id": "2", "name": "High" },
+                "labels": ["backend"],
+                "fixVersions": [{ "id": "10", "name": "1.0" }],
+                "assignee": null,
+            },
+        });
+        let issue = IssueHandle::try_new(RawJson::from_string(issue.to_string()).unwrap()).unwrap();
+
+        let meta = json!([
+            meta("summary", "Summary", json!({ "type": "string" })),
+            meta("description", "Description", json!({ "type": "string" })),
+            meta("priority", "Priority", json!({ "type": "priority" })),
+            meta(
+                "labels",
+                "Labels",
+                json!({ "type": "array", "items": "string" })
+            ),
+            meta(
+                "fixVersions",
+                "Fix Versions",
+                json!({ "type": "array", "items": "version" })
+            ),
+            meta("assignee", "Assignee", json!({ "type": "user" })),
+        ])
+        .to_string();
+        let meta: Vec<IssueFieldsMeta<'_>> = json::from_str(&meta).unwrap();
+
+        Document::from_issue(issue.data(), meta.iter())
+    }
+
+    fn payload(document: &Document, changes: &[Change]) -> Value {
+        json::to_value(document.update(changes)).unwrap()["fields"].clone()
+    }
+
+    #[test]
+    fn unchanged_round_trip() {
+        let document = document();
+        let rendered = document.render().unwrap();
+
+        assert!(rendered.contains("\npriority: High\n"), "{}", rendered);
+        assert!(
+            rendered.ends_with("---\nSteps:\n\n1. Log in\n"),
+            "{}",
+            rendered
+        );
+        assert!(document.changes(&rendered).unwrap().is_empty());
+    }
+
+    #[test]
+    fn removed_fields_are_cleared() {
+        let document = document();
+        let edited = document.render().unwrap().replace("priority: High\n", "");
+
+        let changes = document.changes(&edited).unwrap();
+        assert_eq!(
+            changes
+                .iter()
+                .map(|c| (c.id.as_str(), &c.new))
+                .collect::<Vec<_>>(),
+            [("priority", &Value::Null)]
+        );
+        assert_eq!(payload(&document, &changes), json!({ "priority": null }));
+    }
+
+    #[test]
+    fn description_only_edit() {
+        let document = document();
+        let edited = document
+            .render()
+            .unwrap()
+            .replace("1. Log in\n", "1. Log in\n2. Log out\n\n\n");
+
+        let changes = document.changes(&edited).unwrap();
+        assert_eq!(
+            changes.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(),
+            [DESCRIPTION]
+        );
+        assert_eq!(
+            payload(&document, &changes),
+            json!({ "description": "Steps:\n\n1. Log in\n2. Log out" })
+        );
+    }
+
+    #[test]
+    fn simplified_values_expand() {
+        let document = document();
+        let edited = document
+            .render()
+            .unwrap()
+            .replace("  - \"1.0\"", "  - \"1.1\"")
+            .replace("  - backend", "  - backend\n  - ui")
+            .replace("assignee: ~", "assignee: ann");
+
+        let changes = document.changes(&edited).unwrap();
+        assert_eq!(
+            payload(&document, &changes),
+            json!({
+                "assignee": { "name": "ann" },
+                "fixVersions": [{ "name": "1.1" }],
+                "labels": ["backend", "ui"],
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_documents() {
+        let document = document();
+
+        assert!(document.changes("summary: a\n").is_err());
+        assert!(document.changes("---\nsummary: a\n").is_err());
+        assert!(document.changes("---\nresolution: Done\n---\n").is_err());
+    }
+}
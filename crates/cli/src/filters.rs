@@ -0,0 +1,166 @@
+use {
+    crate::{
+        cli::Filters as FiltersCmd,
+        config::Profile,
+        manifest::{Action, FilterManifest},
+        output::Output,
+    },
+    anyhow::{bail, Context, Result},
+    jira_rs::{
+        client::Jira,
+        filter::{self, payload::FilterDetails},
+        issue,
+    },
+};
+
+pub async fn run(cmd: &FiltersCmd, settings: &Profile, output: &Output) -> Result<()> {
+    let client = settings.client()?;
+
+    match cmd {
+        FiltersCmd::List {
+            mine,
+            name: Some(ref name),
+            max_results,
+            start_at,
+        } if !mine => {
+            let options = filter::options::Search::new().with(|this| {
+                this.name(name)
+                    .max_results(max_results.or(settings.page_size))
+                    .start_at(*start_at)
+                    .expand([
+                        filter::options::expands::DESCRIPTION,
+                        filter::options::expands::OWNER,
+                        filter::options::expands::JQL,
+                        filter::options::expands::FAVOURITE,
+                        filter::options::expands::SHARE_PERMISSIONS,
+                    ])
+            });
+            let filters = client.filters().search(Some(&options)).await?;
+
+            output.render(&filters.data().values)?;
+        }
+        FiltersCmd::List { mine: true, .. } => {
+            let filters = client.filters().mine(None).await?;
+
+            output.render(&filters)?;
+        }
+        FiltersCmd::List { .. } => {
+            let filters = client.filters().favourites(None).await?;
+
+            output.render(&filters)?;
+        }
+        FiltersCmd::Get { ref filter } => {
+            let id = filter_id(&client, filter).await?;
+            let filter = client.filters().get(id, None).await?;
+
+            output.render(&filter)?;
+        }
+        FiltersCmd::Create {
+            ref name,
+            ref jql,
+            ref description,
+            ref share,
+            favourite,
+        } => {
+            let mut details =
+                FilterDetails::new(name).with(|this| this.jql(jql).favourite(*favourite));
+            if let Some(ref description) = description {
+                details.description(description);
+            }
+            if !share.is_empty() {
+                details.share_permissions(share.iter().cloned());
+            }
+
+            let created = client.filters().create(&details, None).await?;
+
+            output.render(&created)?;
+        }
+        FiltersCmd::Apply {
+            ref manifest,
+            dry_run,
+        } => {
+            let manifest = FilterManifest::load(manifest)?;
+            let existing = client.filters().mine(None).await?;
+            let mut plan = manifest.plan(existing.data());
+
+            for step in plan.iter_mut().filter(|_| !dry_run) {
+                let details = match step.details {
+                    Some(ref details) => details,
+                    None => continue,
+                };
+
+                let applied = match step.id {
+                    Some(id) => client.filters().edit(id, details, None).await,
+                    None => client.filters().create(details, None).await,
+                }
+                .with_context(|| format!("Unable to apply filter '{}'", step.name))?;
+                step.id = Some(applied.data().id);
+            }
+
+            let count = |action| plan.iter().filter(|s| s.action == action).count();
+            eprintln!(
+                "{}{} to create, {} to update, {} unchanged, {} unmanaged",
+                if *dry_run { "Dry run: " } else { "" },
+                count(Action::Create),
+                count(Action::Update),
+                count(Action::Unchanged),
+                count(Action::Unmanaged),
+            );
+
+            output.render(&plan)?;
+        }
+        FiltersCmd::Run {
+            ref filter,
+            ref fields,
+            max_results,
+            start_at,
+        } => {
+            let id = filter_id(&client, filter).await?;
+            let options = issue::options::Search::new().with(|this| {
+                match fields {
+                    Some(fields) => this.fields(fields),
+                    None => this.fields(settings.fields.iter().flatten()),
+                }
+                .max_results(max_results.or(settings.page_size))
+                .start_at(*start_at)
+            });
+
+            let search = client.issues().search_filter(id, Some(&options)).await?;
+
+            output.render_issues(&search, &search.data().issues)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve a filter given by id or by name, names must match exactly
+/// (ignoring case) a single filter visible to the user
+async fn filter_id(client: &Jira, filter: &str) -> Result<u64> {
+    if let Ok(id) = filter.parse() {
+        return Ok(id);
+    }
+
+    let options = filter::options::Search::new().with(|this| this.name(filter));
+    let found = client.filters().search(Some(&options)).await?;
+    let matches: Vec<_> = found
+        .data()
+        .values
+        .iter()
+        .filter(|f| f.name.eq_ignore_ascii_case(filter))
+        .map(|f| f.id)
+        .collect();
+
+    match matches.as_slice() {
+        [id] => Ok(*id),
+        [] => bail!("No filter named '{}'", filter),
+        ids => bail!(
+            "Several filters are named '{}', use one of the ids: {}",
+            filter,
+            ids.iter()
+                .map(u64::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
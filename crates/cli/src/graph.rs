@@ -0,0 +1,319 @@
+use {
+    jira_rs::workflow::models::{Transition, TransitionType, Workflow},
+    serde_json::{self as json, Value},
+    std::{fmt::Write as _, str::FromStr},
+};
+
+/// The node initial transitions start from
+const START: &str = "start";
+/// The node global transitions start from
+const ANY: &str = "any";
+
+/// The graph languages a workflow can be rendered in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphFormat {
+    /// Graphviz, e.g 'jira workflow show ... | dot -Tsvg > workflow.svg'
+    Dot,
+    /// Mermaid state diagrams, which render in most markdown viewers
+    Mermaid,
+}
+
+/// Render a workflow's statuses and transitions as a graph.
+///
+/// Initial transitions start from a point, global transitions (which
+/// are available from every status) from an 'any status' node. Each
+/// transition is labelled with its name and id, plus the types of any
+/// conditions which may hide it
+pub fn render(workflow: &Workflow<'_>, format: GraphFormat) -> String {
+    match format {
+        GraphFormat::Dot => dot(workflow),
+        GraphFormat::Mermaid => mermaid(workflow),
+    }
+}
+
+fn dot(workflow: &Workflow<'_>) -> String {
+    let mut out = String::new();
+    let global = has_global(workflow);
+
+    let _ = writeln!(out, "digraph {} {{", quote(&workflow.id.name));
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, style=rounded];\n");
+    let _ = writeln!(out, "  {} [shape=point, width=0.2];", START);
+    if global {
+        let _ = writeln!(out, "  {} [shape=plaintext, label=\"(any status)\"];", ANY);
+    }
+
+    for status in workflow.statuses.iter() {
+        let _ = writeln!(
+            out,
+            "  {} [label={}];",
+            node(status.id),
+            quote(&status.name)
+        );
+    }
+
+    for transition in workflow.transitions.iter() {
+        let mut label = label(transition);
+        let conditions = conditions(transition);
+        if !conditions.is_empty() {
+            label = format!("{}\nif {}", label, conditions.join(", "));
+        }
+        let style = match transition.transition_type {
+            TransitionType::Global => ", style=dashed",
+            _ => "",
+        };
+
+        for from in sources(transition) {
+            let _ = writeln!(
+                out,
+                "  {} -> {} [label={}{}];",
+                from,
+                node(transition.to),
+                quote(&label),
+                style
+            );
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn mermaid(workflow: &Workflow<'_>) -> String {
+    let mut out = String::from("stateDiagram-v2\n");
+
+    if has_global(workflow) {
+        let _ = writeln!(out, "    {} : (any status)", ANY);
+    }
+    for status in workflow.statuses.iter() {
+        let _ = writeln!(out, "    {} : {}", node(status.id), plain(&status.name));
+    }
+
+    for transition in workflow.transitions.iter() {
+        let mut label = plain(&label(transition));
+        let conditions = conditions(transition);
+        if !conditions.is_empty() {
+            label = format!("{}<br>if {}", label, conditions.join(", "));
+        }
+
+        for from in sources(transition) {
+            let from = match from.as_str() {
+                START => "[*]".to_string(),
+                _ => from,
+            };
+
+            let _ = writeln!(out, "    {} --> {} : {}", from, node(transition.to), label);
+        }
+    }
+
+    out
+}
+
+fn has_global(workflow: &Workflow<'_>) -> bool {
+    workflow
+        .transitions
+        .iter()
+        .any(|t| t.transition_type == TransitionType::Global)
+}
+
+/// The nodes a transition's edges start from
+fn sources(transition: &Transition<'_>) -> Vec<String> {
+    match transition.transition_type {
+        TransitionType::Initial => vec![START.to_string()],
+        TransitionType::Global => vec![ANY.to_string()],
+        TransitionType::Directed => transition.from.iter().copied().map(node).collect(),
+    }
+}
+
+fn node(status: u64) -> String {
+    format!("s{}", status)
+}
+
+fn label(transition: &Transition<'_>) -> String {
+    format!("{} ({})", transition.name, transition.id)
+}
+
+/// The types of the conditions guarding a transition. Jira Cloud nests
+/// them in a tree of AND / OR nodes, older responses list them flat
+fn conditions(transition: &Transition<'_>) -> Vec<String> {
+    fn walk(node: &Value, found: &mut Vec<String>) {
+        if let Some(children) = node.get("conditions").and_then(Value::as_array) {
+            children.iter().for_each(|child| walk(child, found));
+        }
+        if node.get("conditions").is_none() {
+            if let Some(kind) = node.get("type").and_then(Value::as_str) {
+                found.push(short_name(kind).to_string());
+            }
+        }
+    }
+
+    let rules: Value = match transition.rules.map(|raw| json::from_str(raw.get())) {
+        Some(Ok(rules)) => rules,
+        _ => return Vec::new(),
+    };
+
+    let mut found = Vec::new();
+    match rules.get("conditionsTree") {
+        Some(tree) if !tree.is_null() => walk(tree, &mut found),
+        _ => walk(&rules, &mut found),
+    }
+    found.sort();
+    found.dedup();
+
+    found
+}
+
+/// Strip the package from a condition's class name, e.g
+/// 'com.atlassian.jira.workflow.condition.PermissionCondition'
+fn short_name(kind: &str) -> &str {
+    kind.rsplit('.').next().unwrap_or(kind)
+}
+
+/// A DOT string literal
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+/// Mermaid has no quoting, so drop the characters which end a
+/// statement or label
+fn plain(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\n' | ';' | ':' => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(Self::Dot),
+            "mermaid" => Ok(Self::Mermaid),
+            _ => Err(format!("expected one of [dot, mermaid], got '{}'", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    fn workflow() -> String {
+        let condition = |kind: &str| json!({ "nodeType": "simple", "type": kind });
+
+        json!({
+            "id": { "name": "Bug \"flow\"", "entityId": "e1" },
+            "description": "",
+            "statuses": [
+                { "id": "1", "name": "Open" },
+                { "id": "3", "name": "In \"Progress\"" },
+                { "id": "10001", "name": "Done: really; yes" },
+            ],
+            "transitions": [
+                { "id": "1", "name": "Create", "from": [], "to": "1", "type": "initial" },
+                {
+                    "id": "11",
+                    "name": "Start",
+                    "from": ["1"],
+                    "to": "3",
+                    "type": "directed",
+                    "rules": {
+                        "conditionsTree": {
+                            "nodeType": "compound",
+                            "operator": "AND",
+                            "conditions": [
+                                condition("com.atlassian.jira.PermissionCondition"),
+                                {
+                                    "nodeType": "compound",
+                                    "operator": "OR",
+                                    "conditions": [
+                                        condition("OnlyAssigneeCondition"),
+                                        condition("PermissionCondition"),
+                                    ],
+                                },
+                            ],
+                        },
+                    },
+                },
+                { "id": "21", "name": "Done", "from": [], "to": "10001", "type": "global" },
+                {
+                    "id": "31",
+                    "name": "Reopen",
+                    "from": ["3", "10001"],
+                    "to": "1",
+                    "type": "directed",
+                    "rules": { "conditions": [{ "type": "com.example.InGroupCondition" }] },
+                },
+            ],
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn render_dot() {
+        let source = workflow();
+        let workflow: Workflow<'_> = json::from_str(&source).unwrap();
+
+        assert_eq!(
+            render(&workflow, GraphFormat::Dot),
+            r#"digraph "Bug \"flow\"" {
+  rankdir=LR;
+  node [shape=box, style=rounded];
+  start [shape=point, width=0.2];
+  any [shape=plaintext, label="(any status)"];
+  s1 [label="Open"];
+  s3 [label="In \"Progress\""];
+  s10001 [label="Done: really; yes"];
+  start -> s1 [label="Create (1)"];
+  s1 -> s3 [label="Start (11)\nif OnlyAssigneeCondition, PermissionCondition"];
+  any -> s10001 [label="Done (21)", style=dashed];
+  s3 -> s1 [label="Reopen (31)\nif InGroupCondition"];
+  s10001 -> s1 [label="Reopen (31)\nif InGroupCondition"];
+}
+"#
+        );
+    }
+
+    #[test]
+    fn render_mermaid() {
+        let source = workflow();
+        let workflow: Workflow<'_> = json::from_str(&source).unwrap();
+
+        assert_eq!(
+            render(&workflow, GraphFormat::Mermaid),
+            r#"stateDiagram-v2
+    any : (any status)
+    s1 : Open
+    s3 : In "Progress"
+    s10001 : Done  really  yes
+    [*] --> s1 : Create (1)
+    s1 --> s3 : Start (11)<br>if OnlyAssigneeCondition, PermissionCondition
+    any --> s10001 : Done (21)
+    s3 --> s1 : Reopen (31)<br>if InGroupCondition
+    s10001 --> s1 : Reopen (31)<br>if InGroupCondition
+"#
+        );
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(quote("a \"b\" \\ c\nd"), r#""a \"b\" \\ c\nd""#);
+        assert_eq!(plain("a: b; c\nd"), "a  b  c d");
+    }
+}
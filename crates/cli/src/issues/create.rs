@@ -0,0 +1,160 @@
+use {
+    crate::{
+        cli::IssueCreate, config::Profile, editor, meta::project_meta, prompt,
+        templates::IssueTemplate,
+    },
+    anyhow::{anyhow, Context, Result},
+    jira_rs::{
+        client::Jira,
+        issue::{self, models::CreatedHandle, payload::IssueUpdate},
+    },
+    serde_json as json,
+};
+
+/// Create the issue described by a template, followed by its children
+/// linked to the created issue
+pub async fn from_template(
+    client: &Jira,
+    settings: &Profile,
+    opts: &IssueCreate,
+) -> Result<Vec<CreatedHandle>> {
+    let name = opts.template.as_deref().expect("--template is given");
+    let options: issue::options::Create = opts.into();
+    let template = IssueTemplate::load(name)?;
+    let mut context = template.context(&opts.vars);
+
+    let mut data = template.issue(&context)?;
+    if let Some(ref project) = settings.project {
+        if !data.fields().contains_key("project") {
+            data.set("project", json::json!({ "key": project }));
+        }
+    }
+
+    // Check every payload before creating anything, linking the
+    // children to a stand in for the parent
+    if opts.validate {
+        validate(client, &data).await?;
+
+        let mut placeholder = context.clone();
+        placeholder.parent("PARENT-1", 0);
+        for child in template.children(&placeholder, &data)? {
+            validate(client, &child).await?;
+        }
+    }
+
+    let parent = client.issues().create(&data, Some(&options)).await?;
+    context.parent(&parent.data().key, parent.data().id);
+    let children = template.children(&context, &data)?;
+
+    let mut created = vec![parent];
+    for child in children {
+        match client.issues().create(&child, Some(&options)).await {
+            Ok(handle) => created.push(handle),
+            Err(e) => {
+                let keys: Vec<&str> = created.iter().map(|c| c.data().key.as_ref()).collect();
+                return Err(e).with_context(|| {
+                    format!(
+                        "Failed to create a child issue, after creating: {}",
+                        keys.join(", ")
+                    )
+                });
+            }
+        }
+    }
+
+    Ok(created)
+}
+
+pub async fn validate(client: &Jira, data: &IssueUpdate) -> Result<()> {
+    let meta = client
+        .issues()
+        .meta_create(Some(&data.meta_create_options()))
+        .await?;
+
+    Ok(data.validate_create(meta.data())?)
+}
+
+/// Build a create payload by prompting for each of the required fields
+/// of the chosen project and issue type
+pub async fn interactive(
+    client: &Jira,
+    settings: &Profile,
+    opts: &IssueCreate,
+) -> Result<IssueUpdate> {
+    let project = opts
+        .project
+        .as_ref()
+        .or(settings.project.as_ref())
+        .ok_or_else(|| anyhow!("No project given, use --project or set the profile's project"))?;
+
+    // The issue types alone are cheap to list, only fetch the fields of
+    // the chosen one
+    let listing = project_meta(client, project).await?;
+    let names: Vec<&str> = listing.data().projects[0]
+        .issue_types
+        .iter()
+        .map(|t| t.issue_type.name.as_ref())
+        .collect();
+    let name = match opts.issue_type {
+        Some(ref name) => name.as_str(),
+        None => prompt::choose("Issue type", &names)?,
+    };
+    let issue_type = listing.data().projects[0]
+        .issue_types
+        .iter()
+        .find(|t| t.issue_type.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            anyhow!(
+                "Unknown issue type '{}', expected one of [{}]",
+                name,
+                names.join(", ")
+            )
+        })?;
+
+    let mut data = IssueUpdate::new();
+    data.set(
+        "project",
+        json::json!({ "key": listing.data().projects[0].project.key }),
+    );
+    data.set(
+        "issuetype",
+        json::json!({ "id": issue_type.issue_type.id.to_string() }),
+    );
+
+    let meta = client
+        .issues()
+        .meta_create(Some(&data.meta_create_options()))
+        .await?;
+    let fields = meta
+        .data()
+        .projects
+        .iter()
+        .flat_map(|p| p.issue_types.iter())
+        .find(|t| t.issue_type.id == issue_type.issue_type.id)
+        .and_then(|t| t.fields.as_ref())
+        .ok_or_else(|| anyhow!("Jira did not return the fields of issue type '{}'", name))?;
+
+    // Summary first, then the remaining fields alphabetically
+    let mut required: Vec<_> = fields
+        .values()
+        .filter(|f| {
+            f.required && !["project", "issuetype", "description"].contains(&f.field_id.as_ref())
+        })
+        .collect();
+    required.sort_by_key(|f| (f.field_id != "summary", f.name.to_lowercase()));
+
+    for (id, value) in prompt::fields(required)? {
+        data.set(id, value);
+    }
+
+    if let Some(description) = fields.get("description") {
+        if description.required || prompt::confirm("Write a description in your editor?")? {
+            let text = editor::edit("")?;
+            if !text.trim().is_empty() {
+                data.set("description", text.trim_end());
+            }
+        }
+    }
+
+    Ok(data)
+}
@@ -0,0 +1,59 @@
+use {
+    crate::{
+        editor::{self, Document},
+        prompt,
+    },
+    anyhow::{bail, Result},
+    jira_rs::{client::Jira, issue::payload::IssueUpdate},
+};
+
+/// Edit an issue in the user's editor, returning the changed fields or
+/// None if nothing was changed or the user aborted
+pub async fn interactive(client: &Jira, key: &str, yes: bool) -> Result<Option<IssueUpdate>> {
+    let issues = client.issues();
+    let (issue, meta) = tokio::try_join!(issues.get(key, None), issues.meta_edit(key))?;
+    let document = Document::from_issue(issue.data(), meta.data().fields.values());
+
+    let mut text = document.render()?;
+    let changes = loop {
+        text = editor::edit(&text)?;
+
+        if text.trim().is_empty() {
+            eprintln!("Empty document, aborting");
+            return Ok(None);
+        }
+
+        match document.changes(&text) {
+            Ok(changes) => break changes,
+            Err(e) => {
+                eprintln!("{:#}", e);
+                if !prompt::confirm("Re-open the editor?")? {
+                    bail!("Aborted, no changes were made to {}", key)
+                }
+            }
+        }
+    };
+
+    if changes.is_empty() {
+        eprintln!("No changes made to {}", key);
+        return Ok(None);
+    }
+
+    eprintln!("Changes to {}:", key);
+    for change in changes.iter() {
+        eprintln!(
+            "  {} ({}): {} -> {}",
+            change.name,
+            change.id,
+            editor::preview(&change.old),
+            editor::preview(&change.new)
+        );
+    }
+
+    if !yes && !prompt::confirm("Apply these changes?")? {
+        eprintln!("Aborted, no changes were made to {}", key);
+        return Ok(None);
+    }
+
+    Ok(Some(document.update(&changes)))
+}
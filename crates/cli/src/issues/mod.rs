@@ -0,0 +1,183 @@
+mod create;
+mod edit;
+mod watch;
+
+use {
+    crate::{
+        cli::{Issues as IssuesCmd, MetaKind},
+        config::Profile,
+        mirror,
+        output::Output,
+    },
+    anyhow::{anyhow, Result},
+    jira_rs::issue::{self, payload::IssueUpdate},
+    jira_sync::LocalOptions,
+    serde_json as json,
+};
+
+pub async fn run(cmd: &IssuesCmd, settings: &Profile, output: &Output) -> Result<()> {
+    match cmd {
+        IssuesCmd::Get { ref opts, .. } if opts.offline => return offline(cmd, settings, output),
+        IssuesCmd::Search { ref opts, .. } if opts.offline => {
+            return offline(cmd, settings, output)
+        }
+        _ => {}
+    }
+
+    let client = settings.client()?;
+
+    match cmd {
+        IssuesCmd::Get { ref key, ref opts } => {
+            let key = key.access()?.read_to_string()?;
+
+            let mut options: issue::options::Get = opts.into();
+            if opts.fields.is_none() {
+                options.fields(settings.fields.iter().flatten());
+            }
+
+            let issue = client.issues().get(key, Some(&options)).await?;
+
+            output.render_issues(&issue, std::slice::from_ref(issue.data()))?;
+        }
+        IssuesCmd::Search { ref jql, ref opts } => {
+            let jql = jql.access()?.read_to_string()?;
+
+            let mut options = opts.as_options().with(|this| this.jql(jql));
+            if opts.fields.is_none() {
+                options.fields(settings.fields.iter().flatten());
+            }
+            if opts.max_results.is_none() {
+                options.max_results(settings.page_size);
+            }
+
+            let search = client.issues().search(Some(&options)).await?;
+
+            output.render_issues(&search, &search.data().issues)?;
+        }
+        IssuesCmd::Create { ref opts, .. } if opts.template.is_some() => {
+            let created = create::from_template(&client, settings, opts).await?;
+
+            output.render(&created)?;
+        }
+        IssuesCmd::Create { ref data, ref opts } => {
+            let options: issue::options::Create = opts.into();
+            let mut data = match data {
+                _ if opts.interactive => create::interactive(&client, settings, opts).await?,
+                Some(data) => to_update(data)?,
+                None => unreachable!("--data is required unless --interactive is given"),
+            };
+
+            // Fallback to the profile's project if the data has none
+            match settings.project {
+                Some(ref project) if !data.fields().contains_key("project") => {
+                    data.set("project", json::json!({ "key": project }));
+                }
+                _ => {}
+            }
+
+            // Validation reports the payload's own problems too
+            match opts.validate {
+                true => create::validate(&client, &data).await?,
+                false => data.check()?,
+            }
+
+            let created = client.issues().create(&data, Some(&options)).await?;
+
+            output.render(&created)?;
+        }
+        IssuesCmd::Edit {
+            ref key,
+            ref data,
+            ref opts,
+        } => {
+            let data = match data {
+                _ if opts.interactive => match edit::interactive(&client, key, opts.yes).await? {
+                    Some(data) => data,
+                    None => return Ok(()),
+                },
+                Some(data) => to_update(data)?,
+                None => unreachable!("--data is required unless --interactive is given"),
+            };
+
+            match opts.validate {
+                true => {
+                    let meta = client.issues().meta_edit(key).await?;
+                    data.validate_edit(meta.data())?;
+                }
+                false => data.check()?,
+            }
+
+            client.issues().edit(key, &data).await?;
+
+            output.render(&json::json!({key: "Successfully updated", "data": data}))?;
+        }
+        IssuesCmd::Meta { ref opts } => match MetaKind::from(opts) {
+            // User provided a specific issue
+            MetaKind::Edit(issue) => {
+                let meta_edit = client.issues().meta_edit(issue).await?;
+
+                output.render(&meta_edit)?;
+            }
+            // No issue, run a query based on options passed in
+            MetaKind::Create(options) => {
+                let meta_create = client.issues().meta_create(Some(&options)).await?;
+
+                output.render(&meta_create)?;
+            }
+        },
+        IssuesCmd::Watch { ref jql, ref opts } => {
+            let jql = jql.access()?.read_to_string()?;
+
+            watch::run(&client, jql.trim(), opts, settings, output).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Answer issue lookups and searches from the local mirror
+fn offline(cmd: &IssuesCmd, settings: &Profile, output: &Output) -> Result<()> {
+    match cmd {
+        IssuesCmd::Get { ref key, ref opts } => {
+            let key = key.access()?.read_to_string()?;
+            let key = key.trim();
+            let store = mirror::open(settings, opts.db.as_deref())?;
+
+            let options = LocalOptions::new().with(|this| match opts.fields {
+                Some(ref fields) => this.fields(fields),
+                None => this.fields(settings.fields.iter().flatten()),
+            });
+            let issue = store
+                .get(key, &options)?
+                .ok_or_else(|| anyhow!("Issue '{}' is not in the mirror, see 'jira sync'", key))?;
+
+            output.render_issues(&issue, std::slice::from_ref(issue.data()))
+        }
+        IssuesCmd::Search { ref jql, ref opts } => {
+            let jql = jql.access()?.read_to_string()?;
+            let store = mirror::open(settings, opts.db.as_deref())?;
+
+            let mut options = opts.as_local_options();
+            if let Some(ref user) = settings.user {
+                options.current_user(user);
+            }
+            if opts.fields.is_none() {
+                options.fields(settings.fields.iter().flatten());
+            }
+            if opts.max_results.is_none() {
+                options.max_results(settings.page_size);
+            }
+
+            let search = store.search(&jql, &options)?;
+
+            output.render_issues(&search, &search.data().issues)
+        }
+        _ => unreachable!("only get and search can be answered offline"),
+    }
+}
+
+fn to_update(i: &grab::Input) -> Result<IssueUpdate> {
+    let src = i.access()?;
+
+    Ok(json::from_reader(src)?)
+}
@@ -0,0 +1,89 @@
+use {
+    crate::{cli::IssuesWatch, config::Profile, output::Output},
+    anyhow::{Context, Result},
+    jira_rs::client::Jira,
+    jira_sync::{Change, ChangeKind, WatchOptions, Watcher},
+    serde_json as json,
+    std::time::Duration,
+};
+
+pub async fn run(
+    client: &Jira,
+    jql: &str,
+    opts: &IssuesWatch,
+    settings: &Profile,
+    output: &Output,
+) -> Result<()> {
+    let options = WatchOptions::new().with(|this| {
+        this.page_size(settings.page_size)
+            .fields(opts.fields.iter().flatten())
+    });
+    let interval = Duration::from_secs(opts.interval.max(1));
+    let mut watcher = Watcher::new(client, jql, &options);
+
+    watcher
+        .poll()
+        .await
+        .with_context(|| format!("Unable to search '{}'", jql))?;
+    eprintln!("Watching '{}' every {}s", jql, interval.as_secs());
+
+    loop {
+        tokio::time::sleep(interval).await;
+
+        // A poll failing is likely transient, the next covers its changes
+        let changes = match watcher.poll().await {
+            Ok(changes) => changes,
+            Err(e) => {
+                eprintln!("Unable to poll for changes: {}", e);
+                continue;
+            }
+        };
+
+        for change in changes.iter() {
+            match opts.exec {
+                Some(ref cmd) => run_exec(cmd, change)?,
+                None => output.render(change)?,
+            }
+        }
+    }
+}
+
+/// Run a --exec command for a change, with the change in its environment
+fn run_exec(cmd: &str, change: &Change) -> Result<()> {
+    let value = |value: &json::Value| match value {
+        json::Value::Null => String::new(),
+        json::Value::String(s) => s.clone(),
+        value => value.to_string(),
+    };
+    let (field, from, to) = match change.kind {
+        ChangeKind::FieldChanged {
+            ref field,
+            ref from,
+            ref to,
+        } => (field.as_str(), value(from), value(to)),
+        ChangeKind::Transitioned { ref from, ref to } => ("status", from.clone(), to.clone()),
+        ChangeKind::Commented { ref body, .. } => ("comment", String::new(), body.clone()),
+        _ => ("", String::new(), String::new()),
+    };
+    let json = json::to_value(change)?;
+
+    let status = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(cmd)
+        .env("JIRA_ISSUE_KEY", &change.key)
+        .env("JIRA_ISSUE_ID", change.id.to_string())
+        .env("JIRA_CHANGE", json["change"].as_str().unwrap_or_default())
+        .env("JIRA_FIELD", field)
+        .env("JIRA_FROM", from)
+        .env("JIRA_TO", to)
+        .env("JIRA_CHANGE_JSON", json.to_string())
+        .status()
+        .with_context(|| format!("Unable to run '{}'", cmd))?;
+
+    // Keep watching, one failed notification shouldn't end the watch
+    if !status.success() {
+        eprintln!("'{}' exited unsuccessfully: {}", cmd, status)
+    }
+
+    Ok(())
+}
@@ -1,87 +1,128 @@
+mod auth;
 mod cli;
-
-use crate::cli::{CliOptions, Command, Issues as IssuesCmd, MetaKind};
+mod complete;
+mod config;
+mod credentials;
+mod editor;
+mod filters;
+mod graph;
+mod issues;
+mod manifest;
+mod meta;
+mod mirror;
+mod notes;
+mod output;
+mod prompt;
+mod release_notes;
+mod sync;
+mod templates;
+mod versions;
+mod webhooks;
+mod workflow;
+
+use crate::{
+    cli::{CliOptions, Command},
+    config::Config,
+};
 use {
-    anyhow::{anyhow, Result},
-    jira_rs::{client::Jira, issue},
-    json::{to_writer_pretty as json_pretty, value::RawValue as RawJson},
-    serde_json as json,
-    std::io::stdout,
+    anyhow::Result,
+    std::{
+        env,
+        io::{stderr, stdout, IsTerminal},
+    },
+    tracing_subscriber::EnvFilter,
 };
 
 #[tokio::main(worker_threads = 2)]
 async fn main() -> Result<()> {
     let cli = CliOptions::new();
-
-    let client = Jira::new(
-        cli.host(),
-        cli.authentication()
-            .ok_or_else(|| anyhow!("Unable to locate authentication"))?,
-    )?;
+    init_tracing(cli.debug_level);
+    let mut config = Config::load()?;
 
     match cli.command {
-        Command::Issues(cmd) => match cmd {
-            IssuesCmd::Get { ref key, ref opts } => {
-                let key = key.access()?.read_to_string()?;
-
-                let options = opts.into();
-                let issue = client.issues().get(key, Some(&options)).await?;
-
-                let stdout = stdout();
-                json_pretty(stdout, &issue)?;
-            }
-            IssuesCmd::Search { ref jql, ref opts } => {
-                let jql = jql.access()?.read_to_string()?;
-
-                let options = opts.as_options().with(|this| this.jql(jql));
-
-                let search = client.issues().search(Some(&options)).await?;
-
-                json_pretty(stdout(), &search)?;
-            }
-            IssuesCmd::Create { ref data, ref opts } => {
-                let options: issue::options::Create = opts.into();
-                let data = to_json(data)?;
-
-                let created = client.issues().create(&data, Some(&options)).await?;
-
-                json_pretty(stdout(), &created)?;
-            }
-            IssuesCmd::Edit { ref key, ref data } => {
-                let data = to_json(data)?;
-
-                client.issues().edit(key, &data).await?;
-
-                json_pretty(
-                    stdout(),
-                    &json::json!({key: "Successfully updated", "data": data}),
-                )?;
-            }
-            IssuesCmd::Meta { ref opts } => match MetaKind::from(opts) {
-                // User provided a specific issue
-                MetaKind::Edit(issue) => {
-                    let meta_edit = client.issues().meta_edit(issue).await?;
-
-                    json_pretty(stdout(), &meta_edit)?;
+        Command::Config(ref cmd) => config::run(cmd, &mut config, cli.profile.as_deref())?,
+        Command::Auth(ref cmd) => auth::run(cmd, cli.settings(&config)?).await?,
+        Command::Completions(ref cmd) => complete::script(cmd.shell, &mut stdout().lock())?,
+        Command::Complete(ref cmd) => {
+            // Completions must never print errors into the user's prompt
+            let candidates = match cli.settings(&config) {
+                Ok(settings) => {
+                    complete::candidates(&settings.profile, cmd.kind, &cmd.prefix, cmd.refresh)
+                        .await
+                        .unwrap_or_default()
                 }
-                // No issue, run a query based on options passed in
-                MetaKind::Create(options) => {
-                    let meta_create = client.issues().meta_create(Some(&options)).await?;
+                Err(_) => Vec::new(),
+            };
 
-                    json_pretty(stdout(), &meta_create)?;
+            for candidate in candidates {
+                match candidate.description {
+                    Some(description) => println!("{}\t{}", candidate.value, description),
+                    None => println!("{}", candidate.value),
                 }
-            },
-        },
+            }
+        }
+        Command::Issues(ref cmd) => {
+            let settings = cli.settings(&config)?;
+            let output = cli.output(&settings.profile)?;
+
+            issues::run(cmd, &settings.profile, &output).await?
+        }
+        Command::Filters(ref cmd) => {
+            let settings = cli.settings(&config)?;
+            let output = cli.output(&settings.profile)?;
+
+            filters::run(cmd, &settings.profile, &output).await?
+        }
+        Command::Versions(ref cmd) => {
+            let settings = cli.settings(&config)?;
+            let output = cli.output(&settings.profile)?;
+
+            versions::run(cmd, &settings.profile, &output).await?
+        }
+        Command::ReleaseNotes(ref cmd) => {
+            let settings = cli.settings(&config)?;
+            let output = cli.output(&settings.profile)?;
+
+            release_notes::run(cmd, &settings.profile, &output).await?
+        }
+        Command::Meta(ref cmd) => {
+            let settings = cli.settings(&config)?;
+            let output = cli.output(&settings.profile)?;
+
+            meta::run(cmd, &settings.profile, &output).await?
+        }
+        Command::Webhooks(ref cmd) => webhooks::run(cmd).await?,
+        Command::Workflow(ref cmd) => {
+            let settings = cli.settings(&config)?;
+            let output = cli.output(&settings.profile)?;
+
+            workflow::run(cmd, &settings.profile, &output).await?
+        }
+        Command::Sync(ref cmd) => {
+            let settings = cli.settings(&config)?;
+            let output = cli.output(&settings.profile)?;
+
+            sync::run(cmd, &settings.profile, &output).await?
+        }
     }
 
     Ok(())
 }
 
-fn to_json(i: &grab::Input) -> Result<Box<RawJson>> {
-    let src = i.access()?;
-
-    let json = json::from_reader(src)?;
-
-    Ok(json)
+/// Install a subscriber logging to stderr, based on the number of -v flags
+fn init_tracing(verbosity: u8) {
+    let filter = match (EnvFilter::try_from_default_env(), verbosity) {
+        (Ok(filter), _) => filter,
+        (Err(_), 0) => return,
+        (Err(_), 1) => EnvFilter::new("jira_rs=info"),
+        (Err(_), 2) => EnvFilter::new("jira_rs=debug"),
+        (Err(_), 3) => EnvFilter::new("jira_rs=trace"),
+        (Err(_), _) => EnvFilter::new("trace"),
+    };
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(stderr)
+        .with_ansi(stderr().is_terminal() && env::var_os("NO_COLOR").is_none())
+        .init()
 }
-
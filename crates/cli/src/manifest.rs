@@ -0,0 +1,511 @@
+use {
+    anyhow::{bail, Context as _, Result},
+    jira_rs::filter::{
+        models::Filter,
+        payload::{FilterDetails, Share},
+    },
+    serde::{Deserialize, Serialize},
+    serde_json::{json, Value},
+    std::{collections::BTreeSet, fs, path::Path},
+};
+
+/// A checked in list of saved filters, reconciled against the filters
+/// owned by the user with 'jira filters apply'.
+///
+/// Filters are matched by name, ignoring case. The manifest is the
+/// source of truth for every filter it lists: a missing description
+/// or share list means none. Filters the user owns which are not in
+/// the manifest are reported, but never changed.
+///
+/// ```yaml
+/// filters:
+///   - name: Open bugs
+///     jql: type = Bug AND resolution IS EMPTY
+///     description: Every unresolved bug
+///     share:
+///       - group:jira-developers
+///       - project-role:10000:10002
+///     favourite: true
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct FilterManifest {
+    filters: Vec<FilterSpec>,
+}
+
+/// The desired state of a single filter
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct FilterSpec {
+    name: String,
+    jql: String,
+    #[serde(default)]
+    description: Option<String>,
+    /// Who the filter is shared with, see `Share` for the syntax
+    #[serde(default)]
+    share: Vec<Share>,
+    /// Whether the filter is a favourite, unmanaged if unset
+    #[serde(default)]
+    favourite: Option<bool>,
+}
+
+/// What applying a manifest does, or did, to a single filter
+#[derive(Debug, Clone, Serialize)]
+pub struct Step {
+    pub name: String,
+    pub action: Action,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<Change>,
+    /// The request making the change, if any
+    #[serde(skip)]
+    pub details: Option<FilterDetails>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Create,
+    Update,
+    Unchanged,
+    /// Owned by the user, but not in the manifest
+    Unmanaged,
+}
+
+/// A difference between the manifest and the instance
+#[derive(Debug, Clone, Serialize)]
+pub struct Change {
+    pub field: &'static str,
+    pub from: Value,
+    pub to: Value,
+}
+
+impl FilterManifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = fs::read_to_string(path)
+            .with_context(|| format!("Unable to read {}", path.display()))?;
+
+        Self::parse(&data).with_context(|| format!("Invalid filter manifest {}", path.display()))
+    }
+
+    fn parse(data: &str) -> Result<Self> {
+        let manifest: Self = serde_yaml::from_str(data)?;
+
+        let mut seen = BTreeSet::new();
+        for spec in manifest.filters.iter() {
+            if !seen.insert(spec.name.to_lowercase()) {
+                bail!("'{}' is listed more than once", spec.name)
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// Compare the manifest with the user's existing filters, in manifest
+    /// order followed by any unmanaged filters
+    pub fn plan(&self, existing: &[Filter<'_>]) -> Vec<Step> {
+        let mut steps: Vec<_> = self
+            .filters
+            .iter()
+            .map(|spec| {
+                let found = existing
+                    .iter()
+                    .find(|f| f.name.eq_ignore_ascii_case(&spec.name));
+
+                match found {
+                    Some(filter) => spec.update(filter),
+                    None => spec.create(),
+                }
+            })
+            .collect();
+
+        let unmanaged = existing.iter().filter(|f| {
+            !self
+                .filters
+                .iter()
+                .any(|spec| f.name.eq_ignore_ascii_case(&spec.name))
+        });
+        steps.extend(unmanaged.map(|filter| Step {
+            name: filter.name.to_string(),
+            action: Action::Unmanaged,
+            id: Some(filter.id),
+            changes: Vec::new(),
+            details: None,
+        }));
+
+        steps
+    }
+}
+
+impl FilterSpec {
+    fn create(&self) -> Step {
+        let mut changes = vec![Change::new("jql", Value::Null, json!(self.jql))];
+        if let Some(ref description) = self.description {
+            changes.push(Change::new("description", Value::Null, json!(description)));
+        }
+        if !self.share.is_empty() {
+            changes.push(Change::new("share", Value::Null, shares(self.share.iter())));
+        }
+        if let Some(favourite) = self.favourite {
+            changes.push(Change::new("favourite", Value::Null, json!(favourite)));
+        }
+
+        Step {
+            name: self.name.clone(),
+            action: Action::Create,
+            id: None,
+            changes,
+            details: Some(self.details()),
+        }
+    }
+
+    fn update(&self, filter: &Filter<'_>) -> Step {
+        let mut changes = Vec::new();
+
+        if filter.name != self.name {
+            changes.push(Change::new("name", json!(filter.name), json!(self.name)));
+        }
+        if !same_jql(&filter.jql, &self.jql) {
+            changes.push(Change::new("jql", json!(filter.jql), json!(self.jql)));
+        }
+
+        let description = filter.description.as_deref().unwrap_or_default();
+        if description != self.description.as_deref().unwrap_or_default() {
+            changes.push(Change::new(
+                "description",
+                json!(description),
+                json!(self.description.as_deref().unwrap_or_default()),
+            ));
+        }
+
+        // Permissions which can't be represented in a manifest (e.g on
+        // projects the user can't see) are always drift
+        let current: Option<BTreeSet<Share>> = filter
+            .share_permissions
+            .iter()
+            .map(Share::from_permission)
+            .collect();
+        let wanted: BTreeSet<Share> = self.share.iter().cloned().collect();
+        if current.as_ref() != Some(&wanted) {
+            let from = filter.share_permissions.iter().map(|p| {
+                Share::from_permission(p)
+                    .map(|share| share.to_string())
+                    .unwrap_or_else(|| format!("{:?}", p.share_type).to_lowercase())
+            });
+            changes.push(Change::new(
+                "share",
+                json!(from.collect::<BTreeSet<_>>()),
+                shares(wanted.iter()),
+            ));
+        }
+
+        match self.favourite {
+            Some(favourite) if favourite != filter.favourite => changes.push(Change::new(
+                "favourite",
+                json!(filter.favourite),
+                json!(favourite),
+            )),
+            _ => {}
+        }
+
+        let (action, details) = match changes.is_empty() {
+            true => (Action::Unchanged, None),
+            false => (Action::Update, Some(self.details())),
+        };
+
+        Step {
+            name: self.name.clone(),
+            action,
+            id: Some(filter.id),
+            changes,
+            details,
+        }
+    }
+
+    /// The full desired state, sent for both creates and updates
+    fn details(&self) -> FilterDetails {
+        FilterDetails::new(&self.name).with(|this| {
+            this.jql(&self.jql)
+                .description(self.description.as_deref().unwrap_or_default())
+                .favourite(self.favourite)
+                .share_permissions(self.share.iter().cloned())
+        })
+    }
+}
+
+impl Change {
+    fn new(field: &'static str, from: Value, to: Value) -> Self {
+        Self { field, from, to }
+    }
+}
+
+fn shares<'a>(shares: impl Iterator<Item = &'a Share>) -> Value {
+    json!(shares.map(Share::to_string).collect::<BTreeSet<_>>())
+}
+
+/// Jira stores JQL canonicalized, e.g `type=bug` is returned as
+/// `type = bug`, so queries are compared by their tokens, ignoring
+/// whitespace, quoting and case
+fn same_jql(a: &str, b: &str) -> bool {
+    jql_tokens(a) == jql_tokens(b)
+}
+
+fn jql_tokens(jql: &str) -> Vec<String> {
+    const OPERATORS: &str = "=!~<>";
+    const DELIMITERS: &str = "\"'(),=!~<>";
+
+    let mut tokens = Vec::new();
+    let mut chars = jql.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let mut token = String::new();
+
+        match c {
+            c if c.is_whitespace() => continue,
+            '"' | '\'' => {
+                while let Some(next) = chars.next() {
+                    match next {
+                        '\\' => token.extend(chars.next()),
+                        quote if quote == c => break,
+                        other => token.push(other),
+                    }
+                }
+            }
+            '(' | ')' | ',' => token.push(c),
+            c if OPERATORS.contains(c) => {
+                token.push(c);
+                while let Some(next) = chars.next_if(|n| OPERATORS.contains(*n)) {
+                    token.push(next)
+                }
+            }
+            c => {
+                token.push(c);
+                while let Some(next) =
+                    chars.next_if(|n| !n.is_whitespace() && !DELIMITERS.contains(*n))
+                {
+                    token.push(next)
+                }
+            }
+        }
+
+        tokens.push(token.to_lowercase());
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        jira_rs::filter::models::FilterListHandle,
+        serde_json::{self as json, value::RawValue as RawJson},
+    };
+
+    const MANIFEST: &str = r#"
+filters:
+  - name: Open bugs
+    jql: type = Bug AND resolution IS EMPTY
+    description: Every unresolved bug
+    share: [global, group:jira-developers]
+    favourite: true
+  - name: My work
+    jql: assignee = currentUser() ORDER BY updated
+  - name: Releases
+    jql: type = Epic
+    share: [project-role:10000:10002]
+"#;
+
+    fn group(name: &str) -> Value {
+        json!({ "id": 10001, "type": "group", "group": { "name": name } })
+    }
+
+    fn filter(id: u64, name: &str, jql: &str, fields: Value) -> Value {
+        let mut filter = json!({
+            "self": format!("https://jira.example.com/rest/api/2/filter/{}", id),
+            "id": id.to_string(),
+            "name": name,
+            "jql": jql,
+        });
+        filter
+            .as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().cloned().unwrap_or_default());
+
+        filter
+    }
+
+    fn filters(filters: Vec<Value>) -> FilterListHandle {
+        FilterListHandle::try_new(RawJson::from_string(json::to_string(&filters).unwrap()).unwrap())
+            .unwrap()
+    }
+
+    fn plan(existing: Vec<Value>) -> Vec<Step> {
+        FilterManifest::parse(MANIFEST)
+            .unwrap()
+            .plan(filters(existing).data())
+    }
+
+    fn changed(step: &Step) -> Vec<&str> {
+        step.changes.iter().map(|c| c.field).collect()
+    }
+
+    /// The existing filter equal to the manifest's first, with its shares
+    /// in a different order
+    fn open_bugs() -> Value {
+        filter(
+            10000,
+            "Open bugs",
+            "type = Bug AND resolution IS EMPTY",
+            json!({
+                "description": "Every unresolved bug",
+                "favourite": true,
+                "sharePermissions": [group("jira-developers"), { "id": 1, "type": "global" }],
+            }),
+        )
+    }
+
+    #[test]
+    fn plan_actions() {
+        let steps = plan(vec![
+            filter(10002, "Someone else's", "project = FOO", json!({})),
+            open_bugs(),
+            filter(10001, "my work", "assignee = currentUser()", json!({})),
+        ]);
+        let actions: Vec<_> = steps
+            .iter()
+            .map(|s| (s.name.as_str(), s.action, s.id))
+            .collect();
+
+        assert_eq!(
+            actions,
+            [
+                ("Open bugs", Action::Unchanged, Some(10000)),
+                ("My work", Action::Update, Some(10001)),
+                ("Releases", Action::Create, None),
+                ("Someone else's", Action::Unmanaged, Some(10002)),
+            ]
+        );
+        assert!(steps[0].details.is_none() && steps[3].details.is_none());
+        assert_eq!(changed(&steps[1]), ["name", "jql"]);
+        assert_eq!(changed(&steps[2]), ["jql", "share"]);
+        assert_eq!(steps[2].changes[1].to, json!(["project-role:10000:10002"]));
+    }
+
+    #[test]
+    fn canonical_jql() {
+        let bugs = |jql: &str| {
+            let mut bugs = open_bugs();
+            bugs["jql"] = json!(jql);
+
+            plan(vec![bugs]).remove(0)
+        };
+
+        // As Jira returns the manifest's query
+        for jql in [
+            "type = Bug AND resolution IS EMPTY",
+            "type=Bug and resolution is EMPTY",
+            "  type = \"Bug\"\tAND\n resolution IS EMPTY ",
+            "TYPE = bug AND RESOLUTION IS empty",
+        ] {
+            assert_eq!(bugs(jql).action, Action::Unchanged, "{}", jql);
+        }
+
+        for jql in [
+            "type = Story AND resolution IS EMPTY",
+            "type = Bug AND resolution IS NOT EMPTY",
+            "type = Bug AND resolution IS EMPTY ORDER BY created",
+        ] {
+            assert_eq!(changed(&bugs(jql)), ["jql"], "{}", jql);
+        }
+
+        assert!(same_jql(
+            "status in (Open,\"In Progress\") and summary ~ 'it\\'s'",
+            "status IN (Open, \"In Progress\") AND summary ~ \"it's\"",
+        ));
+        assert!(!same_jql("summary ~ \"a b\"", "summary ~ \"a  b\""));
+        assert!(!same_jql("created >= -1d", "created > -1d"));
+    }
+
+    #[test]
+    fn description_defaults_to_none() {
+        let mine = |description: Value| {
+            filter(
+                10001,
+                "My work",
+                "assignee = currentUser() ORDER BY updated",
+                json!({ "description": description }),
+            )
+        };
+
+        let steps = plan(vec![open_bugs(), mine(Value::Null)]);
+        assert_eq!(steps[1].action, Action::Unchanged);
+
+        let steps = plan(vec![open_bugs(), mine(json!(""))]);
+        assert_eq!(steps[1].action, Action::Unchanged);
+
+        let steps = plan(vec![open_bugs(), mine(json!("Old notes"))]);
+        assert_eq!(changed(&steps[1]), ["description"]);
+        assert_eq!(steps[1].changes[0].to, json!(""));
+    }
+
+    #[test]
+    fn share_drift() {
+        let shared = |shares: Value, favourite: bool| {
+            let mut bugs = open_bugs();
+            bugs["sharePermissions"] = shares;
+            bugs["favourite"] = json!(favourite);
+
+            plan(vec![bugs]).remove(0)
+        };
+
+        let step = shared(json!([{ "type": "global" }]), true);
+        assert_eq!(changed(&step), ["share"]);
+        assert_eq!(step.changes[0].from, json!(["global"]));
+        assert_eq!(
+            step.changes[0].to,
+            json!(["global", "group:jira-developers"])
+        );
+
+        // Shares the manifest can't express are always drift
+        let unknown = json!([
+            { "type": "global" },
+            group("jira-developers"),
+            { "type": "project-unknown" },
+        ]);
+        let step = shared(unknown, true);
+        assert_eq!(changed(&step), ["share"]);
+        assert_eq!(
+            step.changes[0].from,
+            json!(["global", "group:jira-developers", "projectunknown"])
+        );
+
+        let step = shared(open_bugs()["sharePermissions"].clone(), false);
+        assert_eq!(changed(&step), ["favourite"]);
+    }
+
+    #[test]
+    fn unmanaged_favourites() {
+        let mine = filter(
+            10001,
+            "My work",
+            "assignee = currentUser() ORDER BY updated",
+            json!({ "favourite": true }),
+        );
+
+        let steps = plan(vec![open_bugs(), mine]);
+        assert_eq!(steps[1].action, Action::Unchanged);
+    }
+
+    #[test]
+    fn duplicate_names() {
+        let manifest = "filters:\n  - { name: Bugs, jql: a }\n  - { name: bugs, jql: b }";
+        let error = FilterManifest::parse(manifest).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("'bugs' is listed more than once"));
+        assert!(FilterManifest::parse("filters: [{ name: a, jql: b, extra: c }]").is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use {
+    crate::{cli::Meta as MetaCmd, config::Profile, output::Output},
+    anyhow::{bail, Result},
+    jira_rs::{client::Jira, issue, issue::models::MetaCreateHandle},
+};
+
+pub async fn run(cmd: &MetaCmd, settings: &Profile, output: &Output) -> Result<()> {
+    let client = settings.client()?;
+    let meta = client.meta();
+
+    match cmd {
+        MetaCmd::Statuses(ref lookup) => {
+            let statuses = meta.statuses().await?;
+            output.render(&lookup.find("status", &statuses)?)
+        }
+        MetaCmd::StatusCategories(ref lookup) => {
+            let categories = meta.status_categories().await?;
+            output.render(&lookup.find("status category", &categories)?)
+        }
+        MetaCmd::Priorities(ref lookup) => {
+            let priorities = meta.priorities().await?;
+            output.render(&lookup.find("priority", &priorities)?)
+        }
+        MetaCmd::Resolutions(ref lookup) => {
+            let resolutions = meta.resolutions().await?;
+            output.render(&lookup.find("resolution", &resolutions)?)
+        }
+        MetaCmd::IssueTypes {
+            project: None,
+            ref lookup,
+        } => {
+            let types = meta.issue_types().await?;
+            output.render(&lookup.find("issue type", &types)?)
+        }
+        MetaCmd::IssueTypes {
+            project: Some(ref project),
+            ref lookup,
+        } => {
+            let id = match project.parse() {
+                Ok(id) => id,
+                Err(_) => {
+                    project_meta(&client, project).await?.data().projects[0]
+                        .project
+                        .id
+                }
+            };
+
+            let types = meta.project_issue_types(id).await?;
+            output.render(&lookup.find("issue type", &types)?)
+        }
+        MetaCmd::Labels(ref lookup) => {
+            let mut labels = Vec::new();
+            loop {
+                let options = jira_rs::meta::options::Labels::new()
+                    .with(|this| this.start_at(labels.len() as u32));
+                let page = meta.labels(Some(&options)).await?;
+                let data = page.data();
+
+                labels.extend(data.values.iter().map(|label| label.to_string()));
+                if data.is_last || data.values.is_empty() {
+                    break;
+                }
+            }
+
+            output.render(&lookup.find("label", &labels)?)
+        }
+        MetaCmd::ServerInfo => output.render(&meta.server_info().await?),
+    }
+}
+
+/// Retrieve the create metadata of a project given by key or id, which
+/// includes its id and issue types
+pub async fn project_meta(client: &Jira, project: &str) -> Result<MetaCreateHandle> {
+    let options = issue::options::MetaCreate::new().with(|this| match project.parse::<u64>() {
+        Ok(id) => this.project_ids(Some(id)),
+        Err(_) => this.project_keys(Some(project)),
+    });
+
+    let meta = client.issues().meta_create(Some(&options)).await?;
+    if meta.data().projects.is_empty() {
+        bail!("No project '{}'", project)
+    }
+
+    Ok(meta)
+}
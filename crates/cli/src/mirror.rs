@@ -0,0 +1,38 @@
+use {
+    crate::{complete::cache_dir, config::Profile},
+    anyhow::{bail, Context, Result},
+    jira_sync::Store,
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+const MIRROR_DIR: &str = "mirror";
+
+/// Open the mirror at the given path, or the profile's default mirror
+/// under the cache directory
+pub fn open(profile: &Profile, db: Option<&Path>) -> Result<Store> {
+    let path = match db {
+        Some(path) => path.to_path_buf(),
+        None => default_path(profile)?,
+    };
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Unable to create {}", parent.display()))?;
+    }
+
+    Store::open(&path).with_context(|| format!("Unable to open the mirror at {}", path.display()))
+}
+
+fn default_path(profile: &Profile) -> Result<PathBuf> {
+    if profile.host.is_none() {
+        bail!("No Jira host configured, use --db to choose a mirror")
+    }
+
+    Ok(cache_dir()?
+        .join(MIRROR_DIR)
+        .join(profile.host_slug())
+        .with_extension("sqlite"))
+}
@@ -0,0 +1,494 @@
+use {
+    crate::output::{Scope, Template},
+    anyhow::Result,
+    jira_rs::{issue::models::Issue, version::models::Version},
+    serde::Serialize,
+    serde_json::{self as json, Map, Value},
+    std::{collections::BTreeMap, io::Write, str::FromStr},
+};
+
+const MARKDOWN: &str = r#"# {{ project }} {{ version.name }}
+{%- if version.releaseDate %} ({{ version.releaseDate }}){% endif %}
+{%- if version.description %}
+
+{{ version.description }}
+{%- endif %}
+{%- for group in groups %}
+
+## {{ group.name }}
+{% for issue in group.issues %}
+- [{{ issue.key }}]({{ issue.url }}) {{ issue.summary }}
+{%- endfor %}
+{%- else %}
+
+No issues are fixed in this version.
+{%- endfor %}
+"#;
+
+const HTML: &str = r#"<h1>{{ project|e }} {{ version.name|e }}
+{%- if version.releaseDate %} ({{ version.releaseDate|e }}){% endif %}</h1>
+{%- if version.description %}
+<p>{{ version.description|e }}</p>
+{%- endif %}
+{%- for group in groups %}
+<h2>{{ group.name|e }}</h2>
+<ul>
+{%- for issue in group.issues %}
+  <li><a href="{{ issue.url|e }}">{{ issue.key|e }}</a> {{ issue.summary|e }}</li>
+{%- endfor %}
+</ul>
+{%- else %}
+<p>No issues are fixed in this version.</p>
+{%- endfor %}
+"#;
+
+const TEXT: &str = r#"{{ project }} {{ version.name }}
+{%- if version.releaseDate %} ({{ version.releaseDate }}){% endif %}
+{%- if version.description %}
+
+{{ version.description }}
+{%- endif %}
+{%- for group in groups %}
+
+{{ group.name }}
+{%- for issue in group.issues %}
+  * {{ issue.key }}: {{ issue.summary }}
+{%- endfor %}
+{%- else %}
+
+No issues are fixed in this version.
+{%- endfor %}
+"#;
+
+/// The built in release note formats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotesFormat {
+    Markdown,
+    Html,
+    Text,
+}
+
+/// How the issues of a release are grouped
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Grouping {
+    /// By issue type, e.g Bug, Story
+    Type,
+    /// By component, issues in several components are listed in each
+    Component,
+    /// By label, issues with several labels are listed under each
+    Label,
+}
+
+/// Everything a release notes template is rendered against.
+///
+/// ```yaml
+/// project: FOO
+/// version: { name: 1.4.0, releaseDate: 2021-03-04, ... }
+/// url: https://jira.example.com/browse/FOO/fixforversion/10000
+/// group_by: type
+/// groups:
+///   - name: Bug
+///     issues: [{ key: FOO-1, url: ..., summary: ..., fields: {...} }]
+/// issues: [...]
+/// ```
+#[derive(Debug, Serialize)]
+pub struct ReleaseNotes {
+    pub project: String,
+    pub version: Value,
+    pub url: String,
+    pub group_by: Grouping,
+    pub groups: Vec<Group>,
+    /// Every issue in the release, ungrouped
+    pub issues: Vec<NoteIssue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Group {
+    pub name: String,
+    pub issues: Vec<NoteIssue>,
+}
+
+/// An issue in a release, with the commonly used fields flattened to
+/// display strings. The issue's raw fields are available as `fields`
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteIssue {
+    pub key: String,
+    pub url: String,
+    pub summary: String,
+    #[serde(rename = "type")]
+    pub issue_type: String,
+    pub status: String,
+    pub components: Vec<String>,
+    pub labels: Vec<String>,
+    pub fields: Map<String, Value>,
+}
+
+impl ReleaseNotes {
+    /// Build the notes of a version from its issues. `host` is the base
+    /// URL links are built from
+    pub fn new(
+        host: &str,
+        project: &str,
+        version: &Version<'_>,
+        issues: &[Issue<'_>],
+        group_by: Grouping,
+    ) -> Result<Self> {
+        let host = host.trim_end_matches('/');
+        let issues = issues
+            .iter()
+            .map(|issue| NoteIssue::new(host, issue))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            project: project.to_string(),
+            version: json::to_value(version)?,
+            url: format!("{}/browse/{}/fixforversion/{}", host, project, version.id),
+            group_by,
+            groups: group_by.groups(&issues),
+            issues,
+        })
+    }
+
+    /// Render the notes with one of the built in templates
+    pub fn render<W>(&self, out: &mut W, format: NotesFormat) -> Result<()>
+    where
+        W: Write,
+    {
+        let source = match format {
+            NotesFormat::Markdown => MARKDOWN,
+            NotesFormat::Html => HTML,
+            NotesFormat::Text => TEXT,
+        };
+
+        Template::new(source.to_string(), Scope::Response)?.render(out, self)
+    }
+}
+
+impl NoteIssue {
+    fn new(host: &str, issue: &Issue<'_>) -> Result<Self> {
+        let fields: Map<String, Value> = issue
+            .fields
+            .iter()
+            .map(|(name, raw)| Ok((name.to_string(), json::from_str(raw.get())?)))
+            .collect::<Result<_, json::Error>>()?;
+
+        let name = |field: &str| {
+            fields
+                .get(field)
+                .and_then(|value| value.get("name"))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let names = |field: &str| -> Vec<String> {
+            let values = fields.get(field).and_then(Value::as_array);
+
+            values
+                .into_iter()
+                .flatten()
+                .filter_map(|value| value.get("name").unwrap_or(value).as_str())
+                .map(str::to_string)
+                .collect()
+        };
+
+        Ok(Self {
+            key: issue.key.to_string(),
+            url: format!("{}/browse/{}", host, issue.key),
+            summary: fields
+                .get("summary")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            issue_type: name("issuetype"),
+            status: name("status"),
+            components: names("components"),
+            labels: names("labels"),
+            fields,
+        })
+    }
+}
+
+impl Grouping {
+    /// The name of the group for issues without any component or label
+    fn fallback(&self) -> &'static str {
+        match self {
+            Self::Type => "Other",
+            Self::Component => "No component",
+            Self::Label => "No label",
+        }
+    }
+
+    /// Group the issues by name, with the fallback group last
+    fn groups(&self, issues: &[NoteIssue]) -> Vec<Group> {
+        let mut groups: BTreeMap<&str, Vec<NoteIssue>> = BTreeMap::new();
+        let mut rest = Vec::new();
+
+        for issue in issues {
+            let names = match self {
+                Self::Type if issue.issue_type.is_empty() => Vec::new(),
+                Self::Type => vec![issue.issue_type.as_str()],
+                Self::Component => issue.components.iter().map(String::as_str).collect(),
+                Self::Label => issue.labels.iter().map(String::as_str).collect(),
+            };
+
+            if names.is_empty() {
+                rest.push(issue.clone())
+            }
+            for name in names {
+                groups.entry(name).or_default().push(issue.clone())
+            }
+        }
+
+        let mut groups: Vec<_> = groups
+            .into_iter()
+            .map(|(name, issues)| Group {
+                name: name.to_string(),
+                issues,
+            })
+            .collect();
+        if !rest.is_empty() {
+            groups.push(Group {
+                name: self.fallback().to_string(),
+                issues: rest,
+            })
+        }
+
+        groups
+    }
+}
+
+impl FromStr for NotesFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            "text" => Ok(Self::Text),
+            _ => Err(format!(
+                "expected one of [markdown, html, text], got '{}'",
+                s
+            )),
+        }
+    }
+}
+
+impl FromStr for Grouping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "type" => Ok(Self::Type),
+            "component" => Ok(Self::Component),
+            "label" => Ok(Self::Label),
+            _ => Err(format!(
+                "expected one of [type, component, label], got '{}'",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        jira_rs::issue::models::IssueHandle,
+        serde_json::{json, value::RawValue as RawJson},
+    };
+
+    const HOST: &str = "https://jira.example.com/";
+
+    fn issue(key: &str, summary: &str, fields: Value) -> IssueHandle {
+        let mut raw = json!({
+            "self": "x",
+            "id": key[4..],
+            "key": key,
+            "fields": { "summary": summary, "status": { "name": "Done" } },
+        });
+        raw["fields"]
+            .as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().cloned().unwrap_or_default());
+
+        IssueHandle::try_new(RawJson::from_string(raw.to_string()).unwrap()).unwrap()
+    }
+
+    fn issues() -> Vec<IssueHandle> {
+        let components = |names: &[&str]| -> Value {
+            names
+                .iter()
+                .map(|name| json!({ "id": "1", "name": name }))
+                .collect()
+        };
+
+        vec![
+            issue(
+                "FOO-1",
+                "Crash on <save> & exit",
+                json!({
+                    "issuetype": { "name": "Bug" },
+                    "components": components(&["Backend"]),
+                    "labels": ["ui"],
+                }),
+            ),
+            issue(
+                "FOO-2",
+                "Add export",
+                json!({
+                    "issuetype": { "name": "Story" },
+                    "components": components(&["Backend", "UI"]),
+                }),
+            ),
+            issue(
+                "FOO-3",
+                "Tidy docs",
+                json!({ "issuetype": { "name": "Task" }, "labels": ["docs", "ui"] }),
+            ),
+            issue("FOO-4", "Untyped", json!({})),
+        ]
+    }
+
+    fn notes(issues: &[IssueHandle], group_by: Grouping) -> ReleaseNotes {
+        let version = json!({
+            "archived": false,
+            "id": "10000",
+            "name": "1.4.0",
+            "released": true,
+            "self": "x",
+            "releaseDate": "2021-03-04",
+        })
+        .to_string();
+        let version: Version<'_> = json::from_str(&version).unwrap();
+        let issues: Vec<_> = issues.iter().map(|i| i.data().clone()).collect();
+
+        ReleaseNotes::new(HOST, "FOO", &version, &issues, group_by).unwrap()
+    }
+
+    fn groups(notes: &ReleaseNotes) -> Vec<(&str, Vec<&str>)> {
+        notes
+            .groups
+            .iter()
+            .map(|g| {
+                let keys = g.issues.iter().map(|i| i.key.as_str()).collect();
+                (g.name.as_str(), keys)
+            })
+            .collect()
+    }
+
+    fn render(notes: &ReleaseNotes, format: NotesFormat) -> String {
+        let mut out = Vec::new();
+        notes.render(&mut out, format).unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn grouping() {
+        let issues = issues();
+
+        assert_eq!(
+            groups(&notes(&issues, Grouping::Type)),
+            [
+                ("Bug", vec!["FOO-1"]),
+                ("Story", vec!["FOO-2"]),
+                ("Task", vec!["FOO-3"]),
+                ("Other", vec!["FOO-4"]),
+            ]
+        );
+        assert_eq!(
+            groups(&notes(&issues, Grouping::Component)),
+            [
+                ("Backend", vec!["FOO-1", "FOO-2"]),
+                ("UI", vec!["FOO-2"]),
+                ("No component", vec!["FOO-3", "FOO-4"]),
+            ]
+        );
+        assert_eq!(
+            groups(&notes(&issues, Grouping::Label)),
+            [
+                ("docs", vec!["FOO-3"]),
+                ("ui", vec!["FOO-1", "FOO-3"]),
+                ("No label", vec!["FOO-2", "FOO-4"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn links() {
+        let notes = notes(&issues(), Grouping::Type);
+
+        assert_eq!(
+            notes.url,
+            "https://jira.example.com/browse/FOO/fixforversion/10000"
+        );
+        assert_eq!(notes.issues[0].url, "https://jira.example.com/browse/FOO-1");
+        assert_eq!(notes.issues[3].issue_type, "");
+        assert_eq!(notes.issues[1].components, ["Backend", "UI"]);
+    }
+
+    #[test]
+    fn render_markdown() {
+        let issues = issues();
+
+        assert_eq!(
+            render(&notes(&issues[..2], Grouping::Type), NotesFormat::Markdown),
+            "# FOO 1.4.0 (2021-03-04)\n\
+             \n\
+             ## Bug\n\
+             \n\
+             - [FOO-1](https://jira.example.com/browse/FOO-1) Crash on <save> & exit\n\
+             \n\
+             ## Story\n\
+             \n\
+             - [FOO-2](https://jira.example.com/browse/FOO-2) Add export\n"
+        );
+    }
+
+    #[test]
+    fn render_html() {
+        let issues = issues();
+
+        assert_eq!(
+            render(&notes(&issues[..1], Grouping::Type), NotesFormat::Html),
+            "<h1>FOO 1.4.0 (2021-03-04)</h1>\n\
+             <h2>Bug</h2>\n\
+             <ul>\n  \
+             <li><a href=\"https:&#x2f;&#x2f;jira.example.com&#x2f;browse&#x2f;FOO-1\">FOO-1</a> \
+             Crash on &lt;save&gt; &amp; exit</li>\n\
+             </ul>\n"
+        );
+    }
+
+    #[test]
+    fn render_text() {
+        let issues = issues();
+
+        assert_eq!(
+            render(&notes(&issues[2..], Grouping::Label), NotesFormat::Text),
+            "FOO 1.4.0 (2021-03-04)\n\
+             \n\
+             docs\n  \
+             * FOO-3: Tidy docs\n\
+             \n\
+             ui\n  \
+             * FOO-3: Tidy docs\n\
+             \n\
+             No label\n  \
+             * FOO-4: Untyped\n"
+        );
+    }
+
+    #[test]
+    fn empty_release() {
+        let notes = notes(&[], Grouping::Type);
+
+        assert!(render(&notes, NotesFormat::Markdown)
+            .ends_with("\n\nNo issues are fixed in this version.\n"));
+        assert!(render(&notes, NotesFormat::Html)
+            .ends_with("<p>No issues are fixed in this version.</p>\n"));
+        assert!(render(&notes, NotesFormat::Text)
+            .ends_with("\n\nNo issues are fixed in this version.\n"));
+    }
+}
@@ -0,0 +1,503 @@
+pub use template::{Scope, Template};
+
+use {
+    anyhow::{bail, Result},
+    jira_rs::issue::models::Issue,
+    serde::{Deserialize, Serialize},
+    serde_json::{self as json, Map, Value},
+    std::{
+        fmt,
+        io::{self, Write},
+        str::FromStr,
+    },
+};
+
+mod template;
+
+/// The columns used by table and CSV output when none are given
+pub const DEFAULT_COLUMNS: &[&str] = &["key", "status", "assignee", "summary"];
+
+/// How command results are written to stdout
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Format {
+    #[default]
+    Json,
+    JsonCompact,
+    Ndjson,
+    Yaml,
+    Table,
+    Csv,
+    Tsv,
+}
+
+/// Renders command results in the configured format
+#[derive(Debug)]
+pub struct Output {
+    format: Format,
+    columns: Vec<String>,
+    template: Option<Template>,
+}
+
+impl Output {
+    pub fn new(format: Format, columns: Vec<String>) -> Self {
+        let columns = match columns.is_empty() {
+            true => DEFAULT_COLUMNS.iter().map(|c| c.to_string()).collect(),
+            false => columns,
+        };
+
+        Self {
+            format,
+            columns,
+            template: None,
+        }
+    }
+
+    /// Render results with the given template, rather than the format
+    pub fn template(&mut self, template: Template) -> &mut Self {
+        self.template = Some(template);
+        self
+    }
+
+    /// Has the user given a template to render results with?
+    pub fn has_template(&self) -> bool {
+        self.template.is_some()
+    }
+
+    /// Render an arbitrary result. Tabular formats render objects as
+    /// key / value rows and arrays of objects as one row per element
+    pub fn render<T>(&self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let out = io::stdout();
+        let mut out = out.lock();
+
+        if let Some(ref template) = self.template {
+            return template.render(&mut out, value);
+        }
+
+        match self.format {
+            Format::Json | Format::JsonCompact | Format::Ndjson | Format::Yaml => {
+                self.serialized(&mut out, value)
+            }
+            Format::Table | Format::Csv | Format::Tsv => {
+                let (header, rows) = tabulate(json::to_value(value)?);
+                self.tabular(&mut out, &header, &rows)
+            }
+        }
+    }
+
+    /// Render a set of issues, `whole` is the full response they were
+    /// taken from (e.g a search) which is used by the document formats
+    pub fn render_issues<T>(&self, whole: &T, issues: &[Issue<'_>]) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let out = io::stdout();
+
+        self.write_issues(&mut out.lock(), whole, issues)
+    }
+
+    fn write_issues<W, T>(&self, out: &mut W, whole: &T, issues: &[Issue<'_>]) -> Result<()>
+    where
+        W: Write,
+        T: Serialize,
+    {
+        match self.template {
+            Some(ref t) if t.scope == Scope::Issue => {
+                return issues.iter().try_for_each(|issue| t.render(out, issue))
+            }
+            Some(ref t) => return t.render(out, whole),
+            None => {}
+        }
+
+        match self.format {
+            Format::Json | Format::JsonCompact | Format::Yaml => self.serialized(out, whole),
+            // One issue per line, for piping into jq & friends
+            Format::Ndjson => issues
+                .iter()
+                .try_for_each(|issue| self.serialized(out, issue)),
+            Format::Table | Format::Csv | Format::Tsv => {
+                let header: Vec<String> = self.columns.iter().map(|c| c.to_uppercase()).collect();
+                let rows: Vec<Vec<String>> = issues
+                    .iter()
+                    .map(|issue| self.columns.iter().map(|c| column(issue, c)).collect())
+                    .collect();
+
+                self.tabular(out, &header, &rows)
+            }
+        }
+    }
+
+    fn serialized<W, T>(&self, out: &mut W, value: &T) -> Result<()>
+    where
+        W: Write,
+        T: Serialize,
+    {
+        match self.format {
+            Format::Json => json::to_writer_pretty(&mut *out, value)?,
+            // Normalize any raw JSON (e.g issue fields) so it is guaranteed
+            // to fit on one line, and readable by non JSON serializers
+            Format::JsonCompact | Format::Ndjson => {
+                json::to_writer(&mut *out, &json::to_value(value)?)?
+            }
+            Format::Yaml => serde_yaml::to_writer(&mut *out, &json::to_value(value)?)?,
+            _ => unreachable!("only called for serialized formats"),
+        }
+
+        writeln!(out)?;
+        Ok(())
+    }
+
+    fn tabular<W>(&self, out: &mut W, header: &[String], rows: &[Vec<String>]) -> Result<()>
+    where
+        W: Write,
+    {
+        match self.format {
+            Format::Table => table(out, header, rows)?,
+            Format::Csv => delimited(out, header, rows, ',', csv_escape)?,
+            Format::Tsv => delimited(out, header, rows, '\t', tsv_escape)?,
+            _ => unreachable!("only called for tabular formats"),
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolve a column from an issue, `key` and `id` refer to the issue
+/// itself, anything else is looked up in the issue's fields
+fn column(issue: &Issue<'_>, name: &str) -> String {
+    let field = match name {
+        "key" => return issue.key.to_string(),
+        "id" => return issue.id.to_string(),
+        "type" => "issuetype",
+        other => other,
+    };
+
+    issue
+        .fields
+        .get(field)
+        .and_then(|raw| json::from_str::<Value>(raw.get()).ok())
+        .map(|value| display(&value))
+        .unwrap_or_default()
+}
+
+/// Render a JSON value as a single cell, preferring the human readable
+/// name of Jira's objects (users, statuses, options, etc)
+fn display(value: &Value) -> String {
+    const NAMES: [&str; 5] = ["displayName", "name", "value", "key", "id"];
+
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Number(_) | Value::Bool(_) => value.to_string(),
+        Value::Array(values) => values.iter().map(display).collect::<Vec<_>>().join(", "),
+        Value::Object(map) => NAMES
+            .iter()
+            .find_map(|k| map.get(*k))
+            .map(display)
+            .unwrap_or_else(|| value.to_string()),
+    }
+}
+
+/// Convert an arbitrary value into a header and rows
+fn tabulate(value: Value) -> (Vec<String>, Vec<Vec<String>>) {
+    match value {
+        Value::Array(values) if values.iter().all(Value::is_object) => {
+            let mut header: Vec<String> = Vec::new();
+            for key in values
+                .iter()
+                .filter_map(Value::as_object)
+                .flat_map(Map::keys)
+            {
+                if !header.contains(key) {
+                    header.push(key.clone())
+                }
+            }
+
+            let rows = values
+                .iter()
+                .map(|v| header.iter().map(|k| display(&v[k])).collect())
+                .collect();
+
+            (header.iter().map(|k| k.to_uppercase()).collect(), rows)
+        }
+        Value::Object(map) => {
+            let rows = map
+                .iter()
+                .map(|(k, v)| vec![k.clone(), display(v)])
+                .collect();
+
+            (vec!["KEY".into(), "VALUE".into()], rows)
+        }
+        other => (vec!["VALUE".into()], vec![vec![display(&other)]]),
+    }
+}
+
+fn table<W>(out: &mut W, header: &[String], rows: &[Vec<String>]) -> io::Result<()>
+where
+    W: Write,
+{
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count())
+        }
+    }
+
+    let mut line = |cells: &[String]| -> io::Result<()> {
+        let last = cells.len().saturating_sub(1);
+        for (i, (cell, width)) in cells.iter().zip(&widths).enumerate() {
+            match i == last {
+                true => write!(out, "{}", cell)?,
+                false => write!(out, "{:<width$}  ", cell, width = width)?,
+            }
+        }
+        writeln!(out)
+    };
+
+    line(header)?;
+    rows.iter().try_for_each(|row| line(row))
+}
+
+fn delimited<W>(
+    out: &mut W,
+    header: &[String],
+    rows: &[Vec<String>],
+    delimiter: char,
+    escape: fn(&str) -> String,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let separator = delimiter.to_string();
+
+    Some(header)
+        .into_iter()
+        .chain(rows.iter().map(Vec::as_slice))
+        .try_for_each(|cells| {
+            let cells: Vec<String> = cells.iter().map(|c| escape(c)).collect();
+            writeln!(out, "{}", cells.join(&separator))
+        })
+}
+
+fn csv_escape(cell: &str) -> String {
+    match cell.contains(&[',', '"', '\n', '\r'][..]) {
+        true => format!("\"{}\"", cell.replace('"', "\"\"")),
+        false => cell.to_string(),
+    }
+}
+
+fn tsv_escape(cell: &str) -> String {
+    cell.replace(&['\t', '\n', '\r'][..], " ")
+}
+
+impl Format {
+    pub const ALL: [Format; 7] = [
+        Format::Json,
+        Format::JsonCompact,
+        Format::Ndjson,
+        Format::Yaml,
+        Format::Table,
+        Format::Csv,
+        Format::Tsv,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::JsonCompact => "json-compact",
+            Format::Ndjson => "ndjson",
+            Format::Yaml => "yaml",
+            Format::Table => "table",
+            Format::Csv => "csv",
+            Format::Tsv => "tsv",
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Format::ALL.iter().find(|f| f.as_str() == s) {
+            Some(format) => Ok(*format),
+            None => {
+                let formats: Vec<_> = Format::ALL.iter().map(Format::as_str).collect();
+                bail!("expected one of [{}], got '{}'", formats.join(", "), s)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        jira_rs::issue::models::IssueHandle,
+        serde_json::{json, value::RawValue as RawJson},
+    };
+
+    fn issue() -> IssueHandle {
+        let raw = json!({
+            "self": "https://jira.example.com/rest/api/2/issue/10000",
+            "id": "10000",
+            "key": "FOO-1",
+            "fields": {
+                "summary": "Fix the login page",
+                "issuetype": { "id": "1", "name": "Bug" },
+                "status": { "id": "3", "name": "In Progress" },
+                "assignee": { "name": "ann", "displayName": "Ann Smith" },
+                "labels": ["backend", "urgent"],
+                "customfield_10000": 5,
+                "reporter": null,
+            },
+        });
+
+        IssueHandle::try_new(RawJson::from_string(raw.to_string()).unwrap()).unwrap()
+    }
+
+    fn written(f: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> String {
+        let mut out = Vec::new();
+        f(&mut out).unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    fn strings(cells: &[&str]) -> Vec<String> {
+        cells.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn issue_columns() {
+        let issue = issue();
+        let columns = [
+            "key",
+            "id",
+            "type",
+            "status",
+            "assignee",
+            "labels",
+            "customfield_10000",
+            "reporter",
+            "missing",
+        ];
+
+        assert_eq!(
+            columns
+                .iter()
+                .map(|c| column(issue.data(), c))
+                .collect::<Vec<_>>(),
+            [
+                "FOO-1",
+                "10000",
+                "Bug",
+                "In Progress",
+                "Ann Smith",
+                "backend, urgent",
+                "5",
+                "",
+                ""
+            ]
+        );
+    }
+
+    #[test]
+    fn tabulate_values() {
+        let (header, rows) = tabulate(json!([
+            { "name": "1.0", "released": true },
+            { "name": "1.1", "releaseDate": "2021-03-04" },
+        ]));
+        assert_eq!(header, ["NAME", "RELEASED", "RELEASEDATE"]);
+        assert_eq!(
+            rows,
+            [
+                strings(&["1.0", "true", ""]),
+                strings(&["1.1", "", "2021-03-04"])
+            ]
+        );
+
+        let (header, rows) = tabulate(json!({ "id": 1, "lead": { "displayName": "Ann" } }));
+        assert_eq!(header, ["KEY", "VALUE"]);
+        assert_eq!(rows, [strings(&["id", "1"]), strings(&["lead", "Ann"])]);
+
+        let (header, rows) = tabulate(json!([1, { "a": 2 }]));
+        assert_eq!(header, ["VALUE"]);
+        assert_eq!(rows, [strings(&["1, {\"a\":2}"])]);
+    }
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(csv_escape("plain text"), "plain text");
+        assert_eq!(csv_escape("a, b"), "\"a, b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_escape("two\nlines"), "\"two\nlines\"");
+
+        let header = strings(&["KEY", "SUMMARY"]);
+        let rows = [strings(&["FOO-1", "Fix, \"it\""])];
+        assert_eq!(
+            written(|out| delimited(out, &header, &rows, ',', csv_escape)),
+            "KEY,SUMMARY\nFOO-1,\"Fix, \"\"it\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn tsv_cells() {
+        let header = strings(&["KEY", "SUMMARY"]);
+        let rows = [strings(&["FOO-1", "tabs\tand\nnewlines, \"quotes\""])];
+
+        assert_eq!(
+            written(|out| delimited(out, &header, &rows, '\t', tsv_escape)),
+            "KEY\tSUMMARY\nFOO-1\ttabs and newlines, \"quotes\"\n"
+        );
+    }
+
+    #[test]
+    fn template_scopes() {
+        let (first, second) = (issue(), issue());
+        let issues = [first.data().clone(), second.data().clone()];
+        let whole = json!({ "total": 2, "issues": issues });
+
+        let render = |source: &str, scope: Scope| {
+            let mut output = Output::new(Format::Json, Vec::new());
+            output.template(Template::new(source.to_string(), scope).unwrap());
+
+            let mut out = Vec::new();
+            output.write_issues(&mut out, &whole, &issues).unwrap();
+
+            String::from_utf8(out).unwrap()
+        };
+
+        assert_eq!(render("{{ key }}", Scope::Issue), "FOO-1\nFOO-1\n");
+        assert_eq!(
+            render(
+                "{{ total }}: {% for i in issues %}{{ i.key }} {% endfor %}",
+                Scope::Response
+            ),
+            "2: FOO-1 FOO-1 \n"
+        );
+    }
+
+    #[test]
+    fn table_alignment() {
+        let header = strings(&["KEY", "STATUS", "SUMMARY"]);
+        let rows = [
+            strings(&["FOO-10", "Done", "Write docs"]),
+            strings(&["FOO-1", "In Progress", "Fix"]),
+        ];
+
+        assert_eq!(
+            written(|out| table(out, &header, &rows)),
+            "KEY     STATUS       SUMMARY\n\
+             FOO-10  Done         Write docs\n\
+             FOO-1   In Progress  Fix\n"
+        );
+    }
+}
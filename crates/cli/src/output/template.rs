@@ -0,0 +1,275 @@
+use {
+    anyhow::{Context, Result},
+    jira_rs::time::{self, Tz},
+    minijinja::{Environment, Error, ErrorKind, Value},
+    serde::Serialize,
+    std::{
+        env,
+        io::{IsTerminal, Write},
+        str::FromStr,
+    },
+};
+
+const NAME: &str = "output";
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+const DEFAULT_TRUNCATE: usize = 80;
+const UNASSIGNED: &str = "Unassigned";
+
+/// What a template is rendered against when printing a list of issues
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scope {
+    /// Once per issue, with the issue as the context
+    Issue,
+    /// Once, with the whole response (e.g a search) as the context
+    Response,
+}
+
+/// A user supplied template, in minijinja (Jinja2) syntax.
+///
+/// On top of minijinja's builtins, the following filters are available:
+/// - `date(format?, timezone?)`: reformat a Jira timestamp or date, the
+///   timezone may be a zone name or a user (which uses their timezone)
+/// - `user`: a user's display name, or 'Unassigned'
+/// - `truncate(length?, end?)`: shorten text to at most `length` characters
+/// - `color(name)`: wrap text in an ANSI color or style, e.g 'red', 'bold'.
+///   Disabled when stdout is not a terminal or NO_COLOR is set
+#[derive(Debug)]
+pub struct Template {
+    env: Environment<'static>,
+    pub scope: Scope,
+}
+
+impl Template {
+    pub fn new(source: String, scope: Scope) -> Result<Self> {
+        let mut env = Environment::new();
+        let colors = colors_enabled();
+
+        env.add_filter("date", date);
+        env.add_filter("user", user);
+        env.add_filter("truncate", truncate);
+        env.add_filter("color", move |value: String, name: String| {
+            color(value, &name, colors)
+        });
+        env.add_template_owned(NAME, source)
+            .context("Invalid template")?;
+
+        Ok(Self { env, scope })
+    }
+
+    /// Render the template against the given value, ensuring the
+    /// output ends with a newline
+    pub fn render<W, T>(&self, out: &mut W, value: &T) -> Result<()>
+    where
+        W: Write,
+        T: Serialize,
+    {
+        // Go via serde_json so any raw JSON (e.g issue fields) is
+        // visible to the template as structured data
+        let context = Value::from_serialize(serde_json::to_value(value)?);
+        let rendered = self
+            .env
+            .get_template(NAME)?
+            .render(context)
+            .context("Unable to render template")?;
+
+        out.write_all(rendered.as_bytes())?;
+        if !rendered.ends_with('\n') {
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Scope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "issue" => Ok(Scope::Issue),
+            "response" => Ok(Scope::Response),
+            _ => Err(format!("expected one of [issue, response], got '{}'", s)),
+        }
+    }
+}
+
+fn date(value: Value, format: Option<String>, timezone: Option<Value>) -> Result<String, Error> {
+    let value = match value.as_str() {
+        Some(value) => value,
+        None => return Ok(String::new()),
+    };
+    let format = format.as_deref().unwrap_or(DEFAULT_DATE_FORMAT);
+
+    if let Ok(datetime) = time::parse_datetime(value) {
+        return Ok(match timezone.map(timezone_of).transpose()? {
+            Some(tz) => datetime.with_timezone(&tz).format(format).to_string(),
+            None => datetime.format(format).to_string(),
+        });
+    }
+
+    time::parse_date(value)
+        .map(|date| date.format(format).to_string())
+        .map_err(|e| invalid(format!("'{}' is not a Jira date: {}", value, e)))
+}
+
+/// Find the timezone of a zone name or a user object
+fn timezone_of(value: Value) -> Result<Tz, Error> {
+    let zone = match value.as_str() {
+        Some(zone) => zone.to_string(),
+        None => match value.get_attr("timeZone")?.as_str() {
+            Some(zone) => zone.to_string(),
+            None => return Err(invalid("expected a timezone name or a user")),
+        },
+    };
+
+    zone.parse()
+        .map_err(|_| invalid(format!("unknown timezone '{}'", zone)))
+}
+
+fn user(value: Value) -> Result<String, Error> {
+    if value.is_undefined() || value.is_none() {
+        return Ok(UNASSIGNED.to_string());
+    }
+
+    if let Some(name) = value.as_str() {
+        return Ok(name.to_string());
+    }
+
+    for attr in ["displayName", "name", "emailAddress"].iter() {
+        if let Some(name) = value.get_attr(attr)?.as_str() {
+            return Ok(name.to_string());
+        }
+    }
+
+    Ok(UNASSIGNED.to_string())
+}
+
+fn truncate(value: String, length: Option<usize>, end: Option<String>) -> String {
+    let length = length.unwrap_or(DEFAULT_TRUNCATE);
+    let end = end.as_deref().unwrap_or("…");
+
+    if value.chars().count() <= length {
+        return value;
+    }
+
+    let keep = length.saturating_sub(end.chars().count());
+    value.chars().take(keep).chain(end.chars()).collect()
+}
+
+fn color(value: String, name: &str, enabled: bool) -> Result<String, Error> {
+    let code = match name {
+        "bold" => 1,
+        "dim" => 2,
+        "underline" => 4,
+        "black" => 30,
+        "red" => 31,
+        "green" => 32,
+        "yellow" => 33,
+        "blue" => 34,
+        "magenta" => 35,
+        "cyan" => 36,
+        "white" => 37,
+        _ => return Err(invalid(format!("unknown color '{}'", name))),
+    };
+
+    Ok(match enabled {
+        true => format!("\x1b[{}m{}\x1b[0m", code, value),
+        false => value,
+    })
+}
+
+fn colors_enabled() -> bool {
+    env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal()
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::new(ErrorKind::InvalidOperation, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    fn render(source: &str) -> Result<String> {
+        let context = json!({
+            "key": "FOO-1",
+            "created": "2021-03-04T10:11:12.000+0000",
+            "due": "2021-03-05",
+            "summary": "Fix the login page, which fails after the session expires",
+            "assignee": { "name": "ann", "displayName": "Ann Smith", "timeZone": "Asia/Tokyo" },
+            "reporter": { "emailAddress": "bob@example.com" },
+            "watcher": null,
+        });
+
+        let mut out = Vec::new();
+        Template::new(source.to_string(), Scope::Issue)?.render(&mut out, &context)?;
+
+        Ok(String::from_utf8(out)?)
+    }
+
+    #[test]
+    fn date_filter() {
+        assert_eq!(render("{{ created|date }}").unwrap(), "2021-03-04 10:11\n");
+        assert_eq!(
+            render("{{ created|date('%H:%M', 'America/New_York') }}").unwrap(),
+            "05:11\n"
+        );
+        assert_eq!(
+            render("{{ created|date('%H:%M %Z', assignee) }}").unwrap(),
+            "19:11 JST\n"
+        );
+        assert_eq!(
+            render("{{ due|date('%d/%m/%Y') }}").unwrap(),
+            "05/03/2021\n"
+        );
+        assert_eq!(render("[{{ missing|date }}]").unwrap(), "[]\n");
+
+        assert!(render("{{ key|date }}").is_err());
+        assert!(render("{{ created|date('%H', 'Mars/Olympus') }}").is_err());
+        assert!(render("{{ created|date('%H', reporter) }}").is_err());
+    }
+
+    #[test]
+    fn user_filter() {
+        assert_eq!(
+            render(
+                "{{ assignee|user }}, {{ reporter|user }}, {{ watcher|user }}, {{ missing|user }}"
+            )
+            .unwrap(),
+            "Ann Smith, bob@example.com, Unassigned, Unassigned\n"
+        );
+    }
+
+    #[test]
+    fn truncate_filter() {
+        assert_eq!(
+            render("{{ summary|truncate(20) }}").unwrap(),
+            "Fix the login page,…\n"
+        );
+        assert_eq!(
+            render("{{ summary|truncate(12, '...') }}").unwrap(),
+            "Fix the l...\n"
+        );
+        assert_eq!(render("{{ key|truncate }}").unwrap(), "FOO-1\n");
+    }
+
+    #[test]
+    fn color_filter() {
+        assert_eq!(
+            color("FOO-1".into(), "red", true).unwrap(),
+            "\x1b[31mFOO-1\x1b[0m"
+        );
+        assert_eq!(color("FOO-1".into(), "bold", false).unwrap(), "FOO-1");
+        assert!(color("FOO-1".into(), "mauve", true).is_err());
+
+        // Tests never run with stdout as a terminal
+        assert_eq!(render("{{ key|color('green') }}").unwrap(), "FOO-1\n");
+        assert!(render("{{ key|color('mauve') }}").is_err());
+    }
+
+    #[test]
+    fn invalid_templates() {
+        assert!(Template::new("{{ key".into(), Scope::Issue).is_err());
+        assert_eq!(render("{{ key }}\n").unwrap(), "FOO-1\n");
+    }
+}
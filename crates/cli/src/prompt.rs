@@ -0,0 +1,256 @@
+use {
+    crate::editor,
+    anyhow::{bail, Result},
+    jira_rs::issue::models::IssueFieldsMeta,
+    serde_json::{self as json, Map, Value},
+    std::io::stdin,
+};
+
+/// Prompt for a line of input on stderr, returning it trimmed
+pub fn line(message: &str) -> Result<String> {
+    eprint!("{}", message);
+
+    let mut line = String::new();
+    if stdin().read_line(&mut line)? == 0 {
+        bail!("Unexpected end of input")
+    }
+
+    Ok(line.trim().to_string())
+}
+
+/// Ask a yes / no question, defaulting to no
+pub fn confirm(message: &str) -> Result<bool> {
+    let answer = line(&format!("{} [y/N] ", message))?;
+
+    Ok(matches!(answer.to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Prompt for one of the given choices, by its number or label
+pub fn choose<'c>(message: &str, choices: &[&'c str]) -> Result<&'c str> {
+    eprintln!("{}", message);
+    for (i, choice) in choices.iter().enumerate() {
+        eprintln!("  {}) {}", i + 1, choice);
+    }
+
+    loop {
+        let answer = line("> ")?;
+
+        match resolve(&answer, choices) {
+            Some(i) => return Ok(choices[i]),
+            None => eprintln!("'{}' is not one of the choices", answer),
+        }
+    }
+}
+
+/// Prompt for a value for each of the given fields, returning the field
+/// id -> payload value of every field which was given a value
+pub fn fields<'m, 'a: 'm, I>(fields: I) -> Result<Map<String, Value>>
+where
+    I: IntoIterator<Item = &'m IssueFieldsMeta<'a>>,
+{
+    let mut values = Map::new();
+
+    for meta in fields {
+        if let Some(value) = field(meta)? {
+            values.insert(meta.field_id.to_string(), value);
+        }
+    }
+
+    Ok(values)
+}
+
+/// Prompt for a single field, offering its allowed values as choices and
+/// falling back to its default when nothing is entered
+fn field(meta: &IssueFieldsMeta<'_>) -> Result<Option<Value>> {
+    let (field_type, items) = match meta.schema {
+        Some(ref schema) => (schema.field_type.as_ref(), schema.items.as_deref()),
+        None => ("any", None),
+    };
+    let many = field_type == "array";
+
+    let choices: Vec<Value> = meta
+        .possible_values
+        .iter()
+        .flatten()
+        .filter_map(|raw| json::from_str(raw.get()).ok())
+        .collect();
+    let labels: Vec<String> = choices.iter().map(label).collect();
+    let default: Option<Value> = meta.default.and_then(|raw| json::from_str(raw.get()).ok());
+
+    eprintln!(
+        "{}{}",
+        meta.name,
+        if meta.required { " (required)" } else { "" }
+    );
+    for (i, label) in labels.iter().enumerate() {
+        eprintln!("  {}) {}", i + 1, label);
+    }
+
+    let mut message = String::from(match many {
+        true => "(comma separated) ",
+        false => "",
+    });
+    if let Some(ref default) = default {
+        message.push_str(&format!("[{}] ", label(default)));
+    }
+    message.push_str("> ");
+
+    loop {
+        let answer = line(&message)?;
+
+        if answer.is_empty() {
+            match default {
+                Some(default) => return Ok(Some(default)),
+                None if !meta.required => return Ok(None),
+                None => {
+                    eprintln!("{} requires a value", meta.name);
+                    continue;
+                }
+            }
+        }
+
+        let element_type = match many {
+            true => items.unwrap_or("string"),
+            false => field_type,
+        };
+
+        match parse(&answer, element_type, many, &choices) {
+            Ok(value) => return Ok(Some(value)),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
+
+/// Parse an answer into the payload value of a field, resolving it
+/// against the field's allowed values if it has any
+fn parse(answer: &str, element_type: &str, many: bool, choices: &[Value]) -> Result<Value> {
+    let labels: Vec<String> = choices.iter().map(label).collect();
+    let labels: Vec<&str> = labels.iter().map(String::as_str).collect();
+
+    let answers: Vec<&str> = match many {
+        true => answer.split(',').map(str::trim).collect(),
+        false => vec![answer],
+    };
+
+    let mut values = answers
+        .iter()
+        .map(|answer| match choices.is_empty() {
+            true => typed(element_type, answer),
+            false => match resolve(answer, &labels) {
+                Some(i) => Ok(reference(&choices[i])),
+                None => bail!("'{}' is not one of the choices", answer),
+            },
+        })
+        .collect::<Result<Vec<Value>>>()?;
+
+    match many {
+        true => Ok(Value::Array(values)),
+        false => Ok(values.remove(0)),
+    }
+}
+
+/// Find a choice by its (1 based) number, or case insensitively by label
+fn resolve(answer: &str, choices: &[&str]) -> Option<usize> {
+    match answer.parse::<usize>() {
+        Ok(n) if (1..=choices.len()).contains(&n) => Some(n - 1),
+        _ => choices.iter().position(|c| c.eq_ignore_ascii_case(answer)),
+    }
+}
+
+/// Convert free text into the payload value of the given field type
+fn typed(field_type: &str, answer: &str) -> Result<Value> {
+    let value = match field_type {
+        "number" => match json::from_str::<Value>(answer) {
+            Ok(number) if number.is_number() => number,
+            _ => bail!("'{}' is not a number", answer),
+        },
+        _ => Value::String(answer.to_string()),
+    };
+
+    Ok(editor::expand(field_type, &value))
+}
+
+/// An allowed value is referenced by its id when it has one
+fn reference(choice: &Value) -> Value {
+    match choice.get("id") {
+        Some(id) => json::json!({ "id": id }),
+        None => choice.clone(),
+    }
+}
+
+fn label(value: &Value) -> String {
+    match editor::simplify(value) {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[test]
+    fn resolve_choices() {
+        let choices = ["Bug", "Task"];
+
+        assert_eq!(resolve("1", &choices), Some(0));
+        assert_eq!(resolve("2", &choices), Some(1));
+        assert_eq!(resolve("task", &choices), Some(1));
+        assert_eq!(resolve("0", &choices), None);
+        assert_eq!(resolve("3", &choices), None);
+        assert_eq!(resolve("Story", &choices), None);
+    }
+
+    #[test]
+    fn free_text() {
+        assert_eq!(
+            parse("Hello", "string", false, &[]).unwrap(),
+            json!("Hello")
+        );
+        assert_eq!(parse("3.5", "number", false, &[]).unwrap(), json!(3.5));
+        assert_eq!(
+            parse("High", "priority", false, &[]).unwrap(),
+            json!({ "name": "High" })
+        );
+        assert_eq!(
+            parse("Yes", "option", false, &[]).unwrap(),
+            json!({ "value": "Yes" })
+        );
+        assert_eq!(
+            parse("a, b,c", "string", true, &[]).unwrap(),
+            json!(["a", "b", "c"])
+        );
+
+        let error = parse("three", "number", false, &[]).unwrap_err();
+        assert_eq!(error.to_string(), "'three' is not a number");
+    }
+
+    #[test]
+    fn allowed_values() {
+        let choices = vec![
+            json!({ "id": "1", "name": "High" }),
+            json!({ "id": "2", "name": "Low" }),
+            json!({ "value": "Red" }),
+        ];
+
+        assert_eq!(
+            parse("low", "priority", false, &choices).unwrap(),
+            json!({ "id": "2" })
+        );
+        assert_eq!(
+            parse("1", "priority", false, &choices).unwrap(),
+            json!({ "id": "1" })
+        );
+        assert_eq!(
+            parse("red", "option", false, &choices).unwrap(),
+            json!({ "value": "Red" })
+        );
+        assert_eq!(
+            parse("High, 3", "option", true, &choices).unwrap(),
+            json!([{ "id": "1" }, { "value": "Red" }])
+        );
+
+        let error = parse("High, Medium", "priority", true, &choices).unwrap_err();
+        assert_eq!(error.to_string(), "'Medium' is not one of the choices");
+    }
+}
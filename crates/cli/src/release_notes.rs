@@ -0,0 +1,59 @@
+use {
+    crate::{
+        cli::ReleaseNotes as ReleaseNotesCmd,
+        config::Profile,
+        notes::ReleaseNotes,
+        output::Output,
+        versions::{find_version, project_key},
+    },
+    anyhow::{anyhow, Result},
+    jira_rs::issue,
+    std::io::stdout,
+};
+
+pub async fn run(cmd: &ReleaseNotesCmd, settings: &Profile, output: &Output) -> Result<()> {
+    let client = settings.client()?;
+    let project = project_key(&cmd.project, settings)?;
+
+    let versions = client.versions().list(project).await?;
+    let version = find_version(versions.data(), &cmd.version)
+        .ok_or_else(|| anyhow!("No version '{}' in {}", cmd.version, project))?;
+
+    let mut jql = format!(r#"project = "{}" AND fixVersion = {}"#, project, version.id);
+    if let Some(ref extra) = cmd.jql {
+        jql = format!("{} AND ({})", jql, extra);
+    }
+    jql.push_str(" ORDER BY key ASC");
+
+    // Release notes need every issue, so page through the whole search
+    let mut pages = Vec::new();
+    let mut start = 0;
+    loop {
+        let options = issue::options::Search::new().with(|this| {
+            this.jql(&jql)
+                .start_at(start)
+                .max_results(settings.page_size)
+        });
+        let page = client.issues().search(Some(&options)).await?;
+
+        let data = page.data();
+        start += data.issues.len() as u32;
+        let done = data.issues.is_empty() || start as u64 >= data.total;
+        pages.push(page);
+        if done {
+            break;
+        }
+    }
+
+    let issues: Vec<_> = pages
+        .iter()
+        .flat_map(|page| page.data().issues.iter().cloned())
+        .collect();
+    let host = settings.host.as_deref().unwrap_or_default();
+    let notes = ReleaseNotes::new(host, project, version, &issues, cmd.group_by)?;
+
+    match output.has_template() {
+        true => output.render(&notes),
+        false => notes.render(&mut stdout().lock(), cmd.format),
+    }
+}
@@ -0,0 +1,47 @@
+use {
+    crate::{cli::Sync as SyncCmd, config::Profile, mirror, output::Output},
+    anyhow::{bail, Context, Result},
+    jira_sync::SyncOptions,
+    serde_json as json,
+};
+
+pub async fn run(cmd: &SyncCmd, settings: &Profile, output: &Output) -> Result<()> {
+    let mut store = mirror::open(settings, cmd.db.as_deref())?;
+
+    if cmd.list {
+        return output.render(&store.queries()?);
+    }
+
+    if cmd.forget {
+        let jql = cmd.jql.as_deref().expect("--forget requires a query");
+        let removed = store.forget(jql)?;
+
+        return output.render(&json::json!({ "jql": jql, "removed": removed }));
+    }
+
+    let queries = match cmd.jql {
+        Some(ref jql) => vec![jql.clone()],
+        None => store.queries()?.into_iter().map(|q| q.jql).collect(),
+    };
+    if queries.is_empty() {
+        bail!("Nothing to sync, give a JQL query to start mirroring it")
+    }
+
+    let client = settings.client()?;
+    let options = SyncOptions::new().with(|this| {
+        this.page_size(settings.page_size)
+            .fields(cmd.fields.iter().flatten())
+            .full(cmd.full)
+            .reconcile(cmd.reconcile)
+    });
+
+    let mut reports = Vec::new();
+    for jql in queries.iter() {
+        let report = jira_sync::sync(&client, &mut store, jql, &options)
+            .await
+            .with_context(|| format!("Unable to sync '{}'", jql))?;
+        reports.push(report);
+    }
+
+    output.render(&reports)
+}
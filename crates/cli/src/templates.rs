@@ -0,0 +1,264 @@
+use {
+    crate::config::Config,
+    anyhow::{anyhow, bail, Context as _, Result},
+    jira_rs::issue::payload::IssueUpdate,
+    minijinja::{Environment, UndefinedBehavior},
+    serde::Deserialize,
+    serde_json::{self as json, Map, Value},
+    std::{
+        collections::BTreeMap,
+        env, fs,
+        io::ErrorKind,
+        path::{Path, PathBuf},
+    },
+};
+
+const TEMPLATES_DIR: &str = "templates";
+const EXTENSION: &str = "toml";
+const PARENT_LINK: &str = "parent";
+const PROJECT: &str = "project";
+
+/// A reusable description of an issue, and optionally the children
+/// created under it, stored as `templates/<name>.toml` next to the
+/// config file.
+///
+/// Every string in `fields` and `children` is rendered with minijinja,
+/// with the template's `vars` (overridable via --var), `env.<NAME>` for
+/// environment variables, and for children `parent.key` / `parent.id`
+/// of the issue created from `fields`.
+///
+/// ```toml
+/// link = "parent"
+///
+/// [vars]
+/// version = "1.0"
+///
+/// [fields]
+/// project = { key = "REL" }
+/// issuetype = { name = "Epic" }
+/// summary = "Release {{version}}"
+///
+/// [[children]]
+/// issuetype = { name = "Sub-task" }
+/// summary = "Tag {{version}}, requested by {{env.USER}}"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct IssueTemplate {
+    /// Variables and their default values
+    #[serde(default)]
+    vars: BTreeMap<String, String>,
+    /// The field each child is linked to its parent through, 'parent' for
+    /// subtasks (the default), or e.g the Epic Link custom field
+    #[serde(default = "parent_link")]
+    link: String,
+    /// The fields of the issue to create
+    fields: Map<String, Value>,
+    /// The fields of each child issue, created after and linked to the
+    /// issue above
+    #[serde(default)]
+    children: Vec<Map<String, Value>>,
+}
+
+/// The values available to a template's strings
+#[derive(Debug, Clone)]
+pub struct Context {
+    values: Map<String, Value>,
+}
+
+impl IssueTemplate {
+    /// Load the named template from the templates directory
+    pub fn load(name: &str) -> Result<Self> {
+        let dir = Self::dir()?;
+        let path = Self::path(&dir, name)?;
+
+        let data = match fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                let available = Self::list()?;
+                bail!(
+                    "No template '{}' in {}, available: [{}]",
+                    name,
+                    dir.display(),
+                    available.join(", ")
+                )
+            }
+            Err(e) => return Err(e).with_context(|| format!("Unable to read {}", path.display())),
+        };
+
+        toml::from_str(&data).with_context(|| format!("Invalid template: {}", path.display()))
+    }
+
+    /// The file of the named template. Names are file stems, and may not
+    /// leave the templates directory
+    fn path(dir: &Path, name: &str) -> Result<PathBuf> {
+        if name.is_empty() || name.contains(std::path::is_separator) || name.contains("..") {
+            bail!("Invalid template name '{}'", name)
+        }
+
+        Ok(dir.join(format!("{}.{}", name, EXTENSION)))
+    }
+
+    /// The names of every template in the templates directory
+    pub fn list() -> Result<Vec<String>> {
+        let entries = match fs::read_dir(Self::dir()?) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut names = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == EXTENSION) {
+                if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                    names.push(name.to_string())
+                }
+            }
+        }
+        names.sort();
+
+        Ok(names)
+    }
+
+    /// The templates directory, next to the config file
+    pub fn dir() -> Result<PathBuf> {
+        Ok(Config::path()?.with_file_name(TEMPLATES_DIR))
+    }
+
+    /// Build the context the template is rendered with, `vars` override
+    /// the template's defaults
+    pub fn context(&self, vars: &[(String, String)]) -> Context {
+        let mut values: Map<String, Value> = self
+            .vars
+            .iter()
+            .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+            .collect();
+
+        for (name, value) in vars {
+            values.insert(name.clone(), Value::String(value.clone()));
+        }
+
+        values.insert(
+            "env".to_string(),
+            Value::Object(env::vars().map(|(k, v)| (k, Value::String(v))).collect()),
+        );
+
+        Context { values }
+    }
+
+    /// Render the payload of the template's issue
+    pub fn issue(&self, context: &Context) -> Result<IssueUpdate> {
+        payload(&self.fields, context)
+    }
+
+    /// Render the payloads of the template's children, linked to the parent
+    /// in the context. Children without a project use the parent's
+    pub fn children(&self, context: &Context, parent: &IssueUpdate) -> Result<Vec<IssueUpdate>> {
+        let key = context
+            .values
+            .get(PARENT_LINK)
+            .and_then(|p| p.get("key"))
+            .cloned()
+            .ok_or_else(|| anyhow!("Children can only be rendered once the parent exists"))?;
+
+        self.children
+            .iter()
+            .map(|fields| {
+                let mut child = payload(fields, context)?;
+
+                if !child.fields().contains_key(PROJECT) {
+                    if let Some(project) = parent.fields().get(PROJECT) {
+                        child.set(PROJECT, project.clone());
+                    }
+                }
+
+                if !child.fields().contains_key(&self.link) {
+                    match self.link.as_str() {
+                        PARENT_LINK => child.set(PARENT_LINK, json::json!({ "key": key })),
+                        // Custom link fields, e.g Epic Link, take the bare key
+                        link => child.set(link, key.clone()),
+                    };
+                }
+
+                Ok(child)
+            })
+            .collect()
+    }
+}
+
+impl Context {
+    /// Make the given issue available to children as `parent`
+    pub fn parent(&mut self, key: &str, id: u64) -> &mut Self {
+        self.values.insert(
+            PARENT_LINK.to_string(),
+            json::json!({ "key": key, "id": id.to_string() }),
+        );
+        self
+    }
+}
+
+/// Parse a `--var` argument
+pub fn parse_var(arg: &str) -> Result<(String, String)> {
+    match arg.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.to_string()))
+        }
+        _ => bail!("expected <NAME>=<VALUE>, got '{}'", arg),
+    }
+}
+
+fn payload(fields: &Map<String, Value>, context: &Context) -> Result<IssueUpdate> {
+    let mut env = Environment::new();
+    // Catch typos in variable names, rather than sending empty fields
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+
+    let mut update = IssueUpdate::new();
+    for (id, value) in fields {
+        let value = render(&env, value, &context.values)
+            .with_context(|| format!("Unable to render field '{}'", id))?;
+        update.set(id.as_str(), value);
+    }
+
+    Ok(update)
+}
+
+fn render(env: &Environment<'_>, value: &Value, context: &Map<String, Value>) -> Result<Value> {
+    Ok(match value {
+        Value::String(s) => Value::String(env.render_str(s, context)?),
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|v| render(env, v, context))
+                .collect::<Result<_>>()?,
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| Ok((k.clone(), render(env, v, context)?)))
+                .collect::<Result<_>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+fn parent_link() -> String {
+    PARENT_LINK.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_paths() {
+        let dir = Path::new("/config/templates");
+
+        assert_eq!(
+            IssueTemplate::path(dir, "release-1.4").unwrap(),
+            Path::new("/config/templates/release-1.4.toml")
+        );
+        for name in ["", "../config", "a/b", "..", "a..b"].iter() {
+            assert!(IssueTemplate::path(dir, name).is_err(), "{}", name);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use {
+    crate::{cli::Versions as VersionsCmd, config::Profile, output::Output},
+    anyhow::{anyhow, bail, Context, Result},
+    jira_rs::{
+        time,
+        version::{models::Version, payload::VersionDetails},
+    },
+};
+
+pub async fn run(cmd: &VersionsCmd, settings: &Profile, output: &Output) -> Result<()> {
+    let client = settings.client()?;
+
+    match cmd {
+        VersionsCmd::List {
+            ref project,
+            unreleased,
+        } => {
+            let project = project_key(project, settings)?;
+            let versions = client.versions().list(project).await?;
+
+            match unreleased {
+                true => {
+                    let unreleased: Vec<_> =
+                        versions.data().iter().filter(|v| !v.released).collect();
+                    output.render(&unreleased)?
+                }
+                false => output.render(&versions)?,
+            }
+        }
+        VersionsCmd::Create {
+            ref project,
+            ref name,
+            ref description,
+            ref start_date,
+            ref release_date,
+        } => {
+            let project = project_key(project, settings)?;
+
+            let mut details = VersionDetails::new().with(|this| this.name(name).project(project));
+            if let Some(ref description) = description {
+                details.description(description);
+            }
+            if let Some(ref date) = start_date {
+                details.start_date(check_date(date)?);
+            }
+            if let Some(ref date) = release_date {
+                details.release_date(check_date(date)?);
+            }
+
+            let created = client.versions().create(&details).await?;
+
+            output.render(&created)?;
+        }
+        VersionsCmd::Release {
+            ref project,
+            ref version,
+            ref date,
+            allow_unresolved,
+        } => {
+            let project = project_key(project, settings)?;
+            let date = match date {
+                Some(date) => check_date(date)?,
+                None => chrono::Local::now()
+                    .date_naive()
+                    .format(time::DATE_FORMAT)
+                    .to_string(),
+            };
+
+            let versions = client.versions().list(project).await?;
+            let found = find_version(versions.data(), version)
+                .ok_or_else(|| anyhow!("No version '{}' in {}", version, project))?;
+
+            if !allow_unresolved {
+                let count = client.versions().unresolved_count(found.id).await?;
+                if count.unresolved > 0 {
+                    bail!(
+                        "Version '{}' has {} unresolved issue(s), use --allow-unresolved to \
+                         release it anyway",
+                        found.name,
+                        count.unresolved
+                    )
+                }
+            }
+
+            let released = client.versions().release(found.id, Some(date)).await?;
+
+            output.render(&released)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The project given on the command line, or the profile's
+pub fn project_key<'a>(project: &'a Option<String>, settings: &'a Profile) -> Result<&'a str> {
+    project
+        .as_deref()
+        .or(settings.project.as_deref())
+        .ok_or_else(|| anyhow!("No project given, use --project or set the profile's project"))
+}
+
+/// Find a version by name, or by id if no version has the name
+pub fn find_version<'a, 'v>(versions: &'a [Version<'v>], version: &str) -> Option<&'a Version<'v>> {
+    versions.iter().find(|v| v.name == version).or_else(|| {
+        let id: u64 = version.parse().ok()?;
+        versions.iter().find(|v| v.id == id)
+    })
+}
+
+fn check_date(date: &str) -> Result<String> {
+    time::parse_date(date)
+        .map(|date| date.format(time::DATE_FORMAT).to_string())
+        .with_context(|| format!("Invalid date '{}', expected YYYY-MM-DD", date))
+}
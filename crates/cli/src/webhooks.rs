@@ -0,0 +1,45 @@
+use {
+    crate::cli::Webhooks as WebhooksCmd,
+    anyhow::{Context, Result},
+    jira_rs::webhook::receiver::Receiver,
+    serde_json as json,
+};
+
+pub async fn run(cmd: &WebhooksCmd) -> Result<()> {
+    match cmd {
+        WebhooksCmd::Listen {
+            port,
+            bind,
+            ref secret,
+            max_body,
+            ref events,
+        } => {
+            let events = events.clone();
+            let mut receiver = Receiver::new().with(|this| this.body_limit(*max_body));
+            if let Some(secret) = secret {
+                receiver.secret(secret.as_str());
+            }
+            receiver.on_any(move |event| {
+                let name = event.data().webhook_event.as_ref();
+                if !events.is_empty() && !events.iter().any(|e| e == name) {
+                    return;
+                }
+
+                // Jira may pretty print its payloads, each event must be on one line
+                let line = json::from_str::<json::Value>(event.raw().get())
+                    .and_then(|value| json::to_string(&value));
+                if let Ok(line) = line {
+                    println!("{}", line)
+                }
+            });
+
+            let addr = (*bind, *port).into();
+            eprintln!("Listening for webhook events on http://{}", addr);
+
+            receiver
+                .serve(addr)
+                .await
+                .with_context(|| format!("Unable to listen on {}", addr))
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use {
+    crate::{
+        cli::Workflow as WorkflowCmd, config::Profile, graph, meta::project_meta, output::Output,
+    },
+    anyhow::{anyhow, Result},
+};
+
+pub async fn run(cmd: &WorkflowCmd, settings: &Profile, output: &Output) -> Result<()> {
+    let client = settings.client()?;
+
+    match cmd {
+        WorkflowCmd::Show {
+            ref project,
+            ref issue_type,
+            format,
+        } => {
+            let meta = project_meta(&client, project).await?;
+            let project = &meta.data().projects[0];
+            let found = project
+                .issue_types
+                .iter()
+                .map(|t| &t.issue_type)
+                .find(|t| {
+                    t.name.eq_ignore_ascii_case(issue_type) || t.id.to_string() == *issue_type
+                })
+                .ok_or_else(|| {
+                    let names: Vec<_> = project
+                        .issue_types
+                        .iter()
+                        .map(|t| t.issue_type.name.as_ref())
+                        .collect();
+                    anyhow!(
+                        "No issue type '{}' in {}, available: [{}]",
+                        issue_type,
+                        project.project.key,
+                        names.join(", ")
+                    )
+                })?;
+
+            let schemes = client
+                .workflows()
+                .scheme_for_project(project.project.id)
+                .await?;
+            let name = schemes
+                .data()
+                .values
+                .iter()
+                .find_map(|s| s.workflow_scheme.workflow_for(found.id))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "No workflow is assigned to {} in {}",
+                        found.name,
+                        project.project.key
+                    )
+                })?;
+
+            let options = jira_rs::workflow::options::Search::new().with(|this| {
+                use jira_rs::workflow::options::expands::*;
+
+                this.name(name)
+                    .expand([TRANSITIONS, TRANSITION_RULES, STATUSES])
+            });
+            let search = client.workflows().search(Some(&options)).await?;
+            let workflow = search
+                .data()
+                .values
+                .iter()
+                .find(|w| w.id.name == name)
+                .ok_or_else(|| anyhow!("Workflow '{}' is not visible to you", name))?;
+
+            match format {
+                Some(format) => print!("{}", graph::render(workflow, *format)),
+                None => output.render(workflow)?,
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,282 @@
+//! Derive macro for `jira_rs::issue::payload::JiraIssue`.
+//!
+//! See the trait's documentation for the supported `#[jira(...)]` attributes.
+
+use {
+    proc_macro::TokenStream,
+    proc_macro2::{Span, TokenStream as TokenStream2},
+    quote::quote,
+    syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, LitStr},
+};
+
+#[proc_macro_derive(JiraIssue, attributes(jira))]
+pub fn derive_jira_issue(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => return Err(unsupported(ident.span())),
+        },
+        _ => return Err(unsupported(ident.span())),
+    };
+
+    let mut readers = Vec::with_capacity(fields.len());
+    let mut writers = Vec::with_capacity(fields.len());
+
+    for field in fields {
+        let member = field.ident.as_ref().expect("named fields have idents");
+        let attrs = FieldAttrs::parse(field)?;
+
+        readers.push(attrs.reader(member));
+        if let Some(writer) = attrs.writer(member) {
+            writers.push(writer)
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::jira_rs::issue::payload::JiraIssue for #ident #ty_generics #where_clause {
+            fn from_issue_with(
+                issue: &::jira_rs::issue::models::Issue<'_>,
+                names: &::jira_rs::issue::payload::FieldNames,
+            ) -> ::std::result::Result<Self, ::jira_rs::issue::payload::SchemaError> {
+                #[allow(unused_imports)]
+                use ::jira_rs::issue::payload::__private as p;
+
+                ::std::result::Result::Ok(Self {
+                    #(#readers,)*
+                })
+            }
+
+            fn to_payload(
+                &self,
+                names: &::jira_rs::issue::payload::FieldNames,
+            ) -> ::std::result::Result<
+                ::jira_rs::issue::payload::IssueUpdate,
+                ::jira_rs::issue::payload::SchemaError,
+            > {
+                #[allow(unused_imports)]
+                use ::jira_rs::issue::payload::__private as p;
+
+                let mut payload = ::jira_rs::issue::payload::IssueUpdate::new();
+                #(#writers)*
+
+                ::std::result::Result::Ok(payload)
+            }
+        }
+    })
+}
+
+/// What a struct field maps to in a Jira issue
+enum Source {
+    /// A field in the issue's `fields` map
+    Field(FieldRef),
+    /// The issue's key
+    Key,
+    /// The issue's id
+    Id,
+    /// Nothing, use Default
+    Skip,
+}
+
+enum FieldRef {
+    Id(LitStr),
+    Name(LitStr),
+}
+
+struct FieldAttrs {
+    source: Source,
+    update: Option<Operation>,
+    read_only: bool,
+    default: bool,
+}
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Set,
+    Edit,
+    Add,
+    Remove,
+}
+
+impl FieldAttrs {
+    fn parse(field: &syn::Field) -> Result<Self, Error> {
+        let member = field.ident.as_ref().expect("named fields have idents");
+
+        let mut field_ref = None;
+        let mut key = false;
+        let mut id = false;
+        let mut skip = false;
+        let mut update = None;
+        let mut read_only = false;
+        let mut default = false;
+
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("jira")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("field") {
+                    set_once(&mut field_ref, FieldRef::Id(meta.value()?.parse()?), &meta)
+                } else if meta.path.is_ident("name") {
+                    set_once(
+                        &mut field_ref,
+                        FieldRef::Name(meta.value()?.parse()?),
+                        &meta,
+                    )
+                } else if meta.path.is_ident("update") {
+                    let op: LitStr = meta.value()?.parse()?;
+                    let op = Operation::parse(&op)?;
+                    set_once(&mut update, op, &meta)
+                } else if meta.path.is_ident("key") {
+                    key = true;
+                    Ok(())
+                } else if meta.path.is_ident("id") {
+                    id = true;
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("read_only") {
+                    read_only = true;
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    default = true;
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "unknown jira attribute, expected one of: \
+                        field, name, update, key, id, skip, read_only, default",
+                    ))
+                }
+            })?;
+        }
+
+        let source = match (field_ref, key, id, skip) {
+            (None, false, false, false) => Source::Field(FieldRef::Id(LitStr::new(
+                &member.to_string(),
+                member.span(),
+            ))),
+            (Some(field_ref), false, false, false) => Source::Field(field_ref),
+            (None, true, false, false) => Source::Key,
+            (None, false, true, false) => Source::Id,
+            (None, false, false, true) => Source::Skip,
+            _ => {
+                return Err(Error::new(
+                    field.span(),
+                    "at most one of `field`, `name`, `key`, `id` or `skip` may be used",
+                ))
+            }
+        };
+
+        if update.is_some() && !matches!(source, Source::Field(_)) {
+            return Err(Error::new(
+                field.span(),
+                "`update` may only be used on issue fields",
+            ));
+        }
+
+        Ok(Self {
+            source,
+            update,
+            read_only,
+            default,
+        })
+    }
+
+    fn reader(&self, member: &syn::Ident) -> TokenStream2 {
+        let default = match self.default {
+            true => quote! { ::std::option::Option::Some(::std::default::Default::default) },
+            false => quote! { ::std::option::Option::None },
+        };
+
+        match self.source {
+            Source::Field(ref field) => {
+                let field = field.tokens();
+                quote! { #member: p::from_field(issue, names, #field, #default)? }
+            }
+            Source::Key => quote! { #member: p::from_key(issue)? },
+            Source::Id => quote! { #member: p::from_id(issue)? },
+            Source::Skip => quote! { #member: ::std::default::Default::default() },
+        }
+    }
+
+    fn writer(&self, member: &syn::Ident) -> Option<TokenStream2> {
+        let field = match self.source {
+            Source::Field(ref field) if !self.read_only => field.tokens(),
+            _ => return None,
+        };
+
+        let op = match self.update {
+            Some(op) => {
+                let op = op.tokens();
+                quote! { ::std::option::Option::Some(#op) }
+            }
+            None => quote! { ::std::option::Option::None },
+        };
+
+        Some(quote! {
+            p::to_field(&mut payload, names, #field, #op, &self.#member)?;
+        })
+    }
+}
+
+impl FieldRef {
+    fn tokens(&self) -> TokenStream2 {
+        match self {
+            Self::Id(id) => quote! { p::FieldRef::Id(#id) },
+            Self::Name(name) => quote! { p::FieldRef::Name(#name) },
+        }
+    }
+}
+
+impl Operation {
+    fn parse(lit: &LitStr) -> Result<Self, Error> {
+        match lit.value().as_str() {
+            "set" => Ok(Self::Set),
+            "edit" => Ok(Self::Edit),
+            "add" => Ok(Self::Add),
+            "remove" => Ok(Self::Remove),
+            _ => Err(Error::new(
+                lit.span(),
+                "expected one of: \"set\", \"edit\", \"add\", \"remove\"",
+            )),
+        }
+    }
+
+    fn tokens(self) -> TokenStream2 {
+        match self {
+            Self::Set => quote! { p::Op::Set },
+            Self::Edit => quote! { p::Op::Edit },
+            Self::Add => quote! { p::Op::Add },
+            Self::Remove => quote! { p::Op::Remove },
+        }
+    }
+}
+
+fn set_once<T>(
+    slot: &mut Option<T>,
+    value: T,
+    meta: &syn::meta::ParseNestedMeta,
+) -> Result<(), Error> {
+    match slot {
+        Some(_) => Err(meta.error("duplicate jira attribute")),
+        None => {
+            *slot = Some(value);
+            Ok(())
+        }
+    }
+}
+
+fn unsupported(span: Span) -> Error {
+    Error::new(
+        span,
+        "JiraIssue can only be derived for structs with named fields",
+    )
+}
@@ -0,0 +1,427 @@
+//! An opt-in cache of raw response bodies, see `client::Jira::cache`.
+//!
+//! Only successful GET requests are cached, for as long as the TTL of their
+//! endpoint's class allows. Stale entries which carried an `ETag` or
+//! `Last-Modified` header are revalidated with a conditional request,
+//! rather than being refetched in full.
+
+use {
+    serde::{Deserialize, Serialize},
+    serde_json::{self as json, value::RawValue as RawJson},
+    std::{
+        collections::HashMap,
+        fmt, fs,
+        io::{self, Write},
+        path::{Path, PathBuf},
+        sync::{Arc, Mutex},
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+    tracing::warn,
+};
+
+const DEFAULT_METADATA_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// A cache of responses, cheap to clone and shared between clones of
+/// the client it is attached to
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    store: Arc<dyn CacheStore>,
+    policy: CachePolicy,
+}
+
+/// Somewhere cached responses are kept.
+///
+/// Caching is best effort: stores should swallow (and ideally log) their
+/// own failures rather than failing the request being made
+pub trait CacheStore: fmt::Debug + Send + Sync {
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+
+    fn put(&self, key: &str, response: &CachedResponse);
+
+    fn remove(&self, key: &str);
+}
+
+/// A cached response body, and the validators Jira sent with it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CachedResponse {
+    /// The cache key this response was stored under
+    pub key: String,
+    pub body: Box<RawJson>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    /// When the response was stored or last revalidated, in seconds
+    /// since the unix epoch
+    pub stored_at: u64,
+}
+
+/// The kinds of endpoint a TTL can be set for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndpointClass {
+    /// Create and edit metadata, which rarely changes
+    Metadata,
+    /// Single issues
+    Issue,
+    /// Issue searches
+    Search,
+    /// Users, e.g `myself`
+    User,
+    Other,
+}
+
+/// How long responses of each endpoint class stay fresh, a class
+/// without a TTL is never cached.
+///
+/// By default only metadata is cached, for an hour
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachePolicy {
+    ttls: HashMap<EndpointClass, Duration>,
+}
+
+/// Keeps responses in memory, for the lifetime of the process
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+/// Keeps responses on disk, one file per response
+#[derive(Debug, Clone)]
+pub struct DiskStore {
+    dir: PathBuf,
+}
+
+impl ResponseCache {
+    pub fn new<S>(store: S) -> Self
+    where
+        S: CacheStore + 'static,
+    {
+        Self {
+            store: Arc::new(store),
+            policy: CachePolicy::default(),
+        }
+    }
+
+    /// A cache kept in memory
+    pub fn memory() -> Self {
+        Self::new(MemoryStore::default())
+    }
+
+    /// A cache kept in the given directory
+    pub fn disk(dir: impl Into<PathBuf>) -> Self {
+        Self::new(DiskStore::new(dir))
+    }
+
+    /// Set the policy deciding what is cached, and for how long
+    pub fn policy(&mut self, policy: CachePolicy) -> &mut Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+
+    /// How long responses from the given endpoint path stay fresh, if
+    /// they are cached at all
+    pub(crate) fn ttl(&self, path: &str) -> Option<Duration> {
+        self.policy.ttl_of(EndpointClass::of(path))
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.store.get(key).filter(|cached| cached.key == key)
+    }
+
+    pub(crate) fn put(&self, response: &CachedResponse) {
+        self.store.put(&response.key, response)
+    }
+}
+
+impl CachedResponse {
+    pub fn new(key: String, body: Box<RawJson>) -> Self {
+        Self {
+            key,
+            body,
+            etag: None,
+            last_modified: None,
+            stored_at: now(),
+        }
+    }
+
+    /// Is this response still fresh, given the TTL of its endpoint?
+    pub fn is_fresh(&self, ttl: Duration) -> bool {
+        now().saturating_sub(self.stored_at) < ttl.as_secs()
+    }
+
+    /// Can this response be revalidated with a conditional request?
+    pub fn is_validatable(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+
+    /// Mark the response as just revalidated
+    pub fn touch(&mut self) {
+        self.stored_at = now();
+    }
+}
+
+impl EndpointClass {
+    /// Classify an endpoint by its URL path
+    pub fn of(path: &str) -> Self {
+        let path = path.trim_end_matches('/');
+        let last = path.rsplit('/').next().unwrap_or_default();
+
+        match last {
+            "createmeta" | "editmeta" | "field" => EndpointClass::Metadata,
+            "search" => EndpointClass::Search,
+            "myself" | "user" => EndpointClass::User,
+            _ if path.contains("/issue/") => EndpointClass::Issue,
+            _ => EndpointClass::Other,
+        }
+    }
+}
+
+impl CachePolicy {
+    /// A policy which caches nothing
+    pub fn none() -> Self {
+        Self {
+            ttls: HashMap::new(),
+        }
+    }
+
+    /// Set how long responses of the given class stay fresh, `None`
+    /// disables caching of the class
+    pub fn ttl(&mut self, class: EndpointClass, ttl: impl Into<Option<Duration>>) -> &mut Self {
+        match ttl.into() {
+            Some(ttl) => self.ttls.insert(class, ttl),
+            None => self.ttls.remove(&class),
+        };
+        self
+    }
+
+    pub fn ttl_of(&self, class: EndpointClass) -> Option<Duration> {
+        self.ttls.get(&class).copied()
+    }
+
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self::none().with(|this| this.ttl(EndpointClass::Metadata, DEFAULT_METADATA_TTL))
+    }
+}
+
+impl CacheStore for MemoryStore {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.entries.lock().ok()?.get(key).cloned()
+    }
+
+    fn put(&self, key: &str, response: &CachedResponse) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(key.to_string(), response.clone());
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(key);
+        }
+    }
+}
+
+impl DiskStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:016x}.json", fnv1a(key.as_bytes())))
+    }
+}
+
+impl CacheStore for DiskStore {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let data = fs::read(self.path(key)).ok()?;
+
+        json::from_slice(&data)
+            .map_err(|e| warn!(error = %e, "ignoring corrupt cache entry"))
+            .ok()
+    }
+
+    fn put(&self, key: &str, response: &CachedResponse) {
+        // Responses hold whatever the cached identity may see
+        let write = create_private_dir(&self.dir)
+            .and_then(|_| Ok(json::to_vec(response)?))
+            .and_then(|data| write_private(&self.path(key), &data));
+
+        if let Err(e) = write {
+            warn!(error = %e, dir = %self.dir.display(), "unable to write cache entry")
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = fs::remove_file(self.path(key));
+    }
+}
+
+/// Create a directory (and its parents) accessible only by the current user
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+    builder.create(dir)
+}
+
+/// Write a file readable only by the current user, replacing its contents
+fn write_private(path: &Path, data: &[u8]) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(path)?;
+
+    // The mode above only applies to newly created files
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+
+    file.write_all(data)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// A stable hash for naming cache files, unlike std's hasher which may
+/// change between releases
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoint_classes() {
+        let cases = [
+            ("/rest/api/2/issue/createmeta", EndpointClass::Metadata),
+            ("/rest/api/2/issue/FOO-1/editmeta", EndpointClass::Metadata),
+            ("/rest/api/2/search", EndpointClass::Search),
+            ("/rest/api/2/myself", EndpointClass::User),
+            ("/rest/api/2/issue/FOO-1", EndpointClass::Issue),
+            ("/rest/api/2/serverInfo", EndpointClass::Other),
+        ];
+
+        for (path, class) in cases.iter() {
+            assert_eq!(EndpointClass::of(path), *class, "{}", path)
+        }
+    }
+
+    #[test]
+    fn default_policy_caches_metadata() {
+        let policy = CachePolicy::default();
+
+        assert_eq!(
+            policy.ttl_of(EndpointClass::Metadata),
+            Some(DEFAULT_METADATA_TTL)
+        );
+        assert_eq!(policy.ttl_of(EndpointClass::Issue), None);
+    }
+
+    #[test]
+    fn memory_store_round_trip() {
+        let cache = ResponseCache::memory();
+        let body = RawJson::from_string("{\"a\":1}".into()).unwrap();
+
+        cache.put(&CachedResponse::new("GET /a".into(), body));
+
+        assert_eq!(cache.get("GET /a").unwrap().body.get(), "{\"a\":1}");
+        assert!(cache.get("GET /b").is_none());
+    }
+
+    #[test]
+    fn disk_store_round_trip() {
+        let dir = std::env::temp_dir().join(format!("jira-rs-cache-{}", std::process::id()));
+        let store = DiskStore::new(&dir);
+        let body = RawJson::from_string("[1,2]".into()).unwrap();
+        let mut response = CachedResponse::new("GET /a".into(), body);
+        response.etag = Some("\"v1\"".into());
+
+        store.put("GET /a", &response);
+        let cached = store.get("GET /a").unwrap();
+        store.remove("GET /a");
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(cached.body.get(), "[1,2]");
+        assert_eq!(cached.etag.as_deref(), Some("\"v1\""));
+        assert!(store.get("GET /a").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn disk_store_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        let root = std::env::temp_dir().join(format!("jira-rs-private-{}", std::process::id()));
+        let dir = root.join("cache");
+        let store = DiskStore::new(&dir);
+        let body = RawJson::from_string("{}".into()).unwrap();
+
+        store.put(
+            "GET /a",
+            &CachedResponse::new("GET /a".into(), body.clone()),
+        );
+        let created = (mode(&root), mode(&dir), mode(&store.path("GET /a")));
+
+        // Entries written before the cache was private are tightened
+        fs::set_permissions(store.path("GET /a"), fs::Permissions::from_mode(0o644)).unwrap();
+        store.put("GET /a", &CachedResponse::new("GET /a".into(), body));
+        let rewritten = mode(&store.path("GET /a"));
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(created, (0o700, 0o700, 0o600));
+        assert_eq!(rewritten, 0o600);
+    }
+
+    #[test]
+    fn freshness() {
+        let body = RawJson::from_string("{}".into()).unwrap();
+        let mut response = CachedResponse::new("k".into(), body);
+
+        assert!(response.is_fresh(Duration::from_secs(60)));
+
+        response.stored_at -= 120;
+        assert!(!response.is_fresh(Duration::from_secs(60)));
+
+        response.touch();
+        assert!(response.is_fresh(Duration::from_secs(60)));
+    }
+}
@@ -1,20 +1,58 @@
+pub use crate::error::{InitError, JiraError};
+
 use {
     crate::{
-        error::{ApiError, ClientFault, InitError, JiraError},
+        cache::{CachedResponse, ResponseCache},
+        component::Components,
+        error::{ApiError, ClientFault},
+        filter::Filters,
         issue::Issues,
+        meta::Meta,
+        screen::Screens,
+        user::Users,
+        version::Versions,
+        webhook::Webhooks,
+        workflow::Workflows,
+    },
+    reqwest::{
+        header::{self, HeaderMap, HeaderValue},
+        Client, Method, Request, RequestBuilder, StatusCode,
     },
-    reqwest::{header, Client, Method, RequestBuilder},
     serde::de::DeserializeOwned,
-    serde_json as json,
-    std::sync::Arc,
+    serde_json::{self as json, value::RawValue as RawJson},
+    std::{
+        fmt,
+        sync::Arc,
+        time::{Duration, Instant},
+    },
+    tracing::{debug, field, info, info_span, trace, warn, Instrument, Span},
     url::{Position, Url},
 };
 
+/// How many times a failed request is retried, by default
+const DEFAULT_RETRIES: u32 = 2;
+
+/// The delay before the first retry, doubled for each one after
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// The longest a `Retry-After` header may delay a retry
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Headers whose values are never logged
+const SENSITIVE_HEADERS: [header::HeaderName; 4] = [
+    header::AUTHORIZATION,
+    header::PROXY_AUTHORIZATION,
+    header::COOKIE,
+    header::SET_COOKIE,
+];
+
 #[derive(Debug, Clone)]
 pub struct Jira {
     agent: Client,
     auth: Authentication,
     remote: Arc<Url>,
+    cache: Option<ResponseCache>,
+    retries: u32,
 }
 
 impl Jira {
@@ -38,11 +76,75 @@ impl Jira {
             agent: client,
             auth: auth.into(),
             remote: remote.into(),
+            cache: None,
+            retries: DEFAULT_RETRIES,
         })
     }
 
+    /// Cache responses according to the given cache's policy, or stop
+    /// caching if given `None`.
+    ///
+    /// Cached responses are keyed on the authenticated user, so clients
+    /// with different credentials can safely share a cache
+    pub fn cache(&mut self, cache: impl Into<Option<ResponseCache>>) -> &mut Self {
+        self.cache = cache.into();
+        self
+    }
+
+    /// Retry requests which failed to connect, were rate limited (429) or
+    /// hit an unavailable server (502, 503, 504) up to the given number of
+    /// times, backing off between attempts.
+    ///
+    /// Requests which Jira may have acted on (e.g a POST which timed out)
+    /// are only retried when they are idempotent
+    pub fn retries(&mut self, retries: u32) -> &mut Self {
+        self.retries = retries;
+        self
+    }
+
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+
     pub fn issues(&self) -> Issues {
-        Issues::new(&self)
+        Issues::new(self)
+    }
+
+    pub fn users(&self) -> Users {
+        Users::new(self)
+    }
+
+    pub fn filters(&self) -> Filters {
+        Filters::new(self)
+    }
+
+    pub fn versions(&self) -> Versions {
+        Versions::new(self)
+    }
+
+    pub fn components(&self) -> Components {
+        Components::new(self)
+    }
+
+    pub fn meta(&self) -> Meta {
+        Meta::new(self)
+    }
+
+    pub fn webhooks(&self) -> Webhooks {
+        Webhooks::new(self)
+    }
+
+    pub fn workflows(&self) -> Workflows {
+        Workflows::new(self)
+    }
+
+    pub fn screens(&self) -> Screens {
+        Screens::new(self)
     }
 
     pub(crate) fn get<F>(&self, endpoint: &[&str], handler: F) -> Result<JiraRequest, JiraError>
@@ -88,16 +190,38 @@ impl Jira {
             .expect("Always have a valid pathable URL")
             .extend(endpoint);
 
+        // Attempts, status and latency are filled in once the response arrives
+        let span = info_span!(
+            "request",
+            method = %method,
+            endpoint = base.path(),
+            attempt = field::Empty,
+            status = field::Empty,
+            latency_ms = field::Empty,
+        );
+
         let request = handler(self.agent.request(method, base.as_str()))?;
         let request = request.header(header::ACCEPT, "application/json");
         let request = self.auth.authorize(request)?;
 
-        Ok(request.into())
+        Ok(JiraRequest {
+            inner: request,
+            span,
+            cache: self
+                .cache
+                .clone()
+                .map(|cache| (cache, self.auth.identity())),
+            retries: self.retries,
+        })
     }
 }
 
 pub(crate) struct JiraRequest {
     inner: RequestBuilder,
+    span: Span,
+    /// The response cache, and the identity responses are cached for
+    cache: Option<(ResponseCache, Arc<str>)>,
+    retries: u32,
 }
 
 impl JiraRequest {
@@ -112,31 +236,223 @@ impl JiraRequest {
     where
         T: DeserializeOwned,
     {
-        let response = self.inner.send().await?;
-        match response.status() {
+        let span = self.span.clone();
+
+        self.send::<T>().instrument(span).await
+    }
+
+    async fn send<T>(self) -> Result<T, JiraError>
+    where
+        T: DeserializeOwned,
+    {
+        let (client, request) = self.inner.build_split();
+        let mut request = request?;
+
+        let cache = cacheable(self.cache.as_ref(), &request);
+        let mut cached = None;
+        if let Some((cache, key, ttl)) = cache.as_ref() {
+            match cache.get(key) {
+                Some(entry) if entry.is_fresh(*ttl) => {
+                    info!("using cached response");
+                    return parse_body(entry.body.get().as_bytes());
+                }
+                Some(entry) if entry.is_validatable() => {
+                    conditional(&mut request, &entry);
+                    cached = Some(entry);
+                }
+                _ => {}
+            }
+        }
+
+        debug!(query = request.url().query(), headers = ?Redacted(request.headers()), "sending request");
+        if let Some(body) = request.body().and_then(|b| b.as_bytes()) {
+            trace!(body = %String::from_utf8_lossy(body), "request body");
+        }
+
+        let start = Instant::now();
+        let mut attempt = 1;
+        let response = loop {
+            let retry = match attempt <= self.retries {
+                true => request.try_clone(),
+                false => None,
+            };
+            let method = request.method().clone();
+            let result = client.execute(request).await;
+
+            let retry =
+                retry.and_then(|next| Some((retry_delay(&method, &result, attempt)?, next)));
+            let (delay, next) = match retry {
+                Some(retry) => retry,
+                None => {
+                    self.span.record("attempt", attempt);
+                    break result.map_err(|e| {
+                        warn!(error = %e, "request failed");
+                        e
+                    })?;
+                }
+            };
+
+            warn!(
+                attempt,
+                status = result.as_ref().ok().map(|r| r.status().as_u16()),
+                error = result.err().map(field::display),
+                delay_ms = delay.as_millis() as u64,
+                "retrying request"
+            );
+            tokio::time::sleep(delay).await;
+            request = next;
+            attempt += 1;
+        };
+        let status = response.status();
+
+        self.span
+            .record("status", status.as_u16())
+            .record("latency_ms", start.elapsed().as_millis() as u64);
+        debug!(headers = ?Redacted(response.headers()), "received response");
+        let headers = response.headers().clone();
+
+        let body = response.bytes().await?;
+        trace!(body = %String::from_utf8_lossy(&body), "response body");
+
+        match status {
             error if error.is_client_error() || error.is_server_error() => {
+                warn!("request was rejected");
+
                 Err(JiraError::Fault(ClientFault {
                     code: error,
-                    errors: response.json::<ApiError>().await?,
+                    errors: json::from_slice::<ApiError>(&body)?,
                 }))
             }
+            StatusCode::NOT_MODIFIED if cached.is_some() => {
+                info!("cached response is still valid");
+
+                let mut entry = cached.expect("checked above");
+                entry.touch();
+                if let Some((cache, _, _)) = cache {
+                    cache.put(&entry);
+                }
+
+                parse_body(entry.body.get().as_bytes())
+            }
             _ => {
-                let body = response.bytes().await?;
+                info!("request complete");
 
-                // Handle empty responses in a manner serde_json can interpret
-                if body.is_empty() || body == b"null".as_ref() {
-                    Ok(json::from_slice(&b"{}".to_vec())?)
-                } else {
-                    Ok(json::from_slice(&body)?)
+                if let Some((cache, key, _)) = cache {
+                    if let Ok(raw) = json::from_slice::<Box<RawJson>>(&body) {
+                        let mut entry = CachedResponse::new(key, raw);
+                        entry.etag = header_string(&headers, header::ETAG);
+                        entry.last_modified = header_string(&headers, header::LAST_MODIFIED);
+                        cache.put(&entry);
+                    }
                 }
+
+                parse_body(&body)
             }
         }
     }
 }
 
-impl From<RequestBuilder> for JiraRequest {
-    fn from(inner: RequestBuilder) -> Self {
-        JiraRequest { inner }
+/// How long to wait before retrying the given outcome of a request, if it
+/// should be retried at all
+fn retry_delay(
+    method: &Method,
+    outcome: &Result<reqwest::Response, reqwest::Error>,
+    attempt: u32,
+) -> Option<Duration> {
+    // Jira never saw a request which could not connect, or was rate limited
+    let idempotent = matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+    );
+    let backoff = RETRY_DELAY * 2u32.saturating_pow(attempt - 1);
+
+    match outcome {
+        Err(e) if e.is_connect() || (idempotent && e.is_timeout()) => Some(backoff),
+        Ok(response) => match response.status() {
+            StatusCode::TOO_MANY_REQUESTS => Some(
+                header_string(response.headers(), header::RETRY_AFTER)
+                    .and_then(|s| s.trim().parse().ok())
+                    .map(Duration::from_secs)
+                    .map_or(backoff, |d| d.min(MAX_RETRY_DELAY)),
+            ),
+            StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+                if idempotent =>
+            {
+                Some(backoff)
+            }
+            _ => None,
+        },
+        Err(_) => None,
+    }
+}
+
+/// Handle empty responses in a manner serde_json can interpret
+fn parse_body<T>(body: &[u8]) -> Result<T, JiraError>
+where
+    T: DeserializeOwned,
+{
+    if body.is_empty() || body == b"null".as_ref() {
+        Ok(json::from_slice(b"{}".as_ref())?)
+    } else {
+        Ok(json::from_slice(body)?)
+    }
+}
+
+/// The cache, key and TTL of the request if its response can be cached
+fn cacheable(
+    cache: Option<&(ResponseCache, Arc<str>)>,
+    request: &Request,
+) -> Option<(ResponseCache, String, Duration)> {
+    let (cache, identity) = cache?;
+
+    if request.method() != Method::GET {
+        return None;
+    }
+
+    let ttl = cache.ttl(request.url().path())?;
+    let key = format!("{} {} {}", request.method(), request.url(), identity);
+
+    Some((cache.clone(), key, ttl))
+}
+
+/// Turn the request into a conditional one, using the validators of a
+/// stale cached response
+fn conditional(request: &mut Request, cached: &CachedResponse) {
+    let validators = [
+        (header::IF_NONE_MATCH, cached.etag.as_deref()),
+        (header::IF_MODIFIED_SINCE, cached.last_modified.as_deref()),
+    ];
+
+    for (name, value) in validators.iter() {
+        if let Some(value) = value.and_then(|v| HeaderValue::from_str(v).ok()) {
+            request.headers_mut().insert(name.clone(), value);
+        }
+    }
+}
+
+fn header_string(headers: &HeaderMap, name: header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from)
+}
+
+/// Debug formats headers, hiding the values of credentials
+struct Redacted<'a>(&'a HeaderMap);
+
+impl fmt::Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.0.iter().map(|(name, value)| {
+                let value = match SENSITIVE_HEADERS.contains(name) {
+                    true => "<redacted>",
+                    false => value.to_str().unwrap_or("<binary>"),
+                };
+                (name.as_str(), value)
+            }))
+            .finish()
     }
 }
 
@@ -159,4 +475,163 @@ impl Authentication {
             Self::Basic(user, password) => Ok(request.basic_auth(user, Some(password))),
         }
     }
+
+    /// Who requests are made as, never including any secret
+    fn identity(&self) -> Arc<str> {
+        match self {
+            Self::Basic(user, _) => user.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{CachePolicy, EndpointClass};
+    use reqwest::header::HeaderValue;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::Mutex,
+        thread,
+    };
+
+    /// Serve `{"a":1}` with validators
+    const OK: &str = concat!(
+        "HTTP/1.1 200 OK\r\n",
+        "etag: \"v1\"\r\n",
+        "last-modified: Thu, 04 Mar 2021 10:11:12 GMT\r\n",
+        "content-type: application/json\r\n",
+        "content-length: 7\r\n\r\n",
+        "{\"a\":1}",
+    );
+    const NOT_MODIFIED: &str = "HTTP/1.1 304 Not Modified\r\ncontent-length: 0\r\n\r\n";
+    const UNAVAILABLE: &str = "HTTP/1.1 503 Service Unavailable\r\ncontent-length: 2\r\n\r\n{}";
+    const RATE_LIMITED: &str =
+        "HTTP/1.1 429 Too Many Requests\r\nretry-after: 0\r\ncontent-length: 2\r\n\r\n{}";
+
+    /// Answer each request with `respond`, given the lowercased head of the
+    /// request and how many came before it. Returns the server's address,
+    /// and the head of every request it receives
+    fn serve<F>(respond: F) -> (String, Arc<Mutex<Vec<String>>>)
+    where
+        F: Fn(&str, usize) -> &'static str + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut buffer = Vec::new();
+                let mut chunk = [0; 1024];
+
+                // Requests may share a connection, none of them have bodies
+                while let Ok(n @ 1..) = stream.read(&mut chunk) {
+                    buffer.extend_from_slice(&chunk[..n]);
+
+                    while let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                        let head = String::from_utf8_lossy(&buffer[..end]).to_lowercase();
+                        buffer.drain(..end + 4);
+
+                        let mut log = log.lock().unwrap();
+                        let response = respond(&head, log.len());
+                        log.push(head);
+                        let _ = stream.write_all(response.as_bytes());
+                    }
+                }
+            }
+        });
+
+        (address, requests)
+    }
+
+    fn client(address: String) -> Jira {
+        Jira::new(address, Authentication::basic("me", "secret")).unwrap()
+    }
+
+    async fn get(client: &Jira) -> Result<json::Value, JiraError> {
+        client
+            .get(&["issue", "createmeta"], Ok)
+            .unwrap()
+            .retrieve()
+            .await
+    }
+
+    #[tokio::test]
+    async fn revalidates_stale_responses() {
+        let (address, requests) = serve(|head, _| match head.contains("if-none-match: \"v1\"") {
+            true => NOT_MODIFIED,
+            false => OK,
+        });
+        // Everything is stale as soon as it is stored
+        let policy =
+            CachePolicy::none().with(|this| this.ttl(EndpointClass::Metadata, Duration::ZERO));
+        let cache = ResponseCache::memory().with(|this| this.policy(policy));
+        let client = client(address).with(|this| this.cache(cache.clone()));
+
+        let bodies = vec![get(&client).await.unwrap(), get(&client).await.unwrap()];
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(!requests[0].contains("if-none-match"));
+        assert!(requests[1].contains("if-none-match: \"v1\""));
+        assert!(requests[1].contains("if-modified-since: thu, 04 mar 2021 10:11:12 gmt"));
+        assert_eq!(bodies, vec![json::json!({ "a": 1 }); 2]);
+    }
+
+    #[tokio::test]
+    async fn retries_unavailable() {
+        let (address, requests) = serve(|_, n| match n {
+            0 => UNAVAILABLE,
+            _ => OK,
+        });
+
+        let body = get(&client(address)).await.unwrap();
+
+        assert_eq!(body, json::json!({ "a": 1 }));
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn retries_are_limited() {
+        let (address, requests) = serve(|_, _| RATE_LIMITED);
+        let client = client(address).with(|this| this.retries(1));
+
+        let error = get(&client).await.unwrap_err();
+
+        assert!(matches!(error, JiraError::Fault(ClientFault { code, .. }) if code == 429));
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn posts_are_not_retried_once_sent() {
+        let (address, requests) = serve(|_, _| UNAVAILABLE);
+
+        let result: Result<json::Value, _> = client(address)
+            .post(&["issue"], |req| Ok(req.body("{}")))
+            .unwrap()
+            .retrieve()
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn redacted_hides_credentials() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Basic c2VjcmV0"),
+        );
+        headers.insert(header::ACCEPT, HeaderValue::from_static("application/json"));
+
+        let formatted = format!("{:?}", Redacted(&headers));
+
+        assert!(formatted.contains("\"authorization\": \"<redacted>\""));
+        assert!(formatted.contains("\"accept\": \"application/json\""));
+        assert!(!formatted.contains("c2VjcmV0"));
+    }
 }
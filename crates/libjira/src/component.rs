@@ -0,0 +1,99 @@
+pub use crate::{
+    models::component as models, options::component as options, payload::component as payload,
+};
+
+use {
+    self::endpoint::*,
+    crate::{client::Jira, error::JiraError, models::empty::Empty},
+    models::{ComponentHandle, ComponentListHandle, IssueCount},
+    payload::ComponentDetails,
+    reqwest::RequestBuilder,
+};
+
+/// A handle for interacting with JIRA project components
+///
+/// It is cheap to clone a handle and may be done liberally
+#[derive(Debug, Clone)]
+pub struct Components {
+    client: Jira,
+}
+
+impl Components {
+    /// Create a new handle from a JIRA client
+    pub fn new(client: &Jira) -> Self {
+        Self {
+            client: client.clone(),
+        }
+    }
+
+    /// Retrieve every component of a project, by its key or id
+    pub async fn list(&self, project: &str) -> Result<ComponentListHandle, JiraError> {
+        self.client
+            .get(&[PROJECT, project, COMPONENTS], Ok)?
+            .retrieve()
+            .await
+    }
+
+    /// Retrieve a single component by id
+    pub async fn get(&self, id: u64) -> Result<ComponentHandle, JiraError> {
+        self.client
+            .get(&[COMPONENT, &id.to_string()], Ok)?
+            .retrieve()
+            .await
+    }
+
+    /// Create a new component, the details must include a name and
+    /// project
+    pub async fn create(&self, details: &ComponentDetails) -> Result<ComponentHandle, JiraError> {
+        let handler = |req: RequestBuilder| Ok(req.json(details));
+
+        self.client.post(&[COMPONENT], handler)?.retrieve().await
+    }
+
+    /// Edit an existing component
+    pub async fn edit(
+        &self,
+        id: u64,
+        details: &ComponentDetails,
+    ) -> Result<ComponentHandle, JiraError> {
+        let handler = |req: RequestBuilder| Ok(req.json(details));
+
+        self.client
+            .put(&[COMPONENT, &id.to_string()], handler)?
+            .retrieve()
+            .await
+    }
+
+    /// Delete a component, optionally moving its issues to another
+    pub async fn delete(
+        &self,
+        id: u64,
+        options: Option<&options::Delete>,
+    ) -> Result<(), JiraError> {
+        let handler = |req: RequestBuilder| match options {
+            Some(options) => Ok(req.query(options)),
+            None => Ok(req),
+        };
+
+        self.client
+            .delete(&[COMPONENT, &id.to_string()], handler)?
+            .retrieve::<Empty>()
+            .await
+            .map(Into::into)
+    }
+
+    /// Retrieve how many issues are in a component
+    pub async fn issue_count(&self, id: u64) -> Result<IssueCount, JiraError> {
+        self.client
+            .get(&[COMPONENT, &id.to_string(), RELATED_COUNTS], Ok)?
+            .retrieve()
+            .await
+    }
+}
+
+mod endpoint {
+    pub(super) const PROJECT: &str = "project";
+    pub(super) const COMPONENTS: &str = "components";
+    pub(super) const COMPONENT: &str = "component";
+    pub(super) const RELATED_COUNTS: &str = "relatedIssueCounts";
+}
@@ -27,6 +27,96 @@ pub enum JiraError {
     Fault(ClientFault),
 }
 
+/// Errors converting between a user defined issue schema
+/// and Jira's representation of an issue
+#[derive(Debug, Error)]
+pub enum SchemaError {
+    #[error("Missing required field '{}'", .0)]
+    MissingField(String),
+    #[error("Unable to resolve the field named '{}', did you request the 'names' expand?", .0)]
+    UnknownName(String),
+    #[error("Invalid field '{}': {}", .field, .source)]
+    InvalidField {
+        field: String,
+        source: serde_json::Error,
+    },
+}
+
+/// Errors found in an issue create or edit payload
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum UpdateError {
+    #[error("Field(s) present in both 'fields' and 'update': {}", .0.join(", "))]
+    Conflict(Vec<String>),
+    #[error("Invalid value for field '{}': {}", .field, .reason)]
+    InvalidValue { field: String, reason: String },
+    #[error("Invalid update operation(s) for field '{}', expected a list of {{\"<set|add|remove|edit>\": <value>}}", .0)]
+    InvalidOperation(String),
+}
+
+/// A share permission which could not be parsed
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Invalid share permission '{}', expected one of: global, loggedin, group:<name>, project:<id>, project-role:<project id>:<role id> or user:<account id>", .0)]
+pub struct ShareError(pub String);
+
+/// Errors receiving a webhook event
+#[cfg(feature = "receiver")]
+#[derive(Debug, Error)]
+pub enum ReceiveError {
+    #[error("Missing the X-Hub-Signature header, was the webhook registered with a secret?")]
+    MissingSignature,
+    #[error("Invalid X-Hub-Signature, expected 'sha256=<hex digest>'")]
+    MalformedSignature,
+    #[error("The webhook signature does not match the payload")]
+    BadSignature,
+    #[error("Invalid webhook payload: {}", .0)]
+    Payload(#[from] serde_json::Error),
+    #[error(transparent)]
+    Server(#[from] hyper::Error),
+}
+
+/// Every problem found when validating a payload against
+/// Jira's create or edit metadata
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Invalid issue payload:\n{}", display_list(.problems))]
+pub struct ValidationError {
+    pub problems: Vec<Problem>,
+}
+
+/// A single problem found when validating a payload
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum Problem {
+    #[error(transparent)]
+    Payload(#[from] UpdateError),
+    #[error("Unable to find project '{}' in the create metadata", .0)]
+    UnknownProject(String),
+    #[error("Unable to find issue type '{}' in the create metadata", .0)]
+    UnknownIssueType(String),
+    #[error("The create metadata has no fields, was it requested with the 'projects.issuetypes.fields' expand?")]
+    MissingFieldsMeta,
+    #[error("Missing required field '{}' ({})", .field, .name)]
+    MissingRequired { field: String, name: String },
+    #[error("Field '{}' cannot be set, it is unknown or not on the appropriate screen", .0)]
+    UnknownField(String),
+    #[error("Field '{}' does not support the '{}' operation", .field, .operation)]
+    UnsupportedOperation { field: String, operation: String },
+    #[error("Field '{}' does not allow the value {}", .field, .value)]
+    NotAllowed { field: String, value: String },
+    #[error("Field '{}' expects a value of type '{}', found {}", .field, .expected, .found)]
+    TypeMismatch {
+        field: String,
+        expected: String,
+        found: String,
+    },
+}
+
+fn display_list(problems: &[Problem]) -> String {
+    problems
+        .iter()
+        .map(|p| format!("- {}", p))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[derive(Debug)]
 pub struct ClientFault {
     pub code: StatusCode,
@@ -67,7 +157,7 @@ impl<'a, 'b> io::Write for WriteBridge<'a, 'b> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         fn io_error<E>(_: E) -> io::Error {
             // Error value does not matter because fmt::Display just maps it to fmt::Error
-            io::Error::new(io::ErrorKind::Other, "...")
+            io::Error::other("...")
         }
         let s = std::str::from_utf8(buf).map_err(io_error)?;
         self.inner.write_str(s).map_err(io_error)?;
@@ -0,0 +1,179 @@
+pub use crate::{models::filter as models, options::filter as options, payload::filter as payload};
+
+use {
+    self::endpoint::*,
+    crate::{client::Jira, error::JiraError, models::empty::Empty},
+    models::{FilterHandle, FilterListHandle, FilterSearchHandle, PermissionsHandle},
+    payload::{FilterDetails, Share},
+    reqwest::RequestBuilder,
+    serde::Serialize,
+};
+
+/// A handle for interacting with JIRA filters, i.e saved searches
+///
+/// It is cheap to clone a handle and may be done liberally
+#[derive(Debug, Clone)]
+pub struct Filters {
+    client: Jira,
+}
+
+impl Filters {
+    /// Create a new handle from a JIRA client
+    pub fn new(client: &Jira) -> Self {
+        Self {
+            client: client.clone(),
+        }
+    }
+
+    /// Retrieve a single filter by id
+    pub async fn get(
+        &self,
+        id: u64,
+        options: Option<&options::Get>,
+    ) -> Result<FilterHandle, JiraError> {
+        let handler = |req| Ok(apply(options, req));
+
+        self.client
+            .get(&[FILTER, &id.to_string()], handler)?
+            .retrieve()
+            .await
+    }
+
+    /// Search the filters visible to the user
+    ///
+    /// Note that Jira only returns the id, name and links of each filter
+    /// unless more are requested via the options' expands
+    pub async fn search(
+        &self,
+        options: Option<&options::Search>,
+    ) -> Result<FilterSearchHandle, JiraError> {
+        let handler = |req| Ok(apply(options, req));
+
+        self.client
+            .get(&[FILTER, SEARCH], handler)?
+            .retrieve()
+            .await
+    }
+
+    /// Retrieve the user's favourite filters
+    pub async fn favourites(
+        &self,
+        options: Option<&options::Get>,
+    ) -> Result<FilterListHandle, JiraError> {
+        let handler = |req| Ok(apply(options, req));
+
+        self.client
+            .get(&[FILTER, FAVOURITE], handler)?
+            .retrieve()
+            .await
+    }
+
+    /// Retrieve the filters owned by the user
+    pub async fn mine(
+        &self,
+        options: Option<&options::Mine>,
+    ) -> Result<FilterListHandle, JiraError> {
+        let handler = |req| Ok(apply(options, req));
+
+        self.client.get(&[FILTER, MY], handler)?.retrieve().await
+    }
+
+    /// Create a new filter, owned by the user
+    pub async fn create(
+        &self,
+        details: &FilterDetails,
+        options: Option<&options::Get>,
+    ) -> Result<FilterHandle, JiraError> {
+        let handler = |req| Ok(apply(options, req).json(details));
+
+        self.client.post(&[FILTER], handler)?.retrieve().await
+    }
+
+    /// Edit an existing filter, which must be owned by the user
+    pub async fn edit(
+        &self,
+        id: u64,
+        details: &FilterDetails,
+        options: Option<&options::Get>,
+    ) -> Result<FilterHandle, JiraError> {
+        let handler = |req| Ok(apply(options, req).json(details));
+
+        self.client
+            .put(&[FILTER, &id.to_string()], handler)?
+            .retrieve()
+            .await
+    }
+
+    /// Delete a filter, which must be owned by the user
+    pub async fn delete(&self, id: u64) -> Result<(), JiraError> {
+        self.client
+            .delete(&[FILTER, &id.to_string()], Ok)?
+            .retrieve::<Empty>()
+            .await
+            .map(Into::into)
+    }
+
+    /// Add the filter to the user's favourites
+    pub async fn favourite(&self, id: u64) -> Result<FilterHandle, JiraError> {
+        self.client
+            .put(&[FILTER, &id.to_string(), FAVOURITE], Ok)?
+            .retrieve()
+            .await
+    }
+
+    /// Remove the filter from the user's favourites
+    pub async fn unfavourite(&self, id: u64) -> Result<FilterHandle, JiraError> {
+        self.client
+            .delete(&[FILTER, &id.to_string(), FAVOURITE], Ok)?
+            .retrieve()
+            .await
+    }
+
+    /// Retrieve who the filter is shared with
+    pub async fn permissions(&self, id: u64) -> Result<PermissionsHandle, JiraError> {
+        self.client
+            .get(&[FILTER, &id.to_string(), PERMISSION], Ok)?
+            .retrieve()
+            .await
+    }
+
+    /// Share the filter, returning all of its share permissions
+    pub async fn share(&self, id: u64, share: &Share) -> Result<PermissionsHandle, JiraError> {
+        let handler = |req: RequestBuilder| Ok(req.json(&share.as_permission_body()));
+
+        self.client
+            .post(&[FILTER, &id.to_string(), PERMISSION], handler)?
+            .retrieve()
+            .await
+    }
+
+    /// Remove one of the filter's share permissions, by its id
+    pub async fn unshare(&self, id: u64, permission: u64) -> Result<(), JiraError> {
+        self.client
+            .delete(
+                &[FILTER, &id.to_string(), PERMISSION, &permission.to_string()],
+                Ok,
+            )?
+            .retrieve::<Empty>()
+            .await
+            .map(Into::into)
+    }
+}
+
+fn apply<S>(options: Option<&S>, req: RequestBuilder) -> RequestBuilder
+where
+    S: Serialize,
+{
+    match options {
+        Some(options) => req.query(options),
+        None => req,
+    }
+}
+
+mod endpoint {
+    pub(super) const FILTER: &str = "filter";
+    pub(super) const SEARCH: &str = "search";
+    pub(super) const FAVOURITE: &str = "favourite";
+    pub(super) const MY: &str = "my";
+    pub(super) const PERMISSION: &str = "permission";
+}
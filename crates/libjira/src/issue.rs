@@ -1,4 +1,4 @@
-pub use crate::{models::issue as models, options::issue as options};
+pub use crate::{models::issue as models, options::issue as options, payload::issue as payload};
 
 use {
     self::endpoint::*,
@@ -61,20 +61,33 @@ impl Issues {
         self.client.get(&[SEARCH], handler)?.retrieve().await
     }
 
-    /// Create a new issue from a serializable struct
+    /// Search for the issues matched by a saved filter, by id
     ///
-    /// This struct should contain at least one of:
-    /// - fields: ...
-    /// - update: ...
-    /// In addition, it must contain all of the datums required
-    /// by JIRA - `project name`, `issue type` & any `required` fields
-    /// of that issue type.
+    /// This is a search for the JQL `filter = <id>`, any query set
+    /// in the passed options is replaced
+    pub async fn search_filter(
+        &self,
+        id: u64,
+        options: Option<&options::Search>,
+    ) -> Result<SearchHandle, JiraError> {
+        let options = options
+            .cloned()
+            .unwrap_or_default()
+            .with(|this| this.jql(format!("filter = {}", id)));
+
+        self.search(Some(&options)).await
+    }
+
+    /// Create a new issue from the given payload
     ///
-    /// If your struct has both 'fields:' & 'update:' you
-    /// must ensure that no field is present in both.
+    /// The simplest way of building this payload is with a `payload::IssueUpdate`,
+    /// or by deriving `payload::JiraIssue` on your own issue struct and calling
+    /// `JiraIssue::to_payload`. Any other serializable struct with the same
+    /// shape (`{"fields": ..., "update": ...}`) is also accepted.
     ///
-    /// You can use the `meta_create` and `meta_edit` methods
-    /// to get the relevant information.
+    /// The payload must contain all of the datums required by JIRA -
+    /// `project name`, `issue type` & any `required` fields of that issue type.
+    /// You can use the `meta_create` method to get the relevant information.
     ///
     /// Finally, the following links may be helpful:
     /// - [endpoint docs](https://developer.atlassian.com/cloud/jira/platform/rest/v2/api-group-issues/#api-rest-api-2-issue-post)
@@ -92,11 +105,10 @@ impl Issues {
         self.client.post(&[ISSUE], handler)?.retrieve().await
     }
 
-    /// Edit an existing issue with the passed serializable changes
+    /// Edit an existing issue with the passed changes
     ///
-    /// These changes should include at least one of:
-    /// - fields: ...
-    /// - update: ...
+    /// Like `create`, these changes are typically a `payload::IssueUpdate`
+    /// or built via `JiraIssue::to_payload`.
     ///
     /// You can use the `meta_edit` method to get the schema of this issue's
     /// changeable fields.
@@ -134,13 +146,13 @@ impl Issues {
         K: AsRef<str>,
     {
         self.client
-            .get(&[ISSUE, key.as_ref(), EDIT_M], |req| Ok(req))?
+            .get(&[ISSUE, key.as_ref(), EDIT_M], Ok)?
             .retrieve()
             .await
     }
 }
 
-fn apply<'a, S>(options: Option<&'a S>, req: RequestBuilder) -> RequestBuilder
+fn apply<S>(options: Option<&S>, req: RequestBuilder) -> RequestBuilder
 where
     S: Serialize,
 {
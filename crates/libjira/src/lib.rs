@@ -4,6 +4,18 @@ mod error;
 
 mod models;
 mod options;
+mod payload;
 
+pub mod cache;
 pub mod client;
+pub mod component;
+pub mod filter;
 pub mod issue;
+pub mod meta;
+pub mod screen;
+#[cfg(feature = "chrono")]
+pub mod time;
+pub mod user;
+pub mod version;
+pub mod webhook;
+pub mod workflow;
@@ -0,0 +1,101 @@
+pub use crate::{models::meta as models, options::meta as options};
+
+use {
+    self::endpoint::*,
+    crate::{client::Jira, error::JiraError},
+    models::{
+        IssueTypeListHandle, LabelsHandle, PriorityListHandle, ResolutionListHandle,
+        ServerInfoHandle, StatusCategoryListHandle, StatusListHandle,
+    },
+    reqwest::RequestBuilder,
+};
+
+/// A handle for looking up the instance wide values of JIRA's fields,
+/// i.e statuses, priorities, resolutions, issue types and labels
+///
+/// It is cheap to clone a handle and may be done liberally
+#[derive(Debug, Clone)]
+pub struct Meta {
+    client: Jira,
+}
+
+impl Meta {
+    /// Create a new handle from a JIRA client
+    pub fn new(client: &Jira) -> Self {
+        Self {
+            client: client.clone(),
+        }
+    }
+
+    /// Retrieve every status, across all workflows
+    pub async fn statuses(&self) -> Result<StatusListHandle, JiraError> {
+        self.client.get(&[STATUS], Ok)?.retrieve().await
+    }
+
+    /// Retrieve every status category
+    pub async fn status_categories(&self) -> Result<StatusCategoryListHandle, JiraError> {
+        self.client.get(&[STATUS_CATEGORY], Ok)?.retrieve().await
+    }
+
+    /// Retrieve every priority
+    pub async fn priorities(&self) -> Result<PriorityListHandle, JiraError> {
+        self.client.get(&[PRIORITY], Ok)?.retrieve().await
+    }
+
+    /// Retrieve every resolution
+    pub async fn resolutions(&self) -> Result<ResolutionListHandle, JiraError> {
+        self.client.get(&[RESOLUTION], Ok)?.retrieve().await
+    }
+
+    /// Retrieve every issue type visible to the user
+    pub async fn issue_types(&self) -> Result<IssueTypeListHandle, JiraError> {
+        self.client.get(&[ISSUE_TYPE], Ok)?.retrieve().await
+    }
+
+    /// Retrieve the issue types of a single project, by its id
+    ///
+    /// Note this endpoint is only available on Jira Cloud
+    pub async fn project_issue_types(
+        &self,
+        project_id: u64,
+    ) -> Result<IssueTypeListHandle, JiraError> {
+        let handler = |req: RequestBuilder| Ok(req.query(&[(PROJECT_ID, project_id)]));
+
+        self.client
+            .get(&[ISSUE_TYPE, PROJECT], handler)?
+            .retrieve()
+            .await
+    }
+
+    /// Retrieve a page of the labels in use
+    ///
+    /// Note this endpoint is only available on Jira Cloud
+    pub async fn labels(
+        &self,
+        options: Option<&options::Labels>,
+    ) -> Result<LabelsHandle, JiraError> {
+        let handler = |req: RequestBuilder| match options {
+            Some(options) => Ok(req.query(options)),
+            None => Ok(req),
+        };
+
+        self.client.get(&[LABEL], handler)?.retrieve().await
+    }
+
+    /// Retrieve the instance's version and deployment type
+    pub async fn server_info(&self) -> Result<ServerInfoHandle, JiraError> {
+        self.client.get(&[SERVER_INFO], Ok)?.retrieve().await
+    }
+}
+
+mod endpoint {
+    pub(super) const STATUS: &str = "status";
+    pub(super) const STATUS_CATEGORY: &str = "statuscategory";
+    pub(super) const PRIORITY: &str = "priority";
+    pub(super) const RESOLUTION: &str = "resolution";
+    pub(super) const ISSUE_TYPE: &str = "issuetype";
+    pub(super) const PROJECT: &str = "project";
+    pub(super) const PROJECT_ID: &str = "projectId";
+    pub(super) const LABEL: &str = "label";
+    pub(super) const SERVER_INFO: &str = "serverInfo";
+}
@@ -0,0 +1,156 @@
+pub use super::issue::Component;
+
+use {
+    serde::{Deserialize, Serialize, Serializer},
+    serde_json::{self as json, value::RawValue as RawJson, Error as JsonError},
+    std::convert::TryFrom,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct ComponentHandle {
+    // This handle must never be exposed in the public API
+    inner: handle::ComponentInner,
+}
+
+impl ComponentHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::ComponentInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &Component<'_> {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for ComponentHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for ComponentHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// Every component of a project
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct ComponentListHandle {
+    inner: handle::ComponentListInner,
+}
+
+impl ComponentListHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::ComponentListInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &[Component<'_>] {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for ComponentListHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for ComponentListHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// The number of issues in a component
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct IssueCount {
+    #[serde(rename = "issueCount")]
+    pub issues: u64,
+}
+
+mod handle {
+    use super::*;
+    use ouroboros::self_referencing as ouroboros;
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct ComponentInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Component<'this>,
+    }
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct ComponentListInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Vec<Component<'this>>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::issue::common::types;
+
+    #[test]
+    fn deserialize_component_handle() {
+        let mut component = types::component();
+        component["project"] = "FOO".into();
+        component["projectId"] = 10000.into();
+        component["lead"] = types::user();
+        component["assigneeType"] = "COMPONENT_LEAD".into();
+        let json = json::to_vec(&component).unwrap();
+
+        let handle: ComponentHandle = json::from_slice(&json).expect("a valid component");
+
+        assert_eq!(handle.data().project.as_deref(), Some("FOO"));
+        assert_eq!(handle.data().lead.as_ref().unwrap().name, "foo");
+    }
+
+    #[test]
+    fn deserialize_component_list_handle() {
+        let json = json::to_vec(&serde_json::json!([types::component()])).unwrap();
+
+        let handle: ComponentListHandle = json::from_slice(&json).expect("valid components");
+
+        assert_eq!(handle.data().len(), 1);
+        assert!(handle.data()[0].lead.is_none());
+    }
+}
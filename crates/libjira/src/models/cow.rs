@@ -31,6 +31,16 @@ where
     Ok(specialized.map(|cs| cs.0))
 }
 
+/// Deserialize a list of Cows
+pub fn deserialize_vec<'de, D>(deserializer: D) -> Result<Vec<Cow<'de, str>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let specialized: Vec<CowStr> = Vec::deserialize(deserializer)?;
+
+    Ok(specialized.into_iter().map(|cs| cs.0).collect())
+}
+
 /// Deserialize a hash map with both keys and values being Cow
 pub fn deserialize_kv<'de, D>(
     deserializer: D,
@@ -69,9 +79,9 @@ impl<'a> CowStr<'a> {
     }
 }
 
-impl<'a> Into<Cow<'a, str>> for CowStr<'a> {
-    fn into(self) -> Cow<'a, str> {
-        self.0
+impl<'a> From<CowStr<'a>> for Cow<'a, str> {
+    fn from(val: CowStr<'a>) -> Self {
+        val.0
     }
 }
 
@@ -145,7 +155,7 @@ mod tests {
         b: Cow<'a, str>,
     }
 
-    const INPUT: &'static str = r#"{"b": "I should be borrowed"}"#;
+    const INPUT: &str = r#"{"b": "I should be borrowed"}"#;
 
     #[test]
     fn deserialize_is_borrowed() {
@@ -0,0 +1,237 @@
+use {
+    super::*,
+    json::{value::RawValue as RawJson, Error as JsonError},
+    serde::Serializer,
+    serde_json as json,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct FilterHandle {
+    inner: handle::FilterInner,
+}
+
+impl FilterHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::FilterInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &Filter<'_> {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for FilterHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for FilterHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// A list of filters, e.g the user's favourites
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct FilterListHandle {
+    inner: handle::FilterListInner,
+}
+
+impl FilterListHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::FilterListInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &[Filter<'_>] {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for FilterListHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for FilterListHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// A saved search, a JQL query shared with other users
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Filter<'a> {
+    #[serde(with = "id")]
+    pub id: u64,
+    #[serde(rename = "self", borrow, deserialize_with = "cow::deserialize")]
+    pub self_link: Cow<'a, str>,
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub name: Cow<'a, str>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<User<'a>>,
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub jql: Cow<'a, str>,
+
+    /// A link to the filter's results in Jira's UI
+    #[serde(
+        rename = "viewUrl",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub view_url: Option<Cow<'a, str>>,
+
+    /// A link to the filter's results via the search API
+    #[serde(
+        rename = "searchUrl",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub search_url: Option<Cow<'a, str>>,
+
+    /// Is this filter one of the user's favourites?
+    #[serde(default)]
+    pub favourite: bool,
+    #[serde(rename = "favouritedCount", default)]
+    pub favourited_count: u64,
+
+    /// Who the filter is shared with
+    #[serde(rename = "sharePermissions", default, borrow)]
+    pub share_permissions: Vec<SharePermission<'a>>,
+
+    /// Who may edit the filter, besides its owner
+    #[serde(
+        rename = "editPermissions",
+        default,
+        borrow,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub edit_permissions: Vec<SharePermission<'a>>,
+}
+
+mod handle {
+    use super::*;
+    use ouroboros::self_referencing as ouroboros;
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct FilterInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Filter<'this>,
+    }
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct FilterListInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Vec<Filter<'this>>,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod types {
+    use {
+        crate::models::{filter::permission, issue::common},
+        serde_json::{json, Value as Json},
+    };
+
+    pub fn filter() -> Json {
+        json!({
+            "self": "https://jira.example.com/rest/api/2/filter/10000",
+            "id": "10000",
+            "name": "Open bugs",
+            "description": "Every unresolved bug",
+            "owner": common::types::user(),
+            "jql": "type = Bug AND resolution IS EMPTY",
+            "viewUrl": "https://jira.example.com/issues/?filter=10000",
+            "searchUrl": "https://jira.example.com/rest/api/2/search?jql=type+%3D+Bug",
+            "favourite": true,
+            "favouritedCount": 3,
+            "sharePermissions": [permission::types::global(), permission::types::group()],
+            "subscriptions": { "size": 0, "items": [] }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_filter_handle() {
+        let json = json::to_vec(&types::filter()).unwrap();
+
+        let handle: FilterHandle = json::from_slice(&json).expect("a valid filter");
+        let filter = handle.data();
+
+        assert_eq!(filter.id, 10000);
+        assert_eq!(filter.name, "Open bugs");
+        assert_eq!(filter.share_permissions.len(), 2);
+        assert!(filter.edit_permissions.is_empty());
+    }
+
+    #[test]
+    fn deserialize_filter_list_handle() {
+        let minimal = serde_json::json!({
+            "self": "https://jira.example.com/rest/api/2/filter/10001",
+            "id": "10001",
+            "name": "Mine",
+            "jql": "assignee = currentUser()"
+        });
+        let json = json::to_vec(&serde_json::json!([types::filter(), minimal])).unwrap();
+
+        let handle: FilterListHandle = json::from_slice(&json).expect("a valid filter list");
+
+        assert_eq!(handle.data().len(), 2);
+        assert!(!handle.data()[1].favourite);
+        assert!(handle.data()[1].owner.is_none());
+    }
+}
@@ -0,0 +1,14 @@
+#[allow(clippy::module_inception)]
+pub mod filter;
+pub mod permission;
+pub mod search;
+
+use {
+    super::{
+        cow,
+        issue::common::{id, Project, User},
+    },
+    serde::{Deserialize, Serialize},
+    std::{borrow::Cow, convert::TryFrom},
+};
+pub use {filter::*, permission::*, search::*};
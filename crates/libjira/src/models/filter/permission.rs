@@ -0,0 +1,214 @@
+use {
+    super::*,
+    json::{value::RawValue as RawJson, Error as JsonError},
+    serde::Serializer,
+    serde_json as json,
+};
+
+/// The share permissions of a filter
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct PermissionsHandle {
+    inner: handle::PermissionsInner,
+}
+
+impl PermissionsHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::PermissionsInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &[SharePermission<'_>] {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for PermissionsHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for PermissionsHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// Who an object (e.g a filter) is shared with. Depending on the
+/// `share_type` one of the project, role, group or user is set
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SharePermission<'a> {
+    /// The permission's id, absent for some global permissions
+    #[serde(default, deserialize_with = "optional_id")]
+    pub id: Option<u64>,
+    #[serde(rename = "type")]
+    pub share_type: ShareType,
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub project: Option<Project<'a>>,
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub role: Option<ProjectRole<'a>>,
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub group: Option<Group<'a>>,
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub user: Option<User<'a>>,
+}
+
+/// The kinds of share permission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ShareType {
+    /// Shared with everyone, including anonymous users
+    #[serde(rename = "global")]
+    Global,
+    /// Shared with any logged in user
+    #[serde(rename = "loggedin", alias = "authenticated")]
+    LoggedIn,
+    #[serde(rename = "group")]
+    Group,
+    /// Shared with anyone who can browse the project
+    #[serde(rename = "project")]
+    Project,
+    /// Shared with the members of a role in a project
+    #[serde(rename = "projectRole")]
+    ProjectRole,
+    #[serde(rename = "user")]
+    User,
+    /// Shared with a project the user can't see
+    #[serde(rename = "project-unknown")]
+    ProjectUnknown,
+}
+
+/// A role within a project, e.g "Developers"
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProjectRole<'a> {
+    #[serde(with = "id")]
+    pub id: u64,
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub name: Cow<'a, str>,
+    #[serde(
+        rename = "self",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub self_link: Option<Cow<'a, str>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Group<'a> {
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub name: Cow<'a, str>,
+    #[serde(
+        rename = "self",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub self_link: Option<Cow<'a, str>>,
+}
+
+fn optional_id<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Id(#[serde(with = "id")] u64);
+
+    let id: Option<Id> = Deserialize::deserialize(deserializer)?;
+
+    Ok(id.map(|Id(id)| id))
+}
+
+mod handle {
+    use super::*;
+    use ouroboros::self_referencing as ouroboros;
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct PermissionsInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Vec<SharePermission<'this>>,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod types {
+    use serde_json::{json, Value as Json};
+
+    pub fn global() -> Json {
+        json!({ "id": 10000, "type": "global" })
+    }
+
+    pub fn group() -> Json {
+        json!({
+            "id": 10001,
+            "type": "group",
+            "group": {
+                "name": "jira-developers",
+                "self": "https://jira.example.com/rest/api/2/group?groupname=jira-developers"
+            }
+        })
+    }
+
+    pub fn project_role() -> Json {
+        json!({
+            "id": 10002,
+            "type": "projectRole",
+            "project": {
+                "id": "10100",
+                "self": "https://jira.example.com/rest/api/2/project/10100",
+                "key": "FOO",
+                "name": "Foo"
+            },
+            "role": {
+                "id": 10360,
+                "name": "Developers",
+                "self": "https://jira.example.com/rest/api/2/project/FOO/role/10360"
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_permissions_handle() {
+        let permissions = serde_json::json!([
+            types::global(),
+            types::group(),
+            types::project_role(),
+            { "type": "authenticated" }
+        ]);
+        let json = json::to_vec(&permissions).unwrap();
+
+        let handle: PermissionsHandle = json::from_slice(&json).expect("valid permissions");
+        let data = handle.data();
+
+        assert_eq!(data[0].share_type, ShareType::Global);
+        assert_eq!(data[1].group.as_ref().unwrap().name, "jira-developers");
+        assert_eq!(data[2].role.as_ref().unwrap().id, 10360);
+        assert_eq!(data[2].project.as_ref().unwrap().key, "FOO");
+        assert_eq!(data[3].share_type, ShareType::LoggedIn);
+        assert_eq!(data[3].id, None);
+    }
+}
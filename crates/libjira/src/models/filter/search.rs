@@ -0,0 +1,101 @@
+use {
+    super::*,
+    json::{value::RawValue as RawJson, Error as JsonError},
+    serde::Serializer,
+    serde_json as json,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct FilterSearchHandle {
+    inner: handle::FilterSearchInner,
+}
+
+impl FilterSearchHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::FilterSearchInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &FilterSearch<'_> {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for FilterSearchHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for FilterSearchHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// A page of filters matching a search
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FilterSearch<'a> {
+    #[serde(rename = "maxResults")]
+    pub max_results: u64,
+    #[serde(rename = "startAt")]
+    pub start_at: u64,
+    pub total: u64,
+    #[serde(rename = "isLast", default)]
+    pub is_last: bool,
+    #[serde(borrow)]
+    pub values: Vec<Filter<'a>>,
+}
+
+mod handle {
+    use super::*;
+    use ouroboros::self_referencing as ouroboros;
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct FilterSearchInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: FilterSearch<'this>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::filter::filter;
+
+    #[test]
+    fn deserialize_filter_search_handle() {
+        let page = serde_json::json!({
+            "self": "https://jira.example.com/rest/api/2/filter/search?startAt=0",
+            "maxResults": 50,
+            "startAt": 0,
+            "total": 1,
+            "isLast": true,
+            "values": [filter::types::filter()]
+        });
+        let json = json::to_vec(&page).unwrap();
+
+        let search: FilterSearchHandle = json::from_slice(&json).expect("a valid page");
+
+        assert_eq!(search.data().total, 1);
+        assert_eq!(search.data().values[0].name, "Open bugs");
+    }
+}
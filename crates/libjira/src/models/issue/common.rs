@@ -22,11 +22,30 @@ pub struct User<'a> {
     pub avatar_urls: HashMap<Cow<'a, str>, Cow<'a, str>>,
     #[serde(rename = "displayName", borrow, deserialize_with = "cow::deserialize")]
     pub display_name: Cow<'a, str>,
-    #[serde(rename = "emailAddress", borrow, deserialize_with = "cow::deserialize")]
+
+    /// Empty if hidden by the user's privacy settings on Jira Cloud
+    #[serde(
+        rename = "emailAddress",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize"
+    )]
     pub email_address: Cow<'a, str>,
     #[serde(default, borrow, deserialize_with = "cow::deserialize_option")]
     pub key: Option<Cow<'a, str>>,
-    #[serde(borrow, deserialize_with = "cow::deserialize")]
+
+    /// The user's id on Jira Cloud, which has no user names or keys
+    #[serde(
+        rename = "accountId",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub account_id: Option<Cow<'a, str>>,
+
+    /// The user's name, empty on Jira Cloud
+    #[serde(default, borrow, deserialize_with = "cow::deserialize")]
     pub name: Cow<'a, str>,
     #[serde(rename = "self", borrow, deserialize_with = "cow::deserialize")]
     pub self_link: Cow<'a, str>,
@@ -55,7 +74,31 @@ pub struct Status<'a> {
     pub name: Cow<'a, str>,
     #[serde(rename = "self", borrow, deserialize_with = "cow::deserialize")]
     pub self_link: Cow<'a, str>,
-    // TODO: Add statusCategory as optional
+    #[serde(
+        rename = "statusCategory",
+        default,
+        borrow,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status_category: Option<StatusCategory<'a>>,
+}
+
+/// The broad category a status belongs to, one of: "To Do",
+/// "In Progress", "Done" or (rarely) "No Category"
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StatusCategory<'a> {
+    #[serde(with = "id")]
+    pub id: u64,
+    /// A stable identifier for the category, e.g "new", "indeterminate"
+    /// or "done"
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub key: Cow<'a, str>,
+    #[serde(rename = "colorName", borrow, deserialize_with = "cow::deserialize")]
+    pub color_name: Cow<'a, str>,
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub name: Cow<'a, str>,
+    #[serde(rename = "self", borrow, deserialize_with = "cow::deserialize")]
+    pub self_link: Cow<'a, str>,
 }
 
 /// The issue kind, examples include: "Story", "Epic"
@@ -75,6 +118,9 @@ pub struct IssueType<'a> {
 }
 
 /// A project or product release
+///
+/// The optional fields are only present when the version is
+/// retrieved directly, rather than as part of an issue
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Version<'a> {
     pub archived: bool,
@@ -85,6 +131,84 @@ pub struct Version<'a> {
     pub released: bool,
     #[serde(rename = "self", borrow, deserialize_with = "cow::deserialize")]
     pub self_link: Cow<'a, str>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<Cow<'a, str>>,
+    #[serde(rename = "projectId", default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<u64>,
+
+    /// The start date of the version, as YYYY-MM-DD
+    #[serde(
+        rename = "startDate",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub start_date: Option<Cow<'a, str>>,
+
+    /// The (planned) release date of the version, as YYYY-MM-DD
+    #[serde(
+        rename = "releaseDate",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub release_date: Option<Cow<'a, str>>,
+
+    /// Is the version unreleased past its release date?
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overdue: Option<bool>,
+}
+
+/// A grouping of issues within a project, e.g: "Backend", "Docs"
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Component<'a> {
+    #[serde(with = "id")]
+    pub id: u64,
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub name: Cow<'a, str>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<Cow<'a, str>>,
+    #[serde(rename = "self", borrow, deserialize_with = "cow::deserialize")]
+    pub self_link: Cow<'a, str>,
+
+    /// The key of the component's project, only present when the
+    /// component is retrieved directly
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub project: Option<Cow<'a, str>>,
+    #[serde(rename = "projectId", default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<u64>,
+
+    /// The component's lead, if it has one
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub lead: Option<User<'a>>,
+
+    /// Who issues in the component are assigned to by default, e.g
+    /// "PROJECT_DEFAULT", "COMPONENT_LEAD" or "UNASSIGNED"
+    #[serde(
+        rename = "assigneeType",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub assignee_type: Option<Cow<'a, str>>,
 }
 
 // Wrapper struct for flattening Jira's json
@@ -96,9 +220,9 @@ pub(super) struct Comments<'a> {
     inner: Vec<Comment<'a>>,
 }
 
-impl<'a> Into<Vec<Comment<'a>>> for Comments<'a> {
-    fn into(self) -> Vec<Comment<'a>> {
-        self.inner
+impl<'a> From<Comments<'a>> for Vec<Comment<'a>> {
+    fn from(val: Comments<'a>) -> Self {
+        val.inner
     }
 }
 
@@ -133,6 +257,49 @@ pub struct Comment<'a> {
     pub visibility: Option<Visibility<'a>>,
 }
 
+/// A single field change in an issue's history, e.g a status transition
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChangeItem<'a> {
+    /// The field's name, e.g "status" or "Story Points"
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub field: Cow<'a, str>,
+    /// Either "jira" or "custom"
+    #[serde(rename = "fieldtype", borrow, deserialize_with = "cow::deserialize")]
+    pub field_type: Cow<'a, str>,
+    #[serde(
+        rename = "fieldId",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub field_id: Option<Cow<'a, str>>,
+
+    /// The previous value's id, if the field references another object
+    #[serde(default, borrow, deserialize_with = "cow::deserialize_option")]
+    pub from: Option<Cow<'a, str>>,
+    /// The previous value, for display
+    #[serde(
+        rename = "fromString",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option"
+    )]
+    pub from_string: Option<Cow<'a, str>>,
+
+    /// The new value's id, if the field references another object
+    #[serde(default, borrow, deserialize_with = "cow::deserialize_option")]
+    pub to: Option<Cow<'a, str>>,
+    /// The new value, for display
+    #[serde(
+        rename = "toString",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option"
+    )]
+    pub to_string: Option<Cow<'a, str>>,
+}
+
 // Not all Jira's have this...
 /// The visibility of the associated object.
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -186,7 +353,29 @@ pub struct LinkType<'a> {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Resolution<'a> {
     #[serde(borrow, deserialize_with = "cow::deserialize")]
-    name: Cow<'a, str>,
+    pub name: Cow<'a, str>,
+    #[serde(
+        default,
+        deserialize_with = "id::deserialize_option",
+        serialize_with = "id::serialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub id: Option<u64>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<Cow<'a, str>>,
+    #[serde(
+        rename = "self",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub self_link: Option<Cow<'a, str>>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -219,6 +408,22 @@ pub struct Priority<'a> {
     pub name: Cow<'a, str>,
     #[serde(rename = "self", borrow, deserialize_with = "cow::deserialize")]
     pub self_link: Cow<'a, str>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<Cow<'a, str>>,
+    /// The priority's color, as a hex code e.g "#d04437"
+    #[serde(
+        rename = "statusColor",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status_color: Option<Cow<'a, str>>,
 }
 
 /// One of the flavours of response returned by some
@@ -282,6 +487,38 @@ pub mod id {
         Ok(id.into())
     }
 
+    pub fn serialize_option<S>(id: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        id.map(Id::from).serialize(serializer)
+    }
+
+    pub fn deserialize_option<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let id: Option<Id> = Deserialize::deserialize(deserializer)?;
+
+        Ok(id.map(Into::into))
+    }
+
+    pub fn serialize_vec<S>(ids: &[u64], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(ids.iter().copied().map(Id::from))
+    }
+
+    pub fn deserialize_vec<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let ids: Vec<Id> = Deserialize::deserialize(deserializer)?;
+
+        Ok(ids.into_iter().map(Into::into).collect())
+    }
+
     #[derive(Debug, Clone, Copy)]
     struct Id {
         id: u64,
@@ -397,6 +634,16 @@ pub(crate) mod types {
         })
     }
 
+    pub fn status_category() -> Json {
+        json!({
+            "id": 2,
+            "key": "new",
+            "colorName": "blue-gray",
+            "name": "To Do",
+            "self": "foo",
+        })
+    }
+
     pub fn issuetype() -> Json {
         json!({
             "description": "foo",
@@ -418,6 +665,15 @@ pub(crate) mod types {
         })
     }
 
+    pub fn component() -> Json {
+        json!({
+            "id": "42",
+            "name": "foo",
+            "description": "foo",
+            "self": "foo",
+        })
+    }
+
     pub fn comment() -> Json {
         json!({
             "id": "42",
@@ -431,6 +687,30 @@ pub(crate) mod types {
         })
     }
 
+    pub fn cloud_user() -> Json {
+        json!({
+            "self": "foo",
+            "accountId": "5b10ac8d82e05b22cc7d4ef5",
+            "avatarUrls": { "48x48": "foo" },
+            "displayName": "foo",
+            "active": true,
+            "timeZone": "Europe/London",
+            "accountType": "atlassian",
+        })
+    }
+
+    pub fn change_item() -> Json {
+        json!({
+            "field": "status",
+            "fieldtype": "jira",
+            "fieldId": "status",
+            "from": "1",
+            "fromString": "Open",
+            "to": "3",
+            "toString": "In Progress",
+        })
+    }
+
     pub fn comments() -> Json {
         json!([comment(), comment(),])
     }
@@ -534,6 +814,26 @@ mod tests {
         assert!(user.is_ok())
     }
 
+    #[test]
+    fn deserialize_cloud_user() {
+        let json = jbytes!(types::cloud_user());
+        let user: User = deserialize(&json).expect("a Cloud user");
+
+        assert_eq!(user.account_id.as_deref(), Some("5b10ac8d82e05b22cc7d4ef5"));
+        assert!(user.name.is_empty());
+        assert!(user.email_address.is_empty());
+    }
+
+    #[test]
+    fn deserialize_change_item() {
+        let json = jbytes!(types::change_item());
+        let item: ChangeItem = deserialize(&json).expect("a change item");
+
+        assert_eq!(item.field, "status");
+        assert_eq!(item.from_string.as_deref(), Some("Open"));
+        assert_eq!(item.to.as_deref(), Some("3"));
+    }
+
     #[test]
     fn deserialize_status() {
         let json = jbytes!(types::status());
@@ -543,6 +843,17 @@ mod tests {
         assert!(status.is_ok())
     }
 
+    #[test]
+    fn deserialize_status_with_category() {
+        let mut status = types::status();
+        status["statusCategory"] = types::status_category();
+        let json = jbytes!(status);
+
+        let status: Status = deserialize(&json).expect("a valid status");
+
+        assert_eq!(status.status_category.map(|c| c.id), Some(2));
+    }
+
     #[test]
     fn deserialize_issuetype() {
         let json = jbytes!(types::issuetype());
@@ -561,6 +872,15 @@ mod tests {
         assert!(version.is_ok())
     }
 
+    #[test]
+    fn deserialize_component() {
+        let json = jbytes!(types::component());
+
+        let component: Result<Component, _> = deserialize(&json);
+
+        assert!(component.is_ok())
+    }
+
     #[test]
     fn deserialize_comment() {
         let json = jbytes!(types::comment());
@@ -624,6 +944,19 @@ mod tests {
         assert!(resolution.is_ok())
     }
 
+    #[test]
+    fn roundtrip_resolution_id() {
+        let json = jbytes!({ "name": "Done", "id": "10000", "self": "foo" });
+
+        let resolution: Resolution = deserialize(&json).expect("a valid resolution");
+
+        assert_eq!(resolution.id, Some(10000));
+        assert_eq!(
+            serde_json::to_value(&resolution).unwrap(),
+            json!({ "name": "Done", "id": "10000", "self": "foo" })
+        );
+    }
+
     #[test]
     fn deserialize_attachment() {
         let json = jbytes!(types::attachment());
@@ -664,9 +997,8 @@ mod tests {
     where
         T: Deserialize<'de>,
     {
-        let value = serde_json::from_slice(bytes).map_err(|error| {
-            dbg!(&error);
-            error
+        let value = serde_json::from_slice(bytes).inspect_err(|error| {
+            dbg!(error);
         });
 
         value
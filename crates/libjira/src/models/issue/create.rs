@@ -21,7 +21,7 @@ impl CreatedHandle {
     }
 
     /// Access this handle's data
-    pub fn data(&self) -> &Created {
+    pub fn data(&self) -> &Created<'_> {
         self.inner.borrow_handle()
     }
 
@@ -53,14 +53,14 @@ impl Serialize for CreatedHandle {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Created<'a> {
     #[serde(with = "common::id")]
-    id: u64,
+    pub id: u64,
     #[serde(borrow, deserialize_with = "cow::deserialize")]
-    key: Cow<'a, str>,
+    pub key: Cow<'a, str>,
     #[serde(rename = "self", borrow, deserialize_with = "cow::deserialize")]
-    self_link: Cow<'a, str>,
+    pub self_link: Cow<'a, str>,
     // Only exists if a transition was requested in the associated request
     #[serde(skip_serializing_if = "Option::is_none")]
-    transition: Option<NestedResponse<'a>>,
+    pub transition: Option<NestedResponse<'a>>,
 }
 
 mod handle {
@@ -122,9 +122,8 @@ mod tests {
     where
         T: Deserialize<'de>,
     {
-        let value = serde_json::from_slice(bytes).map_err(|error| {
-            dbg!(&error);
-            error
+        let value = serde_json::from_slice(bytes).inspect_err(|error| {
+            dbg!(error);
         });
 
         value
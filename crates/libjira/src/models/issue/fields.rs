@@ -0,0 +1,264 @@
+use {
+    super::{cow::CowStr, *},
+    json::{value::RawValue as RawJson, Error as JsonError},
+    serde::de::Error as _,
+    serde_json as json,
+    std::collections::HashMap,
+};
+
+/// A typed view of the fields every Jira instance ships with.
+///
+/// Each field is parsed from the same backing store as its parent
+/// `Issue`, so any strings are borrowed rather than copied. Fields that
+/// were not returned by Jira (or were `null`) are `None`.
+///
+/// Any field that is not modeled here, including all `customfield_*`s,
+/// is left untouched in `custom`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StandardFields<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status<'a>>,
+    #[serde(rename = "issuetype", skip_serializing_if = "Option::is_none")]
+    pub issue_type: Option<IssueType<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<User<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reporter: Option<User<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<User<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<Cow<'a, str>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<Component<'a>>>,
+    #[serde(rename = "fixVersions", skip_serializing_if = "Option::is_none")]
+    pub fix_versions: Option<Vec<Version<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<Resolution<'a>>,
+
+    /// A ISO-8601 timestamp of issue creation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<Cow<'a, str>>,
+
+    /// A ISO-8601 timestamp of the latest update
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<Cow<'a, str>>,
+
+    /// A calendar date (YYYY-MM-DD) this issue is due by
+    #[serde(rename = "duedate", skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<Cow<'a, str>>,
+
+    /// The parent of this issue, typically only set on subtasks
+    /// and the children of epics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Issue<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtasks: Option<Vec<Issue<'a>>>,
+    #[serde(rename = "issuelinks", skip_serializing_if = "Option::is_none")]
+    pub issue_links: Option<Vec<IssueLink<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachment: Option<Vec<Attachment<'a>>>,
+
+    /// The issue's comments, with Jira's pagination wrapper removed
+    #[serde(rename = "comment", skip_serializing_if = "Option::is_none")]
+    pub comments: Option<Vec<Comment<'a>>>,
+
+    /// Every field not modeled above, left as raw JSON
+    #[serde(flatten)]
+    pub custom: HashMap<Cow<'a, str>, &'a RawJson>,
+}
+
+impl<'a> StandardFields<'a> {
+    /// Parse the standard fields out of an issue's `fields` map, failing
+    /// if any of the known fields do not match their expected type
+    pub fn try_new(fields: &HashMap<Cow<'a, str>, &'a RawJson>) -> Result<Self, JsonError> {
+        let mut this = Self::default();
+
+        for (name, &raw) in fields.iter() {
+            match name.as_ref() {
+                key::SUMMARY => this.summary = parse_str(name, raw)?,
+                key::DESCRIPTION => this.description = parse_str(name, raw)?,
+                key::STATUS => this.status = parse(name, raw)?,
+                key::ISSUE_TYPE => this.issue_type = parse(name, raw)?,
+                key::PRIORITY => this.priority = parse(name, raw)?,
+                key::ASSIGNEE => this.assignee = parse(name, raw)?,
+                key::REPORTER => this.reporter = parse(name, raw)?,
+                key::CREATOR => this.creator = parse(name, raw)?,
+                key::LABELS => {
+                    this.labels = parse::<Vec<CowStr>>(name, raw)?
+                        .map(|labels| labels.into_iter().map(Into::into).collect())
+                }
+                key::COMPONENTS => this.components = parse(name, raw)?,
+                key::FIX_VERSIONS => this.fix_versions = parse(name, raw)?,
+                key::RESOLUTION => this.resolution = parse(name, raw)?,
+                key::CREATED => this.created = parse_str(name, raw)?,
+                key::UPDATED => this.updated = parse_str(name, raw)?,
+                key::DUE_DATE => this.due_date = parse_str(name, raw)?,
+                key::PARENT => this.parent = parse(name, raw)?,
+                key::SUBTASKS => this.subtasks = parse(name, raw)?,
+                key::ISSUE_LINKS => this.issue_links = parse(name, raw)?,
+                key::ATTACHMENT => this.attachment = parse(name, raw)?,
+                key::COMMENT => {
+                    this.comments = parse::<CommentPage>(name, raw)?.map(|page| page.comments)
+                }
+                _ => {
+                    this.custom.insert(name.clone(), raw);
+                }
+            }
+        }
+
+        Ok(this)
+    }
+
+    /// Attempt to deserialize one of the `custom` fields
+    pub fn custom<'de, T>(&self, name: &str) -> Option<Result<T, JsonError>>
+    where
+        T: Deserialize<'de>,
+        'a: 'de,
+    {
+        self.custom.get(name).map(|&raw| json::from_str(raw.get()))
+    }
+}
+
+// Jira nests the actual comments inside a paginated object:
+// comment.comments.[ <-- Comment objects here --> ]
+#[derive(Debug, Deserialize)]
+struct CommentPage<'a> {
+    #[serde(borrow)]
+    comments: Vec<Comment<'a>>,
+}
+
+/// Deserialize a nullable field, annotating any errors with the
+/// field's name
+fn parse<'a, T>(name: &str, raw: &'a RawJson) -> Result<Option<T>, JsonError>
+where
+    T: Deserialize<'a>,
+{
+    json::from_str(raw.get())
+        .map_err(|e| JsonError::custom(format_args!("invalid field '{}': {}", name, e)))
+}
+
+fn parse_str<'a>(name: &str, raw: &'a RawJson) -> Result<Option<Cow<'a, str>>, JsonError> {
+    parse::<CowStr>(name, raw).map(|s| s.map(Into::into))
+}
+
+mod key {
+    pub(super) const SUMMARY: &str = "summary";
+    pub(super) const DESCRIPTION: &str = "description";
+    pub(super) const STATUS: &str = "status";
+    pub(super) const ISSUE_TYPE: &str = "issuetype";
+    pub(super) const PRIORITY: &str = "priority";
+    pub(super) const ASSIGNEE: &str = "assignee";
+    pub(super) const REPORTER: &str = "reporter";
+    pub(super) const CREATOR: &str = "creator";
+    pub(super) const LABELS: &str = "labels";
+    pub(super) const COMPONENTS: &str = "components";
+    pub(super) const FIX_VERSIONS: &str = "fixVersions";
+    pub(super) const RESOLUTION: &str = "resolution";
+    pub(super) const CREATED: &str = "created";
+    pub(super) const UPDATED: &str = "updated";
+    pub(super) const DUE_DATE: &str = "duedate";
+    pub(super) const PARENT: &str = "parent";
+    pub(super) const SUBTASKS: &str = "subtasks";
+    pub(super) const ISSUE_LINKS: &str = "issuelinks";
+    pub(super) const ATTACHMENT: &str = "attachment";
+    pub(super) const COMMENT: &str = "comment";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::issue::issue::types;
+    use serde_json::Value as Json;
+
+    #[test]
+    fn standard_fields() {
+        let json = jbytes(types::issue());
+        let issue: Issue = deserialize(&json).unwrap();
+
+        let fields = issue.standard_fields().expect("valid standard fields");
+
+        assert_eq!(fields.summary.as_deref(), Some("foo"));
+        assert_eq!(fields.due_date.as_deref(), Some("2021-03-04"));
+        assert_eq!(fields.labels.map(|l| l.len()), Some(2));
+        assert_eq!(fields.comments.map(|c| c.len()), Some(2));
+        assert_eq!(fields.subtasks.map(|s| s.len()), Some(1));
+        assert_eq!(
+            fields.parent.map(|p| p.key),
+            Some(Cow::Borrowed("PARENT-1"))
+        );
+        assert!(fields.status.is_some());
+        assert!(fields.components.is_some());
+    }
+
+    #[test]
+    fn standard_fields_null() {
+        let json = jbytes(serde_json::json!({
+            "summary": null,
+            "assignee": null,
+        }));
+        let map: HashMap<Cow<str>, &RawJson> = deserialize(&json).unwrap();
+
+        let fields = StandardFields::try_new(&map).expect("valid standard fields");
+
+        assert!(fields.summary.is_none());
+        assert!(fields.assignee.is_none());
+        assert!(fields.custom.is_empty());
+    }
+
+    #[test]
+    fn standard_fields_invalid() {
+        let json = jbytes(serde_json::json!({ "status": "not a status" }));
+        let map: HashMap<Cow<str>, &RawJson> = deserialize(&json).unwrap();
+
+        let error = StandardFields::try_new(&map).unwrap_err();
+
+        assert!(error.to_string().contains("status"))
+    }
+
+    #[test]
+    fn custom_fields_raw() {
+        let json = jbytes(types::issue());
+        let issue: Issue = deserialize(&json).unwrap();
+
+        let fields = issue.standard_fields().unwrap();
+        let points: Option<f64> = fields.custom("customfield_10016").and_then(Result::ok);
+
+        assert_eq!(points, Some(3.0));
+        assert!(!fields.custom.contains_key("summary"));
+    }
+
+    #[test]
+    fn labels_borrow() {
+        let json = jbytes(types::issue());
+        let issue: Issue = deserialize(&json).unwrap();
+
+        for label in issue.standard_fields().unwrap().labels.unwrap() {
+            match label {
+                Cow::Borrowed(_) => {}
+                Cow::Owned(_) => panic!("label '{}' is not borrowed", label),
+            }
+        }
+    }
+
+    fn jbytes(json: Json) -> Vec<u8> {
+        serde_json::to_vec(&json)
+            .expect("Failed to serialize in models/issue/fields tests... this is a bug")
+    }
+
+    fn deserialize<'de, 'a: 'de, T>(bytes: &'a [u8]) -> Result<T, serde_json::Error>
+    where
+        T: Deserialize<'de>,
+    {
+        let value = serde_json::from_slice(bytes).inspect_err(|error| {
+            dbg!(error);
+        });
+
+        value
+    }
+}
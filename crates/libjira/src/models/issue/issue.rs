@@ -24,10 +24,15 @@ impl IssueHandle {
     }
 
     /// Access this handle's data
-    pub fn data(&self) -> &Issue {
+    pub fn data(&self) -> &Issue<'_> {
         self.inner.borrow_handle()
     }
 
+    /// Parse this handle's standard fields, see `Issue::standard_fields`
+    pub fn standard_fields(&self) -> Result<StandardFields<'_>, JsonError> {
+        self.data().standard_fields()
+    }
+
     /// Consume the handle returning the backing
     /// storage
     pub fn into_inner(self) -> Box<RawJson> {
@@ -61,7 +66,8 @@ pub struct Issue<'a> {
     pub id: u64,
     #[serde(borrow)]
     pub key: Cow<'a, str>,
-    #[serde(borrow)]
+    // Nested issues (e.g parents, subtasks, links) omit this
+    #[serde(borrow, skip_serializing_if = "str_is_empty")]
     pub expand: Cow<'a, str>,
     #[serde(borrow)]
     pub fields: HashMap<Cow<'a, str>, &'a RawJson>,
@@ -74,6 +80,16 @@ pub struct Issue<'a> {
 impl<'a> Issue<'a> {
     const FIELDS: &'static str = "fields";
 
+    /// Parse the standard Jira fields of this issue into their typed
+    /// representations. Custom and otherwise unknown fields are kept as
+    /// raw JSON, see `StandardFields` for more.
+    ///
+    /// This is done on demand, so callers that only want one or two
+    /// fields may prefer the individual accessors (e.g `Issue::summary`).
+    pub fn standard_fields(&self) -> Result<StandardFields<'a>, JsonError> {
+        StandardFields::try_new(&self.fields)
+    }
+
     /// Attempt to deserialize an arbitrary value from the `fields` with the given dot `.`
     /// delimited json pointer.
     ///
@@ -149,22 +165,22 @@ impl<'a> Issue<'a> {
         self.field(key)
     }
 
-    fn user_field(&self, key: &str) -> Option<Result<User, JsonError>> {
+    fn user_field(&self, key: &str) -> Option<Result<User<'_>, JsonError>> {
         self.field(key)
     }
 
     /// User assigned to the issue
-    pub fn assignee(&self) -> Option<User> {
+    pub fn assignee(&self) -> Option<User<'_>> {
         self.field("assignee").and_then(Result::ok)
     }
 
     /// User that originally created the issue
-    pub fn creator(&self) -> Option<User> {
+    pub fn creator(&self) -> Option<User<'_>> {
         self.user_field("creator").and_then(Result::ok)
     }
 
     /// User that reported the issue
-    pub fn reporter(&self) -> Option<User> {
+    pub fn reporter(&self) -> Option<User<'_>> {
         self.user_field("reporter").and_then(Result::ok)
     }
 
@@ -174,7 +190,7 @@ impl<'a> Issue<'a> {
     }
 
     /// Issue status
-    pub fn status(&self) -> Option<Status> {
+    pub fn status(&self) -> Option<Status<'_>> {
         self.field("status").and_then(Result::ok)
     }
 
@@ -198,8 +214,13 @@ impl<'a> Issue<'a> {
         self.string_field("resolutiondate").and_then(Result::ok)
     }
 
+    /// Issue's due date
+    pub fn due_date(&self) -> Option<&str> {
+        self.string_field("duedate").and_then(Result::ok)
+    }
+
     /// Description of the issue's type
-    pub fn issue_type(&self) -> Option<IssueType> {
+    pub fn issue_type(&self) -> Option<IssueType<'_>> {
         self.field("issuetype").and_then(Result::ok)
     }
 
@@ -209,12 +230,12 @@ impl<'a> Issue<'a> {
     }
 
     /// Issue fix version(s)
-    pub fn fix_versions(&self) -> Option<Vec<Version>> {
+    pub fn fix_versions(&self) -> Option<Vec<Version<'_>>> {
         self.field("fixVersions").and_then(Result::ok)
     }
 
     /// Issue's comments
-    pub fn comments(&self) -> Option<Vec<Comment>> {
+    pub fn comments(&self) -> Option<Vec<Comment<'_>>> {
         // Note JIRA's json path here looks like: issue.comment.comments.[ <-- Comment objects here --> ]
         // We remove some of this indirection here, so it appears to the user like: issue.comments.[...]
         self.field::<Comments>("comment")
@@ -222,27 +243,27 @@ impl<'a> Issue<'a> {
     }
 
     /// Issue's priority
-    pub fn priority(&self) -> Option<Priority> {
+    pub fn priority(&self) -> Option<Priority<'_>> {
         self.field("priority").and_then(Result::ok)
     }
 
     /// Other Issues that are linked to the current Issue
-    pub fn issue_links(&self) -> Option<Vec<IssueLink>> {
+    pub fn issue_links(&self) -> Option<Vec<IssueLink<'_>>> {
         self.field("issuelinks").and_then(Result::ok)
     }
 
     /// The project this Issue is assigned to
-    pub fn project(&self) -> Option<Project> {
+    pub fn project(&self) -> Option<Project<'_>> {
         self.field("project").and_then(Result::ok)
     }
 
     /// This Issue's resolution, if it exists
-    pub fn resolution(&self) -> Option<Resolution> {
+    pub fn resolution(&self) -> Option<Resolution<'_>> {
         self.field("resolution").and_then(Result::ok)
     }
 
     /// Any attachments this Issue contains
-    pub fn attachment(&self) -> Option<Vec<Attachment>> {
+    pub fn attachment(&self) -> Option<Vec<Attachment<'_>>> {
         self.field("attachment").and_then(Result::ok)
     }
 }
@@ -370,9 +391,7 @@ impl<'a, 'de: 'a> Deserialize<'de> for Issue<'a> {
                         .ok_or_else(|| missing(Self::SELF_LINK))?,
                     id: id.ok_or_else(|| missing(Self::ID))?.value,
                     key: key.map(|s| s.into()).ok_or_else(|| missing(Self::KEY))?,
-                    expand: expand
-                        .map(|s| s.into())
-                        .ok_or_else(|| missing(Self::EXPAND))?,
+                    expand: expand.map(|s| s.into()).unwrap_or_default(),
                     fields: fields
                         .map(|map| map.into_iter().map(|(k, v)| (k.0, v)).collect())
                         .ok_or_else(|| missing(Self::FIELDS))?,
@@ -387,6 +406,10 @@ impl<'a, 'de: 'a> Deserialize<'de> for Issue<'a> {
     }
 }
 
+fn str_is_empty(s: &str) -> bool {
+    s.is_empty()
+}
+
 mod handle {
     use super::*;
     use ouroboros::self_referencing as ouroboros;
@@ -430,12 +453,31 @@ pub(crate) mod types {
                 "issuelinks": [common::types::issuelink()],
                 "priority": common::types::priority(),
                 "resolution": common::types::resolution(),
-                "attachment": common::types::attachment(),
+                "attachment": [common::types::attachment()],
+                "components": [common::types::component()],
+                "duedate": "2021-03-04",
+                "parent": nested_issue("PARENT-1"),
+                "subtasks": [nested_issue("SUB-1")],
+                "customfield_10016": 3.0,
             },
             "nonstandard": "field",
             "another": "strange field",
         })
     }
+
+    /// Issues embedded in other issues have no 'expand'
+    /// and only a handful of fields
+    pub fn nested_issue(key: &str) -> Json {
+        json!({
+            "self": "foo",
+            "id": "42",
+            "key": key,
+            "fields": {
+                "summary": "foo",
+                "status": common::types::status(),
+            },
+        })
+    }
 }
 
 #[cfg(test)]
@@ -463,6 +505,15 @@ mod tests {
         assert!(issue.is_ok())
     }
 
+    #[test]
+    fn deserialize_nested_issue() {
+        let json = jbytes(types::nested_issue("foo"));
+
+        let issue: Result<Issue, _> = deserialize(&json);
+
+        assert!(issue.is_ok())
+    }
+
     #[test]
     fn fields_map_borrow() {
         let json = jbytes(types::issue());
@@ -500,9 +551,8 @@ mod tests {
     where
         T: Deserialize<'de>,
     {
-        let value = serde_json::from_slice(bytes).map_err(|error| {
-            dbg!(&error);
-            error
+        let value = serde_json::from_slice(bytes).inspect_err(|error| {
+            dbg!(error);
         });
 
         value
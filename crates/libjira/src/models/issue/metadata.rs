@@ -32,7 +32,7 @@ impl MetaCreateHandle {
     }
 
     /// Access this handle's data
-    pub fn data(&self) -> &MetaCreate {
+    pub fn data(&self) -> &MetaCreate<'_> {
         self.inner.borrow_handle()
     }
 
@@ -83,7 +83,7 @@ impl MetaEditHandle {
     }
 
     /// Access this handle's data
-    pub fn data(&self) -> &MetaEdit {
+    pub fn data(&self) -> &MetaEdit<'_> {
         self.inner.borrow_handle()
     }
 
@@ -181,7 +181,7 @@ pub struct FieldSchema<'a> {
     pub items: Option<Cow<'a, str>>,
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Operations {
     Set,
@@ -190,6 +190,28 @@ pub enum Operations {
     Remove,
 }
 
+impl Operations {
+    pub fn try_new(input: &str) -> Option<Self> {
+        match input {
+            "set" => Some(Self::Set),
+            "edit" => Some(Self::Edit),
+            "add" => Some(Self::Add),
+            "remove" => Some(Self::Remove),
+            _ => None,
+        }
+    }
+
+    /// The name Jira uses for this operation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Set => "set",
+            Self::Edit => "edit",
+            Self::Add => "add",
+            Self::Remove => "remove",
+        }
+    }
+}
+
 mod handle {
     use super::*;
     use ouroboros::self_referencing as ouroboros;
@@ -234,9 +256,9 @@ pub(crate) mod types {
     pub fn project_meta() -> Json {
         let mut value = json!({ "issuetypes": [issuetype_meta()] });
 
-        value
-            .as_object_mut()
-            .map(|map| map.append(common::types::project().as_object_mut().unwrap()));
+        if let Some(map) = value.as_object_mut() {
+            map.append(common::types::project().as_object_mut().unwrap())
+        }
 
         value
     }
@@ -248,9 +270,9 @@ pub(crate) mod types {
             },
         });
 
-        value
-            .as_object_mut()
-            .map(|map| map.append(common::types::issuetype().as_object_mut().unwrap()));
+        if let Some(map) = value.as_object_mut() {
+            map.append(common::types::issuetype().as_object_mut().unwrap())
+        }
 
         value
     }
@@ -354,7 +376,7 @@ mod tests {
         struct OpT {
             op: Json,
             t: &'static str,
-        };
+        }
         let operations = vec![
             OpT {
                 op: types::operation_set(),
@@ -388,9 +410,8 @@ mod tests {
     where
         T: Deserialize<'de>,
     {
-        let value = serde_json::from_slice(bytes).map_err(|error| {
-            dbg!(&error);
-            error
+        let value = serde_json::from_slice(bytes).inspect_err(|error| {
+            dbg!(error);
         });
 
         value
@@ -1,5 +1,7 @@
 pub mod common;
 pub mod create;
+pub mod fields;
+#[allow(clippy::module_inception)]
 pub mod issue;
 pub mod metadata;
 pub mod search;
@@ -9,4 +11,4 @@ use {
     serde::{Deserialize, Serialize},
     std::{borrow::Cow, convert::TryFrom},
 };
-pub use {common::*, create::*, issue::*, metadata::*, search::*};
+pub use {common::*, create::*, fields::*, issue::*, metadata::*, search::*};
@@ -20,7 +20,7 @@ impl SearchHandle {
     }
 
     /// Access this handle's data
-    pub fn data(&self) -> &Search {
+    pub fn data(&self) -> &Search<'_> {
         self.inner.borrow_handle()
     }
 
@@ -122,9 +122,8 @@ mod tests {
     where
         T: Deserialize<'de>,
     {
-        let value = serde_json::from_slice(bytes).map_err(|error| {
-            dbg!(&error);
-            error
+        let value = serde_json::from_slice(bytes).inspect_err(|error| {
+            dbg!(error);
         });
 
         value
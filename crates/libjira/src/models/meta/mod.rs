@@ -0,0 +1,516 @@
+pub use super::issue::{IssueType, Priority, Resolution, Status, StatusCategory};
+
+use {
+    super::cow,
+    serde::{Deserialize, Serialize, Serializer},
+    serde_json::{self as json, value::RawValue as RawJson, Error as JsonError},
+    std::{borrow::Cow, convert::TryFrom},
+};
+
+/// Every status of the instance, across all workflows
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct StatusListHandle {
+    inner: handle::StatusListInner,
+}
+
+impl StatusListHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::StatusListInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &[Status<'_>] {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for StatusListHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for StatusListHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// Every status category
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct StatusCategoryListHandle {
+    inner: handle::StatusCategoryListInner,
+}
+
+impl StatusCategoryListHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner =
+            handle::StatusCategoryListInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &[StatusCategory<'_>] {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for StatusCategoryListHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for StatusCategoryListHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// Every issue priority, highest first
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct PriorityListHandle {
+    inner: handle::PriorityListInner,
+}
+
+impl PriorityListHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::PriorityListInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &[Priority<'_>] {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for PriorityListHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for PriorityListHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// Every issue resolution
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct ResolutionListHandle {
+    inner: handle::ResolutionListInner,
+}
+
+impl ResolutionListHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::ResolutionListInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &[Resolution<'_>] {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for ResolutionListHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for ResolutionListHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// A list of issue types, either every type visible to the user or
+/// those of a single project
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct IssueTypeListHandle {
+    inner: handle::IssueTypeListInner,
+}
+
+impl IssueTypeListHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::IssueTypeListInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &[IssueType<'_>] {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for IssueTypeListHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for IssueTypeListHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// A page of the labels in use
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct LabelsHandle {
+    inner: handle::LabelsInner,
+}
+
+impl LabelsHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::LabelsInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &Labels<'_> {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for LabelsHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for LabelsHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// Information about the Jira instance
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct ServerInfoHandle {
+    inner: handle::ServerInfoInner,
+}
+
+impl ServerInfoHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::ServerInfoInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &ServerInfo<'_> {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for ServerInfoHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for ServerInfoHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// A page of the labels used by issues
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Labels<'a> {
+    #[serde(rename = "maxResults")]
+    pub max_results: u64,
+    #[serde(rename = "startAt")]
+    pub start_at: u64,
+    pub total: u64,
+    #[serde(rename = "isLast")]
+    pub is_last: bool,
+    #[serde(borrow, deserialize_with = "cow::deserialize_vec")]
+    pub values: Vec<Cow<'a, str>>,
+}
+
+/// The version and deployment details of a Jira instance
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ServerInfo<'a> {
+    #[serde(rename = "baseUrl", borrow, deserialize_with = "cow::deserialize")]
+    pub base_url: Cow<'a, str>,
+    /// The Jira version, e.g "8.20.1" or "1001.0.0-SNAPSHOT" on Cloud
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub version: Cow<'a, str>,
+    #[serde(rename = "versionNumbers", default)]
+    pub version_numbers: Vec<u64>,
+    /// "Cloud" or "Server", which includes Data Center
+    #[serde(
+        rename = "deploymentType",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub deployment_type: Option<Cow<'a, str>>,
+    #[serde(rename = "buildNumber", default)]
+    pub build_number: u64,
+    #[serde(
+        rename = "buildDate",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub build_date: Option<Cow<'a, str>>,
+    #[serde(
+        rename = "serverTime",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub server_time: Option<Cow<'a, str>>,
+    #[serde(
+        rename = "scmInfo",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub scm_info: Option<Cow<'a, str>>,
+    #[serde(
+        rename = "serverTitle",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub server_title: Option<Cow<'a, str>>,
+}
+
+mod handle {
+    use super::*;
+    use ouroboros::self_referencing as ouroboros;
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct StatusListInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Vec<Status<'this>>,
+    }
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct StatusCategoryListInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Vec<StatusCategory<'this>>,
+    }
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct PriorityListInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Vec<Priority<'this>>,
+    }
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct ResolutionListInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Vec<Resolution<'this>>,
+    }
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct IssueTypeListInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Vec<IssueType<'this>>,
+    }
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct LabelsInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Labels<'this>,
+    }
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct ServerInfoInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: ServerInfo<'this>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::issue::common::types;
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_list_handles() {
+        let statuses = json::to_vec(&json!([types::status(), types::status()])).unwrap();
+        let priorities = json::to_vec(&json!([types::priority()])).unwrap();
+        let types = json::to_vec(&json!([types::issuetype()])).unwrap();
+
+        let statuses: StatusListHandle = json::from_slice(&statuses).expect("valid statuses");
+        let priorities: PriorityListHandle =
+            json::from_slice(&priorities).expect("valid priorities");
+        let types: IssueTypeListHandle = json::from_slice(&types).expect("valid issue types");
+
+        assert_eq!(statuses.data().len(), 2);
+        assert_eq!(priorities.data().len(), 1);
+        assert_eq!(types.data().len(), 1);
+    }
+
+    #[test]
+    fn deserialize_labels_handle() {
+        let json = json::to_vec(&json!({
+            "maxResults": 2,
+            "startAt": 0,
+            "total": 3,
+            "isLast": false,
+            "values": ["backend", "ui"]
+        }))
+        .unwrap();
+
+        let handle: LabelsHandle = json::from_slice(&json).expect("a valid label page");
+
+        assert_eq!(handle.data().values, ["backend", "ui"]);
+        assert!(!handle.data().is_last);
+    }
+
+    #[test]
+    fn deserialize_server_info_handle() {
+        let json = json::to_vec(&json!({
+            "baseUrl": "https://example.atlassian.net",
+            "version": "1001.0.0-SNAPSHOT",
+            "versionNumbers": [1001, 0, 0],
+            "deploymentType": "Cloud",
+            "buildNumber": 100198,
+            "buildDate": "2022-05-10T00:00:00.000+0000",
+            "serverTime": "2022-05-11T10:11:12.000+0000",
+            "scmInfo": "abc123",
+            "serverTitle": "Jira"
+        }))
+        .unwrap();
+
+        let handle: ServerInfoHandle = json::from_slice(&json).expect("valid server info");
+
+        assert_eq!(handle.data().deployment_type.as_deref(), Some("Cloud"));
+        assert_eq!(handle.data().version_numbers, [1001, 0, 0]);
+    }
+}
@@ -1,3 +1,11 @@
+pub mod component;
 pub mod cow;
 pub mod empty;
+pub mod filter;
 pub mod issue;
+pub mod meta;
+pub mod screen;
+pub mod user;
+pub mod version;
+pub mod webhook;
+pub mod workflow;
@@ -0,0 +1,251 @@
+use {
+    super::{cow, issue::common::id},
+    serde::{Deserialize, Serialize, Serializer},
+    serde_json::{self as json, value::RawValue as RawJson, Error as JsonError},
+    std::{borrow::Cow, convert::TryFrom},
+};
+
+/// A page of screens
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct ScreensHandle {
+    inner: handle::ScreensInner,
+}
+
+impl ScreensHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::ScreensInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &Screens<'_> {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for ScreensHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for ScreensHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// The tabs of a screen, in display order
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct ScreenTabListHandle {
+    inner: handle::ScreenTabListInner,
+}
+
+impl ScreenTabListHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::ScreenTabListInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &[ScreenTab<'_>] {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for ScreenTabListHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for ScreenTabListHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// The fields of a screen tab, in display order
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct ScreenFieldListHandle {
+    inner: handle::ScreenFieldListInner,
+}
+
+impl ScreenFieldListHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::ScreenFieldListInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &[ScreenField<'_>] {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for ScreenFieldListHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for ScreenFieldListHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Screens<'a> {
+    #[serde(rename = "maxResults")]
+    pub max_results: u64,
+    #[serde(rename = "startAt")]
+    pub start_at: u64,
+    pub total: u64,
+    #[serde(rename = "isLast", default)]
+    pub is_last: bool,
+    #[serde(borrow)]
+    pub values: Vec<Screen<'a>>,
+}
+
+/// A screen, i.e the fields shown when creating, editing or
+/// transitioning issues
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Screen<'a> {
+    #[serde(with = "id")]
+    pub id: u64,
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub name: Cow<'a, str>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<Cow<'a, str>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScreenTab<'a> {
+    #[serde(with = "id")]
+    pub id: u64,
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub name: Cow<'a, str>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScreenField<'a> {
+    /// The field's id, e.g "summary" or "customfield_10000"
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub id: Cow<'a, str>,
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub name: Cow<'a, str>,
+}
+
+mod handle {
+    use super::*;
+    use ouroboros::self_referencing as ouroboros;
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct ScreensInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Screens<'this>,
+    }
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct ScreenTabListInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Vec<ScreenTab<'this>>,
+    }
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct ScreenFieldListInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Vec<ScreenField<'this>>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_screens_handle() {
+        let json = json::to_vec(&json!({
+            "maxResults": 100,
+            "startAt": 0,
+            "total": 1,
+            "isLast": true,
+            "values": [{ "id": 1, "name": "Default Screen", "description": "Provides for the update of all system fields." }]
+        }))
+        .unwrap();
+
+        let handle: ScreensHandle = json::from_slice(&json).expect("a valid page");
+
+        assert_eq!(handle.data().values[0].id, 1);
+    }
+
+    #[test]
+    fn deserialize_tab_and_field_handles() {
+        let tabs = json::to_vec(&json!([{ "id": 10000, "name": "Fields Tab" }])).unwrap();
+        let fields = json::to_vec(&json!([{ "id": "summary", "name": "Summary" }])).unwrap();
+
+        let tabs: ScreenTabListHandle = json::from_slice(&tabs).expect("valid tabs");
+        let fields: ScreenFieldListHandle = json::from_slice(&fields).expect("valid fields");
+
+        assert_eq!(tabs.data()[0].name, "Fields Tab");
+        assert_eq!(fields.data()[0].id, "summary");
+    }
+}
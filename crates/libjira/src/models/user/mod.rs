@@ -0,0 +1,80 @@
+pub use super::issue::User;
+
+use {
+    serde::{Deserialize, Serialize, Serializer},
+    serde_json::{self as json, value::RawValue as RawJson, Error as JsonError},
+    std::convert::TryFrom,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct UserHandle {
+    // This handle must never be exposed in the public API
+    inner: handle::UserInner,
+}
+
+impl UserHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::UserInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &User<'_> {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for UserHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for UserHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+mod handle {
+    use super::*;
+    use ouroboros::self_referencing as ouroboros;
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct UserInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: User<'this>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::issue::common::types;
+
+    #[test]
+    fn deserialize_user_handle() {
+        let json = serde_json::to_vec(&types::user()).unwrap();
+
+        let handle: UserHandle = serde_json::from_slice(&json).expect("a valid user");
+
+        assert_eq!(handle.data().display_name, "foo")
+    }
+}
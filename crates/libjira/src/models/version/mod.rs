@@ -0,0 +1,187 @@
+pub use super::issue::Version;
+
+use {
+    serde::{Deserialize, Serialize, Serializer},
+    serde_json::{self as json, value::RawValue as RawJson, Error as JsonError},
+    std::convert::TryFrom,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct VersionHandle {
+    // This handle must never be exposed in the public API
+    inner: handle::VersionInner,
+}
+
+impl VersionHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::VersionInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &Version<'_> {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for VersionHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for VersionHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// Every version of a project
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct VersionListHandle {
+    inner: handle::VersionListInner,
+}
+
+impl VersionListHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::VersionListInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &[Version<'_>] {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for VersionListHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for VersionListHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// The number of issues with a version as their fix version,
+/// and how many of those are unresolved
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct UnresolvedCount {
+    #[serde(rename = "issuesCount")]
+    pub issues: u64,
+    #[serde(rename = "issuesUnresolvedCount")]
+    pub unresolved: u64,
+}
+
+/// The number of issues related to a version
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct RelatedCounts {
+    /// Issues with the version as a fix version
+    #[serde(rename = "issuesFixedCount")]
+    pub fixed: u64,
+    /// Issues with the version as an affected version
+    #[serde(rename = "issuesAffectedCount")]
+    pub affected: u64,
+    /// Issues referencing the version from a custom field
+    #[serde(rename = "issueCountWithCustomFieldsShowingVersion", default)]
+    pub custom_fields: u64,
+}
+
+mod handle {
+    use super::*;
+    use ouroboros::self_referencing as ouroboros;
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct VersionInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Version<'this>,
+    }
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct VersionListInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Vec<Version<'this>>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::issue::common::types;
+
+    #[test]
+    fn deserialize_version_handle() {
+        let mut version = types::version();
+        version["description"] = "The first release".into();
+        version["projectId"] = 10000.into();
+        version["releaseDate"] = "2021-03-04".into();
+        let json = json::to_vec(&version).unwrap();
+
+        let handle: VersionHandle = json::from_slice(&json).expect("a valid version");
+
+        assert_eq!(handle.data().project_id, Some(10000));
+        assert_eq!(handle.data().release_date.as_deref(), Some("2021-03-04"));
+        assert_eq!(handle.data().start_date, None);
+    }
+
+    #[test]
+    fn deserialize_version_list_handle() {
+        let json = json::to_vec(&serde_json::json!([types::version(), types::version()])).unwrap();
+
+        let handle: VersionListHandle = json::from_slice(&json).expect("valid versions");
+
+        assert_eq!(handle.data().len(), 2);
+    }
+
+    #[test]
+    fn deserialize_counts() {
+        let unresolved: UnresolvedCount =
+            json::from_str(r#"{"self": "foo", "issuesUnresolvedCount": 2, "issuesCount": 30}"#)
+                .unwrap();
+        let related: RelatedCounts = json::from_str(
+            r#"{"self": "foo", "issuesFixedCount": 23, "issuesAffectedCount": 101}"#,
+        )
+        .unwrap();
+
+        assert_eq!(unresolved.unresolved, 2);
+        assert_eq!(related.fixed, 23);
+        assert_eq!(related.custom_fields, 0);
+    }
+}
@@ -0,0 +1,295 @@
+use {
+    super::*,
+    crate::models::issue::{ChangeItem, Comment, Issue, User},
+};
+
+/// An event delivered to a webhook
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct EventHandle {
+    inner: handle::EventInner,
+}
+
+impl EventHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::EventInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &Event<'_> {
+        self.inner.borrow_handle()
+    }
+
+    /// The event as Jira sent it, including anything not
+    /// captured by `Event`
+    pub fn raw(&self) -> &RawJson {
+        self.inner.borrow_store_contents()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for EventHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for EventHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// An event delivered to a webhook.
+///
+/// Which of the optional parts are present depends on the kind of
+/// event, for example "jira:issue_updated" carries the `issue`, the
+/// `user` who updated it and a `changelog` of the fields changed, while
+/// "comment_created" carries the `comment` and an abridged `issue`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Event<'a> {
+    /// The event's name, e.g "jira:issue_created", see `Event::kind`
+    #[serde(rename = "webhookEvent", borrow, deserialize_with = "cow::deserialize")]
+    pub webhook_event: Cow<'a, str>,
+
+    /// When the event occurred, in milliseconds since the unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+
+    /// The more specific reason for issue events, e.g "issue_generic"
+    /// or "issue_commented"
+    #[serde(
+        rename = "issue_event_type_name",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub issue_event_type: Option<Cow<'a, str>>,
+
+    /// The user who caused the event
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub user: Option<User<'a>>,
+
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub issue: Option<Issue<'a>>,
+
+    /// The fields changed by an issue update
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub changelog: Option<Changelog<'a>>,
+
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<Comment<'a>>,
+
+    /// The dynamic webhooks this event was delivered for, absent for
+    /// webhooks registered by an administrator
+    #[serde(
+        rename = "matchedWebhookIds",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub matched_webhook_ids: Vec<u64>,
+}
+
+impl<'a> Event<'a> {
+    /// The kind of this event
+    pub fn kind(&self) -> EventKind {
+        EventKind::from_name(&self.webhook_event)
+    }
+}
+
+/// The fields changed by an issue update
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Changelog<'a> {
+    #[serde(
+        default,
+        serialize_with = "id::serialize_option",
+        deserialize_with = "id::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub id: Option<u64>,
+    #[serde(borrow)]
+    pub items: Vec<ChangeItem<'a>>,
+}
+
+impl<'a> Changelog<'a> {
+    /// Find the change to a field, by its id (e.g "status") or name
+    pub fn field(&self, field: &str) -> Option<&ChangeItem<'a>> {
+        self.items
+            .iter()
+            .find(|item| item.field_id.as_deref() == Some(field) || item.field == field)
+    }
+}
+
+/// The kinds of webhook event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    IssueCreated,
+    IssueUpdated,
+    IssueDeleted,
+    CommentCreated,
+    CommentUpdated,
+    CommentDeleted,
+    WorklogCreated,
+    WorklogUpdated,
+    WorklogDeleted,
+    IssueLinkCreated,
+    IssueLinkDeleted,
+    /// Any other event, e.g those for projects, versions or sprints.
+    /// Their payloads are available from `EventHandle::raw`
+    Other,
+}
+
+impl EventKind {
+    const NAMES: [(Self, &'static str); 11] = [
+        (Self::IssueCreated, "jira:issue_created"),
+        (Self::IssueUpdated, "jira:issue_updated"),
+        (Self::IssueDeleted, "jira:issue_deleted"),
+        (Self::CommentCreated, "comment_created"),
+        (Self::CommentUpdated, "comment_updated"),
+        (Self::CommentDeleted, "comment_deleted"),
+        (Self::WorklogCreated, "worklog_created"),
+        (Self::WorklogUpdated, "worklog_updated"),
+        (Self::WorklogDeleted, "worklog_deleted"),
+        (Self::IssueLinkCreated, "issuelink_created"),
+        (Self::IssueLinkDeleted, "issuelink_deleted"),
+    ];
+
+    /// Look up the kind of a `webhookEvent` name
+    pub fn from_name(name: &str) -> Self {
+        Self::NAMES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(kind, _)| *kind)
+            .unwrap_or(Self::Other)
+    }
+
+    /// The `webhookEvent` name of this kind, which is also the name
+    /// used when registering a webhook. `None` for `Other`
+    pub fn name(&self) -> Option<&'static str> {
+        Self::NAMES
+            .iter()
+            .find(|(kind, _)| kind == self)
+            .map(|(_, name)| *name)
+    }
+}
+
+mod handle {
+    use super::*;
+    use ouroboros::self_referencing as ouroboros;
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct EventInner {
+        pub(super) store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Event<'this>,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod types {
+    use {
+        crate::models::issue::common::types as issue,
+        serde_json::{json, Value as Json},
+    };
+
+    pub fn issue_updated() -> Json {
+        json!({
+            "timestamp": 1616000000000u64,
+            "webhookEvent": "jira:issue_updated",
+            "issue_event_type_name": "issue_generic",
+            "user": issue::cloud_user(),
+            "issue": {
+                "id": "10001",
+                "self": "https://jira.example.com/rest/api/2/10001",
+                "key": "FOO-1",
+                "fields": { "summary": "Fix the thing" }
+            },
+            "changelog": { "id": "10100", "items": [issue::change_item()] },
+            "matchedWebhookIds": [1000]
+        })
+    }
+
+    pub fn comment_created() -> Json {
+        json!({
+            "timestamp": 1616000000000u64,
+            "webhookEvent": "comment_created",
+            "comment": {
+                "id": "10200",
+                "self": "https://jira.example.com/rest/api/2/issue/10001/comment/10200",
+                "author": issue::cloud_user(),
+                "updateAuthor": issue::cloud_user(),
+                "body": "Looks good",
+                "created": "2021-03-17T16:53:20.000+0000",
+                "updated": "2021-03-17T16:53:20.000+0000",
+                "jsdPublic": true
+            },
+            "issue": {
+                "id": "10001",
+                "self": "https://jira.example.com/rest/api/2/10001",
+                "key": "FOO-1",
+                "fields": { "summary": "Fix the thing" }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_issue_updated() {
+        let json = json::to_vec(&types::issue_updated()).unwrap();
+        let handle: EventHandle = json::from_slice(&json).expect("a valid event");
+        let event = handle.data();
+
+        assert_eq!(event.kind(), EventKind::IssueUpdated);
+        assert_eq!(event.issue.as_ref().map(|i| i.id), Some(10001));
+        assert_eq!(event.matched_webhook_ids, [1000]);
+
+        let status = event.changelog.as_ref().and_then(|c| c.field("status"));
+        assert_eq!(
+            status.and_then(|item| item.to_string.as_deref()),
+            Some("In Progress")
+        );
+    }
+
+    #[test]
+    fn deserialize_comment_created() {
+        let json = json::to_vec(&types::comment_created()).unwrap();
+        let handle: EventHandle = json::from_slice(&json).expect("a valid event");
+        let event = handle.data();
+
+        assert_eq!(event.kind(), EventKind::CommentCreated);
+        assert_eq!(event.comment.as_ref().map(|c| c.id), Some(10200));
+        assert!(event.user.is_none());
+        assert!(handle.raw().get().contains("jsdPublic"));
+    }
+
+    #[test]
+    fn event_kind_names() {
+        assert_eq!(EventKind::from_name("sprint_started"), EventKind::Other);
+        assert_eq!(EventKind::Other.name(), None);
+
+        for (kind, name) in EventKind::NAMES.iter() {
+            assert_eq!(EventKind::from_name(name), *kind);
+            assert_eq!(kind.name(), Some(*name));
+        }
+    }
+}
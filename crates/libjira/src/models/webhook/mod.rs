@@ -0,0 +1,192 @@
+pub mod event;
+
+pub use event::*;
+
+use {
+    super::{cow, issue::common::id},
+    json::{value::RawValue as RawJson, Error as JsonError},
+    serde::{Deserialize, Serialize, Serializer},
+    serde_json as json,
+    std::{borrow::Cow, convert::TryFrom},
+};
+
+/// A page of the webhooks registered by the calling app
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct WebhooksHandle {
+    inner: handle::WebhooksInner,
+}
+
+impl WebhooksHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::WebhooksInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &Webhooks<'_> {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for WebhooksHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for WebhooksHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// A page of registered webhooks
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Webhooks<'a> {
+    #[serde(rename = "maxResults")]
+    pub max_results: u32,
+    #[serde(rename = "startAt")]
+    pub start_at: u32,
+    #[serde(default)]
+    pub total: Option<u32>,
+    #[serde(rename = "isLast", default)]
+    pub is_last: Option<bool>,
+    #[serde(borrow)]
+    pub values: Vec<Webhook<'a>>,
+}
+
+/// A dynamic webhook, registered by a Connect or OAuth 2.0 app
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Webhook<'a> {
+    pub id: u64,
+    /// The JQL an issue must match for the webhook to fire
+    #[serde(rename = "jqlFilter", borrow, deserialize_with = "cow::deserialize")]
+    pub jql_filter: Cow<'a, str>,
+    /// The events the webhook fires on, e.g "jira:issue_created"
+    #[serde(borrow, deserialize_with = "cow::deserialize_vec")]
+    pub events: Vec<Cow<'a, str>>,
+    /// The fields whose changes trigger "jira:issue_updated"
+    #[serde(
+        rename = "fieldIdsFilter",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_vec",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub field_ids_filter: Vec<Cow<'a, str>>,
+    /// The issue properties whose changes trigger "issue_property_set"
+    #[serde(
+        rename = "issuePropertyKeysFilter",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_vec",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub issue_property_keys_filter: Vec<Cow<'a, str>>,
+    /// When the webhook expires, in milliseconds since the unix epoch.
+    ///
+    /// Webhooks expire 30 days after they were registered or last
+    /// refreshed, see `Webhooks::refresh`
+    #[serde(rename = "expirationDate")]
+    pub expiration_date: u64,
+}
+
+/// The result of registering webhooks, one per webhook in the
+/// registration, in order
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RegisteredWebhooks {
+    #[serde(rename = "webhookRegistrationResult")]
+    pub results: Vec<RegistrationResult>,
+}
+
+/// The id of a newly registered webhook, or why it was rejected
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RegistrationResult {
+    #[serde(
+        rename = "createdWebhookId",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+/// When refreshed webhooks now expire
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct WebhookExpiry {
+    /// Milliseconds since the unix epoch
+    #[serde(rename = "expirationDate")]
+    pub expiration_date: u64,
+}
+
+mod handle {
+    use super::*;
+    use ouroboros::self_referencing as ouroboros;
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct WebhooksInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: Webhooks<'this>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_webhooks_handle() {
+        let json = json::to_vec(&serde_json::json!({
+            "maxResults": 3,
+            "startAt": 0,
+            "total": 1,
+            "isLast": true,
+            "values": [{
+                "id": 10000,
+                "jqlFilter": "project = PRJ",
+                "fieldIdsFilter": ["summary", "customfield_10029"],
+                "events": ["jira:issue_updated", "jira:issue_created"],
+                "expirationDate": 1589465280000u64
+            }]
+        }))
+        .unwrap();
+
+        let page: WebhooksHandle = json::from_slice(&json).expect("a valid page");
+        let webhook = &page.data().values[0];
+
+        assert_eq!(webhook.id, 10000);
+        assert_eq!(webhook.events, ["jira:issue_updated", "jira:issue_created"]);
+        assert!(webhook.issue_property_keys_filter.is_empty());
+    }
+
+    #[test]
+    fn deserialize_registration_results() {
+        let registered: RegisteredWebhooks = json::from_str(
+            r#"{"webhookRegistrationResult": [{"createdWebhookId": 1000}, {"errors": ["The clause watchCount is unsupported"]}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(registered.results[0].id, Some(1000));
+        assert!(registered.results[0].errors.is_empty());
+        assert_eq!(registered.results[1].id, None);
+        assert_eq!(registered.results[1].errors.len(), 1);
+    }
+}
@@ -0,0 +1,10 @@
+pub mod scheme;
+#[allow(clippy::module_inception)]
+pub mod workflow;
+
+use {
+    super::{cow, issue::common::id},
+    serde::{Deserialize, Serialize},
+    std::{borrow::Cow, convert::TryFrom},
+};
+pub use {scheme::*, workflow::*};
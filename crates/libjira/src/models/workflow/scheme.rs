@@ -0,0 +1,177 @@
+use {
+    super::*,
+    json::{value::RawValue as RawJson, Error as JsonError},
+    serde::Serializer,
+    serde_json as json,
+    std::collections::HashMap,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct WorkflowSchemeProjectsHandle {
+    inner: handle::WorkflowSchemeProjectsInner,
+}
+
+impl WorkflowSchemeProjectsHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner =
+            handle::WorkflowSchemeProjectsInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &WorkflowSchemeProjects<'_> {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for WorkflowSchemeProjectsHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for WorkflowSchemeProjectsHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// The workflow schemes used by a set of projects
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkflowSchemeProjects<'a> {
+    #[serde(borrow)]
+    pub values: Vec<WorkflowSchemeProject<'a>>,
+}
+
+/// A workflow scheme, and the projects using it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkflowSchemeProject<'a> {
+    #[serde(
+        rename = "projectIds",
+        deserialize_with = "id::deserialize_vec",
+        serialize_with = "id::serialize_vec"
+    )]
+    pub project_ids: Vec<u64>,
+    #[serde(rename = "workflowScheme", borrow)]
+    pub workflow_scheme: WorkflowScheme<'a>,
+}
+
+/// Maps each of a project's issue types to the workflow it uses
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkflowScheme<'a> {
+    #[serde(
+        default,
+        deserialize_with = "id::deserialize_option",
+        serialize_with = "id::serialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub id: Option<u64>,
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub name: Cow<'a, str>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<Cow<'a, str>>,
+    /// The workflow used by issue types without a mapping
+    #[serde(
+        rename = "defaultWorkflow",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_workflow: Option<Cow<'a, str>>,
+    /// Issue type ids to workflow names
+    #[serde(
+        rename = "issueTypeMappings",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_kv"
+    )]
+    pub issue_type_mappings: HashMap<Cow<'a, str>, Cow<'a, str>>,
+    #[serde(
+        rename = "self",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub self_link: Option<Cow<'a, str>>,
+}
+
+impl WorkflowScheme<'_> {
+    /// The name of the workflow used by an issue type
+    pub fn workflow_for(&self, issue_type: u64) -> Option<&str> {
+        let mut buffer = itoa::Buffer::new();
+
+        self.issue_type_mappings
+            .get(buffer.format(issue_type))
+            .or(self.default_workflow.as_ref())
+            .map(AsRef::as_ref)
+    }
+}
+
+mod handle {
+    use super::*;
+    use ouroboros::self_referencing as ouroboros;
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct WorkflowSchemeProjectsInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: WorkflowSchemeProjects<'this>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_scheme_projects_handle() {
+        let json = json::to_vec(&serde_json::json!({
+            "values": [{
+                "projectIds": ["10010", "10020"],
+                "workflowScheme": {
+                    "id": 101010,
+                    "name": "Example workflow scheme",
+                    "description": "The description of the example workflow scheme.",
+                    "defaultWorkflow": "jira",
+                    "issueTypeMappings": { "10000": "scrum workflow" },
+                    "self": "https://jira.example.com/rest/api/2/workflowscheme/101010"
+                }
+            }]
+        }))
+        .unwrap();
+
+        let handle: WorkflowSchemeProjectsHandle = json::from_slice(&json).expect("valid schemes");
+        let scheme = &handle.data().values[0];
+
+        assert_eq!(scheme.project_ids, [10010, 10020]);
+        assert_eq!(scheme.workflow_scheme.id, Some(101010));
+        assert_eq!(
+            scheme.workflow_scheme.workflow_for(10000),
+            Some("scrum workflow")
+        );
+        assert_eq!(scheme.workflow_scheme.workflow_for(10001), Some("jira"));
+    }
+}
@@ -0,0 +1,271 @@
+use {
+    super::*,
+    json::{value::RawValue as RawJson, Error as JsonError},
+    serde::Serializer,
+    serde_json as json,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "Box<RawJson>")]
+pub struct WorkflowSearchHandle {
+    inner: handle::WorkflowSearchInner,
+}
+
+impl WorkflowSearchHandle {
+    /// Try instantiate a new handle with the given backing JSON
+    pub fn try_new(store: Box<RawJson>) -> Result<Self, JsonError> {
+        let inner = handle::WorkflowSearchInner::try_new(store, |raw| json::from_str(raw.get()))?;
+
+        Ok(Self { inner })
+    }
+
+    /// Access this handle's data
+    pub fn data(&self) -> &WorkflowSearch<'_> {
+        self.inner.borrow_handle()
+    }
+
+    /// Consume the handle returning the backing
+    /// storage
+    pub fn into_inner(self) -> Box<RawJson> {
+        self.inner.into_heads().store
+    }
+}
+
+impl TryFrom<Box<RawJson>> for WorkflowSearchHandle {
+    type Error = JsonError;
+
+    fn try_from(value: Box<RawJson>) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+// Delegate the serializer to the internal handle
+impl Serialize for WorkflowSearchHandle {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.data().serialize(serializer)
+    }
+}
+
+/// A page of workflows matching a search
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkflowSearch<'a> {
+    #[serde(rename = "maxResults")]
+    pub max_results: u64,
+    #[serde(rename = "startAt")]
+    pub start_at: u64,
+    pub total: u64,
+    #[serde(rename = "isLast", default)]
+    pub is_last: bool,
+    #[serde(borrow)]
+    pub values: Vec<Workflow<'a>>,
+}
+
+/// A workflow, i.e the statuses an issue moves through and the
+/// transitions between them.
+///
+/// Transitions, statuses and their rules and properties are only
+/// present when requested via the search's expands
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Workflow<'a> {
+    #[serde(borrow)]
+    pub id: WorkflowId<'a>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<Cow<'a, str>>,
+    #[serde(default, borrow, skip_serializing_if = "Vec::is_empty")]
+    pub transitions: Vec<Transition<'a>>,
+    #[serde(default, borrow, skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<WorkflowStatus<'a>>,
+    #[serde(rename = "isDefault", default, skip_serializing_if = "Option::is_none")]
+    pub is_default: Option<bool>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub created: Option<Cow<'a, str>>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub updated: Option<Cow<'a, str>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkflowId<'a> {
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub name: Cow<'a, str>,
+    /// Only present for workflows created on Jira Cloud
+    #[serde(
+        rename = "entityId",
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub entity_id: Option<Cow<'a, str>>,
+}
+
+/// A transition between statuses
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Transition<'a> {
+    #[serde(with = "id")]
+    pub id: u64,
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub name: Cow<'a, str>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "cow::deserialize_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<Cow<'a, str>>,
+    /// The statuses the transition is available from, empty for
+    /// initial and global transitions
+    #[serde(
+        default,
+        deserialize_with = "id::deserialize_vec",
+        serialize_with = "id::serialize_vec"
+    )]
+    pub from: Vec<u64>,
+    /// The status the transition moves issues to
+    #[serde(with = "id")]
+    pub to: u64,
+    #[serde(rename = "type")]
+    pub transition_type: TransitionType,
+    /// The screen shown during the transition, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub screen: Option<TransitionScreen>,
+    /// The transition's conditions, validators and post functions, kept
+    /// as raw JSON as their shape varies between rule types
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<&'a RawJson>,
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<&'a RawJson>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransitionType {
+    /// Creates the issue, from no status
+    Initial,
+    /// Available from every status
+    Global,
+    /// Available from the statuses in `from`
+    Directed,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct TransitionScreen {
+    #[serde(with = "id")]
+    pub id: u64,
+}
+
+/// A status as used in a workflow
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WorkflowStatus<'a> {
+    #[serde(with = "id")]
+    pub id: u64,
+    #[serde(borrow, deserialize_with = "cow::deserialize")]
+    pub name: Cow<'a, str>,
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<&'a RawJson>,
+}
+
+impl<'a> Workflow<'a> {
+    /// Find one of the workflow's statuses by id
+    pub fn status(&self, id: u64) -> Option<&WorkflowStatus<'a>> {
+        self.statuses.iter().find(|status| status.id == id)
+    }
+}
+
+mod handle {
+    use super::*;
+    use ouroboros::self_referencing as ouroboros;
+
+    #[ouroboros(pub_extras)]
+    #[derive(Debug)]
+    pub(super) struct WorkflowSearchInner {
+        store: Box<RawJson>,
+        #[borrows(store)]
+        pub(super) handle: WorkflowSearch<'this>,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod types {
+    use serde_json::{json, Value as Json};
+
+    pub fn workflow() -> Json {
+        json!({
+            "id": { "name": "Software Simplified Workflow", "entityId": "5ed312c5" },
+            "description": "Generated by Jira Software",
+            "transitions": [
+                { "id": "1", "name": "Create", "description": "", "from": [], "to": "1", "type": "initial" },
+                { "id": "11", "name": "Start", "description": "", "from": ["1"], "to": "3", "type": "directed",
+                  "screen": { "id": "10000" },
+                  "rules": { "conditionsTree": { "nodeType": "simple", "type": "PermissionCondition" }, "validators": [], "postFunctions": [] } },
+                { "id": "21", "name": "Done", "description": "", "from": [], "to": "10001", "type": "global" }
+            ],
+            "statuses": [
+                { "id": "1", "name": "Open", "properties": {} },
+                { "id": "3", "name": "In Progress" },
+                { "id": "10001", "name": "Done" }
+            ],
+            "isDefault": false
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_workflow_search_handle() {
+        let page = serde_json::json!({
+            "maxResults": 50,
+            "startAt": 0,
+            "total": 1,
+            "isLast": true,
+            "values": [types::workflow()]
+        });
+        let json = json::to_vec(&page).unwrap();
+
+        let search: WorkflowSearchHandle = json::from_slice(&json).expect("a valid page");
+        let workflow = &search.data().values[0];
+
+        assert_eq!(workflow.id.name, "Software Simplified Workflow");
+        assert_eq!(workflow.transitions[1].from, [1]);
+        assert_eq!(
+            workflow.transitions[2].transition_type,
+            TransitionType::Global
+        );
+        assert!(workflow.transitions[1].rules.is_some());
+        assert_eq!(
+            workflow.status(3).map(|s| s.name.as_ref()),
+            Some("In Progress")
+        );
+    }
+
+    #[test]
+    fn serialize_transition_ids() {
+        let json = json::to_vec(&types::workflow()).unwrap();
+        let workflow: Workflow = json::from_slice(&json).unwrap();
+
+        let transition = json::to_value(&workflow.transitions[1]).unwrap();
+
+        assert_eq!(transition["from"], serde_json::json!(["1"]));
+        assert_eq!(transition["to"], "3");
+    }
+}
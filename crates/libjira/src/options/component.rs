@@ -0,0 +1,33 @@
+use super::*;
+
+/// Options for deleting a component
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Delete {
+    #[serde(rename = "moveIssuesTo")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    move_issues_to: Option<u64>,
+}
+
+impl Delete {
+    /// Instantiate a new, empty options set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move the deleted component's issues to this component, by
+    /// default they are left without it
+    pub fn move_issues_to(&mut self, component: impl Into<Option<u64>>) -> &mut Self {
+        self.move_issues_to = component.into();
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
@@ -0,0 +1,287 @@
+use super::*;
+
+/// Options for retrieving, creating or editing a single filter
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Get {
+    #[serde(skip_serializing_if = "none_or_empty")]
+    expand: Option<CommaDelimited>,
+}
+
+/// Options for searching the filters visible to the user
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Search {
+    #[serde(rename = "filterName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(rename = "accountId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner: Option<String>,
+    #[serde(rename = "groupname")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<String>,
+    #[serde(rename = "projectId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project_id: Option<u64>,
+    #[serde(rename = "orderBy")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    order_by: Option<String>,
+    #[serde(rename = "startAt")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_at: Option<u32>,
+    #[serde(rename = "maxResults")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_results: Option<u32>,
+    #[serde(skip_serializing_if = "none_or_empty")]
+    expand: Option<CommaDelimited>,
+}
+
+/// Options for listing the filters owned by the user
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Mine {
+    #[serde(rename = "includeFavourites")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    include_favourites: Option<bool>,
+    #[serde(skip_serializing_if = "none_or_empty")]
+    expand: Option<CommaDelimited>,
+}
+
+impl Get {
+    /// Instantiate a new, empty options set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The Jira expandable for this endpoint, see `self::expands`
+    /// for the available expands
+    pub fn expand<I, T>(&mut self, expand: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        append_delimited(
+            &mut self.expand,
+            expand.into_iter().map(|s| Element::from(s.as_ref())),
+        );
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
+
+impl Search {
+    /// Instantiate a new, empty options set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only return filters whose name contains this, case insensitively
+    pub fn name<T>(&mut self, name: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.name = name.into().map(|s| s.to_string());
+        self
+    }
+
+    /// Only return filters owned by the user with this account id
+    pub fn owner<T>(&mut self, account_id: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.owner = account_id.into().map(|s| s.to_string());
+        self
+    }
+
+    /// Only return filters shared with this group
+    pub fn group<T>(&mut self, group: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.group = group.into().map(|s| s.to_string());
+        self
+    }
+
+    /// Only return filters shared with this project
+    pub fn project_id(&mut self, project_id: impl Into<Option<u64>>) -> &mut Self {
+        self.project_id = project_id.into();
+        self
+    }
+
+    /// Sort the filters by one of `id`, `name`, `description`, `owner`,
+    /// `favourite_count` or `is_favourite`. Prefix with `-` to reverse
+    /// the order
+    pub fn order_by<T>(&mut self, order_by: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.order_by = order_by.into().map(|s| s.to_string());
+        self
+    }
+
+    /// Only send results starting from the given number
+    pub fn start_at(&mut self, start_at: impl Into<Option<u32>>) -> &mut Self {
+        self.start_at = start_at.into();
+        self
+    }
+
+    /// Sets the limit on number of filters returned in a single request,
+    /// by default Jira returns 50
+    pub fn max_results(&mut self, max_results: impl Into<Option<u32>>) -> &mut Self {
+        self.max_results = max_results.into().filter(|u| *u != 0);
+        self
+    }
+
+    /// The Jira expandable for this endpoint, see `self::expands`
+    /// for the available expands
+    pub fn expand<I, T>(&mut self, expand: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        append_delimited(
+            &mut self.expand,
+            expand.into_iter().map(|s| Element::from(s.as_ref())),
+        );
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
+
+impl Mine {
+    /// Instantiate a new, empty options set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also return the user's favourite filters, even those owned
+    /// by other users
+    pub fn include_favourites(&mut self, include: impl Into<Option<bool>>) -> &mut Self {
+        self.include_favourites = include.into().filter(|b| *b);
+        self
+    }
+
+    /// The Jira expandable for this endpoint, see `self::expands`
+    /// for the available expands
+    pub fn expand<I, T>(&mut self, expand: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        append_delimited(
+            &mut self.expand,
+            expand.into_iter().map(|s| Element::from(s.as_ref())),
+        );
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
+
+fn append_delimited<I, T>(f: &mut Option<CommaDelimited>, iter: I)
+where
+    I: Iterator<Item = T>,
+    T: Into<Element>,
+{
+    match f {
+        Some(ref mut item) => item.extend(iter.map(Into::into)),
+        None => {
+            *f = iter.map(Into::into).fold(None, |mut o, elem| {
+                o.get_or_insert_with(CommaDelimited::new).append(elem);
+                o
+            })
+        }
+    }
+}
+
+pub mod expands {
+    /// Returns the users the filter is shared with
+    pub const SHARED_USERS: &str = "sharedUsers";
+
+    /// Returns the users subscribed to the filter
+    pub const SUBSCRIPTIONS: &str = "subscriptions";
+
+    /// Returns the filter's description (filter searches only)
+    pub const DESCRIPTION: &str = "description";
+
+    /// Returns the filter's owner (filter searches only)
+    pub const OWNER: &str = "owner";
+
+    /// Returns the filter's JQL (filter searches only)
+    pub const JQL: &str = "jql";
+
+    /// Returns the filter's share permissions (filter searches only)
+    pub const SHARE_PERMISSIONS: &str = "sharePermissions";
+
+    /// Returns whether the filter is a favourite (filter searches only)
+    pub const FAVOURITE: &str = "favourite";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn empty() {
+        let req = generate(Search::new());
+
+        assert_eq!(req.url().query(), None);
+    }
+
+    #[test]
+    fn search() {
+        let search = Search::new().with(|this| {
+            this.name("bugs")
+                .project_id(10000)
+                .order_by("-favourite_count")
+                .max_results(0)
+                .expand([expands::JQL, expands::OWNER])
+        });
+        let req = generate(&search);
+
+        assert_eq!(
+            req.url().query(),
+            Some("filterName=bugs&projectId=10000&orderBy=-favourite_count&expand=jql%2Cowner")
+        );
+    }
+
+    #[test]
+    fn mine() {
+        let req = generate(Mine::new().with(|this| this.include_favourites(true)));
+
+        assert_eq!(req.url().query(), Some("includeFavourites=true"));
+    }
+
+    fn generate(s: impl Serialize) -> reqwest::Request {
+        reqwest::Client::new()
+            .get("http://localhost")
+            .query(&s)
+            .build()
+            .expect("a valid request")
+    }
+}
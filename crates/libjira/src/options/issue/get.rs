@@ -85,7 +85,7 @@ impl Get {
             Some(ref mut item) => item.extend(iter.map(Into::into)),
             None => {
                 *f = iter.map(Into::into).fold(None, |mut o, elem| {
-                    o.get_or_insert_with(|| CommaDelimited::new()).append(elem);
+                    o.get_or_insert_with(CommaDelimited::new).append(elem);
                     o
                 })
             }
@@ -128,7 +128,7 @@ mod tests {
     #[test]
     fn complex() {
         let get = Get::new().with(|this| {
-            this.properties(&["foo", "bar", "baz"])
+            this.properties(["foo", "bar", "baz"])
                 .fields_by_key(true)
                 .fields(Some("field"))
                 .update_history(false)
@@ -131,7 +131,7 @@ impl MetaCreate {
             Some(ref mut item) => item.extend(iter.map(Into::into)),
             None => {
                 *f = iter.map(Into::into).fold(None, |mut o, elem| {
-                    o.get_or_insert_with(|| CommaDelimited::new()).append(elem);
+                    o.get_or_insert_with(CommaDelimited::new).append(elem);
                     o
                 })
             }
@@ -175,8 +175,8 @@ mod tests {
     #[test]
     fn complex() {
         let get = MetaCreate::new().with(|this| {
-            this.project_keys(&["key1", "key2"])
-                .issuetype_ids((&[0u32, 10, 30]).iter().copied())
+            this.project_keys(["key1", "key2"])
+                .issuetype_ids([0u32, 10, 30].iter().copied())
                 .expand(Some("value"))
         });
         let req = generate(&get);
@@ -15,8 +15,9 @@ pub use {create::*, get::*, metadata::*, search::*};
 /// - Warn: Any errors are returned as warnings, and the request
 ///   may "succeed" -- typically by returning nothing
 /// - None: No validation is done
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum ValidateQuery {
+    #[default]
     Strict,
     Warn,
     None,
@@ -33,12 +34,6 @@ impl ValidateQuery {
     }
 }
 
-impl Default for ValidateQuery {
-    fn default() -> Self {
-        Self::Strict
-    }
-}
-
 impl Serialize for ValidateQuery {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -141,7 +141,7 @@ impl Search {
             Some(ref mut item) => item.extend(iter.map(Into::into)),
             None => {
                 *f = iter.map(Into::into).fold(None, |mut o, elem| {
-                    o.get_or_insert_with(|| CommaDelimited::new()).append(elem);
+                    o.get_or_insert_with(CommaDelimited::new).append(elem);
                     o
                 })
             }
@@ -213,7 +213,7 @@ mod tests {
         let search = Search::new().with(|this| {
             this.start_at(80)
                 .validate(ValidateQuery::Strict)
-                .properties(&["foo", "bar"])
+                .properties(["foo", "bar"])
         });
         let req = generate(&search);
         let query = req.url().query().expect("a non-empty query");
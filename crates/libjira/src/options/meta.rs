@@ -0,0 +1,41 @@
+use super::*;
+
+/// Options for paging through the labels in use
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Labels {
+    #[serde(rename = "startAt")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_at: Option<u32>,
+    #[serde(rename = "maxResults")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_results: Option<u32>,
+}
+
+impl Labels {
+    /// Instantiate a new, empty options set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return labels starting from this index
+    pub fn start_at(&mut self, start: impl Into<Option<u32>>) -> &mut Self {
+        self.start_at = start.into();
+        self
+    }
+
+    /// The maximum number of labels to return, Jira caps this at 1000
+    pub fn max_results(&mut self, max: impl Into<Option<u32>>) -> &mut Self {
+        self.max_results = max.into();
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
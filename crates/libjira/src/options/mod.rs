@@ -5,7 +5,14 @@ use {
 };
 
 mod cdelim;
+pub mod component;
+pub mod filter;
 pub mod issue;
+pub mod meta;
+pub mod screen;
+pub mod version;
+pub mod webhook;
+pub mod workflow;
 
 mod key {
     pub(super) const JQL: &str = "jql";
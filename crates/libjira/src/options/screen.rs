@@ -0,0 +1,53 @@
+use super::*;
+
+/// Options for listing screens
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct List {
+    #[serde(rename = "queryString")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<String>,
+    #[serde(rename = "startAt")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_at: Option<u32>,
+    #[serde(rename = "maxResults")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_results: Option<u32>,
+}
+
+impl List {
+    /// Instantiate a new, empty options set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only return screens whose name contains this, ignoring case
+    pub fn query<T>(&mut self, query: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.query = query.into().map(|s| s.to_string());
+        self
+    }
+
+    /// Return screens starting from this index
+    pub fn start_at(&mut self, start: impl Into<Option<u32>>) -> &mut Self {
+        self.start_at = start.into();
+        self
+    }
+
+    /// The maximum number of screens to return
+    pub fn max_results(&mut self, max: impl Into<Option<u32>>) -> &mut Self {
+        self.max_results = max.into();
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
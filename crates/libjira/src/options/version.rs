@@ -0,0 +1,43 @@
+use super::*;
+
+/// Options for deleting a version
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Delete {
+    #[serde(rename = "moveFixIssuesTo")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    move_fixed_to: Option<u64>,
+    #[serde(rename = "moveAffectedIssuesTo")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    move_affected_to: Option<u64>,
+}
+
+impl Delete {
+    /// Instantiate a new, empty options set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move the issues fixed in the deleted version to this version, by
+    /// default they are left without it
+    pub fn move_fixed_to(&mut self, version: impl Into<Option<u64>>) -> &mut Self {
+        self.move_fixed_to = version.into();
+        self
+    }
+
+    /// Move the issues affected by the deleted version to this version,
+    /// by default they are left without it
+    pub fn move_affected_to(&mut self, version: impl Into<Option<u64>>) -> &mut Self {
+        self.move_affected_to = version.into();
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
@@ -0,0 +1,156 @@
+use super::*;
+
+/// Options for searching workflows
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Search {
+    #[serde(rename = "workflowName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(rename = "queryString")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<String>,
+    #[serde(rename = "isActive")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    active: Option<bool>,
+    #[serde(rename = "startAt")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_at: Option<u32>,
+    #[serde(rename = "maxResults")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_results: Option<u32>,
+    #[serde(skip_serializing_if = "none_or_empty")]
+    expand: Option<CommaDelimited>,
+}
+
+impl Search {
+    /// Instantiate a new, empty options set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only return the workflow with exactly this name
+    pub fn name<T>(&mut self, name: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.name = name.into().map(|s| s.to_string());
+        self
+    }
+
+    /// Only return workflows whose name contains this, ignoring case
+    pub fn query<T>(&mut self, query: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.query = query.into().map(|s| s.to_string());
+        self
+    }
+
+    /// Only return workflows which are, or are not, used by a project
+    pub fn active(&mut self, active: impl Into<Option<bool>>) -> &mut Self {
+        self.active = active.into();
+        self
+    }
+
+    /// Return workflows starting from this index
+    pub fn start_at(&mut self, start: impl Into<Option<u32>>) -> &mut Self {
+        self.start_at = start.into();
+        self
+    }
+
+    /// The maximum number of workflows to return
+    pub fn max_results(&mut self, max: impl Into<Option<u32>>) -> &mut Self {
+        self.max_results = max.into();
+        self
+    }
+
+    /// The Jira expandable for this endpoint, see `self::expands`
+    /// for the available expands
+    pub fn expand<I, T>(&mut self, expand: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        append_delimited(
+            &mut self.expand,
+            expand.into_iter().map(|s| Element::from(s.as_ref())),
+        );
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
+
+fn append_delimited<I, T>(f: &mut Option<CommaDelimited>, iter: I)
+where
+    I: Iterator<Item = T>,
+    T: Into<Element>,
+{
+    match f {
+        Some(ref mut item) => item.extend(iter.map(Into::into)),
+        None => {
+            *f = iter.map(Into::into).fold(None, |mut o, elem| {
+                o.get_or_insert_with(CommaDelimited::new).append(elem);
+                o
+            })
+        }
+    }
+}
+
+pub mod expands {
+    /// Returns the workflow's transitions
+    pub const TRANSITIONS: &str = "transitions";
+
+    /// Returns the conditions, validators and post functions of each
+    /// transition
+    pub const TRANSITION_RULES: &str = "transitions.rules";
+
+    /// Returns the properties of each transition
+    pub const TRANSITION_PROPERTIES: &str = "transitions.properties";
+
+    /// Returns the workflow's statuses
+    pub const STATUSES: &str = "statuses";
+
+    /// Returns the properties of each status
+    pub const STATUS_PROPERTIES: &str = "statuses.properties";
+
+    /// Returns whether the workflow is the default workflow
+    pub const DEFAULT: &str = "default";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn search() {
+        let search = Search::new().with(|this| {
+            this.name("Software Simplified Workflow")
+                .expand([expands::TRANSITIONS, expands::TRANSITION_RULES])
+                .expand(Some(expands::STATUSES))
+        });
+        let req = generate(&search);
+
+        assert_eq!(
+            req.url().query(),
+            Some("workflowName=Software+Simplified+Workflow&expand=transitions%2Ctransitions.rules%2Cstatuses")
+        );
+    }
+
+    fn generate(s: impl Serialize) -> reqwest::Request {
+        reqwest::Client::new()
+            .get("http://localhost")
+            .query(&s)
+            .build()
+            .expect("a valid request")
+    }
+}
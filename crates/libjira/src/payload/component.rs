@@ -0,0 +1,125 @@
+use super::*;
+
+/// The body of a component create or edit request. Unset fields are
+/// left unchanged when editing.
+///
+/// ```ignore
+/// let details = ComponentDetails::new().with(|this| {
+///     this.name("Backend")
+///         .project("FOO")
+///         .assignee_type(AssigneeType::ComponentLead)
+/// });
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lead_account_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lead_user_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    assignee_type: Option<AssigneeType>,
+}
+
+/// Who issues in a component are assigned to by default
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AssigneeType {
+    ProjectDefault,
+    ComponentLead,
+    ProjectLead,
+    Unassigned,
+}
+
+impl ComponentDetails {
+    /// Instantiate a new, empty payload
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The component's name, required when creating a component
+    pub fn name<T>(&mut self, name: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.name = name.into().map(|s| s.to_string());
+        self
+    }
+
+    pub fn description<T>(&mut self, description: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.description = description.into().map(|s| s.to_string());
+        self
+    }
+
+    /// The key of the component's project, required when creating
+    /// a component
+    pub fn project<T>(&mut self, key: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.project = key.into().map(|s| s.to_string());
+        self
+    }
+
+    /// The component's lead, by account id (Jira Cloud)
+    pub fn lead_account_id<T>(&mut self, account_id: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.lead_account_id = account_id.into().map(|s| s.to_string());
+        self
+    }
+
+    /// The component's lead, by user name (Jira Server)
+    pub fn lead_user_name<T>(&mut self, name: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.lead_user_name = name.into().map(|s| s.to_string());
+        self
+    }
+
+    pub fn assignee_type(&mut self, assignee: impl Into<Option<AssigneeType>>) -> &mut Self {
+        self.assignee_type = assignee.into();
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {pretty_assertions::assert_eq, serde_json as json};
+
+    #[test]
+    fn serialize_details() {
+        let details = ComponentDetails::new().with(|this| {
+            this.name("Backend")
+                .project("FOO")
+                .assignee_type(AssigneeType::ComponentLead)
+        });
+
+        assert_eq!(
+            json::to_value(&details).unwrap(),
+            json!({ "name": "Backend", "project": "FOO", "assigneeType": "COMPONENT_LEAD" })
+        );
+    }
+}
@@ -0,0 +1,375 @@
+pub use crate::error::ShareError;
+
+use {
+    super::*,
+    crate::models::filter::{SharePermission, ShareType},
+    serde::{ser::SerializeMap, Deserialize, Serializer},
+    std::{fmt, str::FromStr},
+};
+
+/// The body of a filter create or edit request.
+///
+/// ```ignore
+/// let details = FilterDetails::new("Open bugs").with(|this| {
+///     this.jql("type = Bug AND resolution IS EMPTY")
+///         .description("Every unresolved bug")
+///         .share_permissions([Share::Group("jira-developers".into())])
+/// });
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilterDetails {
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    jql: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    favourite: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    share_permissions: Option<Vec<Share>>,
+}
+
+/// Who a filter is shared with.
+///
+/// Parses from (and displays as) `global`, `loggedin`, `group:<name>`,
+/// `project:<id>`, `project-role:<project id>:<role id>` or
+/// `user:<account id>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Share {
+    /// Everyone, including anonymous users
+    Global,
+    /// Any logged in user
+    LoggedIn,
+    Group(String),
+    /// Anyone who can browse the project, by id
+    Project(u64),
+    /// The members of a role in a project, by id
+    ProjectRole {
+        project: u64,
+        role: u64,
+    },
+    /// A single user, by account id
+    User(String),
+}
+
+impl FilterDetails {
+    /// Instantiate a new payload for the filter with the given name
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.name = name.into();
+        self
+    }
+
+    /// The filter's query, required when creating a filter
+    pub fn jql<T>(&mut self, jql: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.jql = jql.into().map(|s| s.to_string());
+        self
+    }
+
+    pub fn description<T>(&mut self, description: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.description = description.into().map(|s| s.to_string());
+        self
+    }
+
+    /// Add or remove the filter from the user's favourites
+    pub fn favourite(&mut self, favourite: impl Into<Option<bool>>) -> &mut Self {
+        self.favourite = favourite.into();
+        self
+    }
+
+    /// Who the filter is shared with. When editing a filter this
+    /// replaces its existing share permissions, an empty list making
+    /// the filter private. If never called they are left unchanged
+    pub fn share_permissions<I>(&mut self, shares: I) -> &mut Self
+    where
+        I: IntoIterator<Item = Share>,
+    {
+        self.share_permissions = Some(shares.into_iter().collect());
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
+
+impl Share {
+    /// The share an existing permission grants, if it can be represented,
+    /// e.g not for permissions on projects the user can't see
+    pub fn from_permission(permission: &SharePermission<'_>) -> Option<Self> {
+        let project = || permission.project.as_ref().map(|p| p.id);
+
+        match permission.share_type {
+            ShareType::Global => Some(Self::Global),
+            ShareType::LoggedIn => Some(Self::LoggedIn),
+            ShareType::Group => permission
+                .group
+                .as_ref()
+                .map(|g| Self::Group(g.name.to_string())),
+            ShareType::Project => project().map(Self::Project),
+            ShareType::ProjectRole => {
+                let role = permission.role.as_ref()?.id;
+
+                project().map(|project| Self::ProjectRole { project, role })
+            }
+            ShareType::User => permission
+                .user
+                .as_ref()
+                .and_then(|u| u.account_id.as_ref())
+                .map(|id| Self::User(id.to_string())),
+            ShareType::ProjectUnknown => None,
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            Self::Global => "global",
+            Self::LoggedIn => "loggedin",
+            Self::Group(_) => "group",
+            Self::Project(_) => "project",
+            Self::ProjectRole { .. } => "projectRole",
+            Self::User(_) => "user",
+        }
+    }
+
+    /// The body of a request adding this permission to an existing
+    /// filter, which is flatter than the permissions of a filter
+    pub(crate) fn as_permission_body(&self) -> impl Serialize + '_ {
+        PermissionBody(self)
+    }
+}
+
+// Serializes as the share permissions of a filter create or edit request
+impl Serialize for Share {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", self.type_name())?;
+
+        match self {
+            Self::Global | Self::LoggedIn => {}
+            Self::Group(name) => map.serialize_entry("group", &json!({ "name": name }))?,
+            Self::Project(id) => {
+                map.serialize_entry("project", &json!({ "id": id.to_string() }))?
+            }
+            Self::ProjectRole { project, role } => {
+                map.serialize_entry("project", &json!({ "id": project.to_string() }))?;
+                map.serialize_entry("role", &json!({ "id": role }))?;
+            }
+            Self::User(account_id) => {
+                map.serialize_entry("user", &json!({ "accountId": account_id }))?
+            }
+        }
+
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Share {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl FromStr for Share {
+    type Err = ShareError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ShareError(s.to_string());
+        let (kind, value) = match s.split_once(':') {
+            Some((kind, value)) => (kind, Some(value).filter(|v| !v.is_empty())),
+            None => (s, None),
+        };
+        let id = |value: &str| value.parse::<u64>().map_err(|_| error());
+
+        match (kind.to_ascii_lowercase().as_str(), value) {
+            ("global", None) => Ok(Self::Global),
+            ("loggedin", None) | ("authenticated", None) => Ok(Self::LoggedIn),
+            ("group", Some(name)) => Ok(Self::Group(name.to_string())),
+            ("project", Some(project)) => Ok(Self::Project(id(project)?)),
+            ("project-role", Some(ids)) | ("projectrole", Some(ids)) => {
+                let (project, role) = ids.split_once(':').ok_or_else(error)?;
+
+                Ok(Self::ProjectRole {
+                    project: id(project)?,
+                    role: id(role)?,
+                })
+            }
+            ("user", Some(account_id)) => Ok(Self::User(account_id.to_string())),
+            _ => Err(error()),
+        }
+    }
+}
+
+impl fmt::Display for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Global => f.write_str("global"),
+            Self::LoggedIn => f.write_str("loggedin"),
+            Self::Group(name) => write!(f, "group:{}", name),
+            Self::Project(id) => write!(f, "project:{}", id),
+            Self::ProjectRole { project, role } => write!(f, "project-role:{}:{}", project, role),
+            Self::User(account_id) => write!(f, "user:{}", account_id),
+        }
+    }
+}
+
+struct PermissionBody<'a>(&'a Share);
+
+impl Serialize for PermissionBody<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+
+        match self.0 {
+            // This endpoint predates the 'loggedin' type
+            Share::LoggedIn => map.serialize_entry("type", "authenticated")?,
+            share => map.serialize_entry("type", share.type_name())?,
+        }
+
+        match self.0 {
+            Share::Global | Share::LoggedIn => {}
+            Share::Group(name) => map.serialize_entry("groupname", name)?,
+            Share::Project(id) => map.serialize_entry("projectId", &id.to_string())?,
+            Share::ProjectRole { project, role } => {
+                map.serialize_entry("projectId", &project.to_string())?;
+                map.serialize_entry("projectRoleId", &role.to_string())?;
+            }
+            Share::User(account_id) => map.serialize_entry("accountId", account_id)?,
+        }
+
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {pretty_assertions::assert_eq, serde_json as json};
+
+    #[test]
+    fn parse_shares() {
+        let cases = [
+            ("global", Share::Global),
+            ("loggedin", Share::LoggedIn),
+            (
+                "group:jira-developers",
+                Share::Group("jira-developers".into()),
+            ),
+            ("project:10000", Share::Project(10000)),
+            (
+                "project-role:10000:10360",
+                Share::ProjectRole {
+                    project: 10000,
+                    role: 10360,
+                },
+            ),
+            (
+                "user:5b10a2844c20165700ede21g",
+                Share::User("5b10a2844c20165700ede21g".into()),
+            ),
+        ];
+
+        for (input, share) in cases.iter() {
+            assert_eq!(input.parse::<Share>().as_ref(), Ok(share));
+            assert_eq!(share.to_string(), *input);
+        }
+
+        for invalid in ["", "group", "project:FOO", "project-role:1", "everyone"].iter() {
+            assert!(invalid.parse::<Share>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn shares_from_permissions() {
+        use crate::models::filter::permission::types;
+
+        let permissions = json!([
+            types::global(),
+            types::group(),
+            types::project_role(),
+            { "id": 3, "type": "project-unknown" }
+        ])
+        .to_string();
+        let permissions: Vec<SharePermission> = json::from_str(&permissions).unwrap();
+        let shares: Vec<_> = permissions.iter().map(Share::from_permission).collect();
+
+        assert_eq!(
+            shares,
+            vec![
+                Some(Share::Global),
+                Some(Share::Group("jira-developers".into())),
+                Some(Share::ProjectRole {
+                    project: 10100,
+                    role: 10360
+                }),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_details() {
+        let details = FilterDetails::new("Open bugs").with(|this| {
+            this.jql("type = Bug")
+                .share_permissions([Share::Project(10000), Share::LoggedIn])
+        });
+
+        assert_eq!(
+            json::to_value(&details).unwrap(),
+            json!({
+                "name": "Open bugs",
+                "jql": "type = Bug",
+                "sharePermissions": [
+                    { "type": "project", "project": { "id": "10000" } },
+                    { "type": "loggedin" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_permission_body() {
+        let role = Share::ProjectRole {
+            project: 10000,
+            role: 10360,
+        };
+
+        assert_eq!(
+            json::to_value(role.as_permission_body()).unwrap(),
+            json!({ "type": "projectRole", "projectId": "10000", "projectRoleId": "10360" })
+        );
+        assert_eq!(
+            json::to_value(Share::LoggedIn.as_permission_body()).unwrap(),
+            json!({ "type": "authenticated" })
+        );
+    }
+}
@@ -0,0 +1,10 @@
+mod schema;
+mod update;
+mod validate;
+
+use super::*;
+
+pub use {schema::*, update::*, validate::*};
+
+#[cfg(feature = "derive")]
+pub use jira_rs_derive::JiraIssue;
@@ -0,0 +1,328 @@
+pub use crate::error::SchemaError;
+
+use {
+    super::*,
+    crate::models::issue::{Issue, IssueFieldsMeta, IssueHandle},
+    std::{collections::HashMap, iter::FromIterator},
+};
+
+/// A user defined view of a Jira issue.
+///
+/// Implementors map their own fields to and from the fields of a Jira issue,
+/// allowing them to both read an issue and produce create or edit payloads
+/// for it. Typically this is implemented via `#[derive(JiraIssue)]` (requires
+/// the `derive` feature), rather than by hand:
+///
+/// ```ignore
+/// #[derive(JiraIssue)]
+/// struct Story {
+///     #[jira(key)]
+///     key: String,
+///     summary: String,
+///     #[jira(name = "Story Points")]
+///     points: Option<f64>,
+///     #[jira(field = "labels", update = "add")]
+///     new_labels: Vec<String>,
+///     #[jira(read_only)]
+///     status: serde_json::Value,
+/// }
+/// ```
+///
+/// Fields may be annotated with:
+/// - `field = "<id>"`: the Jira field id, defaults to the Rust field's name
+/// - `name = "<name>"`: the Jira field's display name, resolved via `FieldNames`
+/// - `key` / `id`: the issue's key or id, never sent in a payload
+/// - `update = "<op>"`: send the value as an `update` operation (`set`, `add`,
+///   `remove`, `edit`) rather than as a `fields` entry. Arrays sent with `add`
+///   or `remove` are split into one operation per element
+/// - `read_only`: only read from issues, never sent in a payload
+/// - `skip`: ignored entirely, the field is filled with its `Default`
+/// - `default`: use the field's `Default` if the issue is missing the field
+///
+/// Fields that serialize to `null` (e.g `None`) are sent as `null`, clearing
+/// the field in Jira, unless sent with `add`, `remove` or `edit`.
+pub trait JiraIssue: Sized {
+    /// Build this type from the given issue, resolving any named fields
+    /// with the given names
+    fn from_issue_with(issue: &Issue<'_>, names: &FieldNames) -> Result<Self, SchemaError>;
+
+    /// Build a create or edit payload from this type, resolving any named
+    /// fields with the given names
+    fn to_payload(&self, names: &FieldNames) -> Result<IssueUpdate, SchemaError>;
+
+    /// Build this type from the given issue, resolving any named fields via
+    /// the issue's `names` expand, if it was requested
+    fn from_issue(issue: &Issue<'_>) -> Result<Self, SchemaError> {
+        Self::from_issue_with(issue, &FieldNames::from_issue(issue))
+    }
+}
+
+impl IssueHandle {
+    /// Parse this handle into a user defined issue schema
+    pub fn parse<T>(&self) -> Result<T, SchemaError>
+    where
+        T: JiraIssue,
+    {
+        T::from_issue(self.data())
+    }
+}
+
+/// A mapping of Jira field names (e.g "Story Points") to
+/// their ids (e.g "customfield_10016").
+///
+/// This is typically built from an issue requested with the `names`
+/// expand, or from the create / edit metadata of an issue.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FieldNames {
+    by_name: HashMap<String, String>,
+}
+
+impl FieldNames {
+    const NAMES: &'static str = "names";
+
+    /// Instantiate a new, empty mapping
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a mapping from an issue's `names` expand, this mapping
+    /// will be empty if the expand was not requested
+    pub fn from_issue(issue: &Issue<'_>) -> Self {
+        issue
+            .access::<HashMap<String, String>>(Self::NAMES)
+            .and_then(Result::ok)
+            .map(|names| names.into_iter().map(|(id, name)| (name, id)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Build a mapping from a set of field metadata, e.g `MetaEdit::fields`
+    pub fn from_meta<'m, 'a: 'm, I>(fields: I) -> Self
+    where
+        I: IntoIterator<Item = &'m IssueFieldsMeta<'a>>,
+    {
+        fields
+            .into_iter()
+            .map(|meta| (meta.name.to_string(), meta.field_id.to_string()))
+            .collect()
+    }
+
+    /// Add a single name -> id mapping
+    pub fn insert<N, I>(&mut self, name: N, id: I) -> &mut Self
+    where
+        N: Into<String>,
+        I: Into<String>,
+    {
+        self.by_name.insert(name.into(), id.into());
+        self
+    }
+
+    /// Lookup the id of the given field name
+    pub fn id_of(&self, name: &str) -> Option<&str> {
+        self.by_name.get(name).map(String::as_str)
+    }
+}
+
+impl<N, I> FromIterator<(N, I)> for FieldNames
+where
+    N: Into<String>,
+    I: Into<String>,
+{
+    fn from_iter<T: IntoIterator<Item = (N, I)>>(iter: T) -> Self {
+        let by_name = iter
+            .into_iter()
+            .map(|(n, i)| (n.into(), i.into()))
+            .collect();
+
+        Self { by_name }
+    }
+}
+
+/// Support functions for code generated by `#[derive(JiraIssue)]`,
+/// this is not a public API
+#[doc(hidden)]
+pub mod __private {
+    use {
+        super::*,
+        crate::models::issue::Operations,
+        serde::de::DeserializeOwned,
+        serde_json::{self as json, Error as JsonError},
+    };
+
+    pub use crate::models::issue::Operations as Op;
+
+    /// How a derived field refers to its Jira field
+    #[derive(Debug, Clone, Copy)]
+    pub enum FieldRef {
+        Id(&'static str),
+        Name(&'static str),
+    }
+
+    impl FieldRef {
+        fn resolve<'n>(&self, names: &'n FieldNames) -> Result<&'n str, SchemaError> {
+            match *self {
+                Self::Id(id) => Ok(id),
+                Self::Name(name) => names
+                    .id_of(name)
+                    .ok_or_else(|| SchemaError::UnknownName(name.to_string())),
+            }
+        }
+    }
+
+    pub fn from_field<T>(
+        issue: &Issue<'_>,
+        names: &FieldNames,
+        field: FieldRef,
+        default: Option<fn() -> T>,
+    ) -> Result<T, SchemaError>
+    where
+        T: DeserializeOwned,
+    {
+        let id = field.resolve(names)?;
+
+        match (issue.fields.get(id), default) {
+            (Some(raw), _) => json::from_str(raw.get()).map_err(|e| invalid(id, e)),
+            (None, Some(default)) => Ok(default()),
+            // Allow Option<T> and friends to treat a missing field as null
+            (None, None) => {
+                json::from_value(Value::Null).map_err(|_| SchemaError::MissingField(id.into()))
+            }
+        }
+    }
+
+    pub fn from_key<T>(issue: &Issue<'_>) -> Result<T, SchemaError>
+    where
+        T: DeserializeOwned,
+    {
+        json::from_value(Value::from(issue.key.as_ref())).map_err(|e| invalid("key", e))
+    }
+
+    pub fn from_id<T>(issue: &Issue<'_>) -> Result<T, SchemaError>
+    where
+        T: DeserializeOwned,
+    {
+        json::from_value(Value::from(issue.id)).map_err(|e| invalid("id", e))
+    }
+
+    pub fn to_field<T>(
+        payload: &mut IssueUpdate,
+        names: &FieldNames,
+        field: FieldRef,
+        op: Option<Operations>,
+        value: &T,
+    ) -> Result<(), SchemaError>
+    where
+        T: Serialize,
+    {
+        let id = field.resolve(names)?;
+        let value = json::to_value(value).map_err(|e| invalid(id, e))?;
+
+        match (op, value) {
+            // Null clears a field, but there is nothing to add, remove or edit
+            (Some(Operations::Add | Operations::Remove | Operations::Edit), Value::Null) => {}
+            (None, value) => {
+                payload.field(id, value);
+            }
+            (Some(op @ Operations::Add), Value::Array(values))
+            | (Some(op @ Operations::Remove), Value::Array(values)) => {
+                for value in values {
+                    payload.operation(id, op, value);
+                }
+            }
+            (Some(op), value) => {
+                payload.operation(id, op, value);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn invalid(field: &str, source: JsonError) -> SchemaError {
+        SchemaError::InvalidField {
+            field: field.to_string(),
+            source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{__private::*, *};
+    use crate::models::issue::issue::types;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn names_from_issue() {
+        let mut issue = types::issue();
+        issue["names"] = json!({ "customfield_10016": "Story Points" });
+        let json = serde_json::to_vec(&issue).unwrap();
+        let issue: Issue = serde_json::from_slice(&json).unwrap();
+
+        let names = FieldNames::from_issue(&issue);
+
+        assert_eq!(names.id_of("Story Points"), Some("customfield_10016"))
+    }
+
+    #[test]
+    fn names_without_expand() {
+        let json = serde_json::to_vec(&types::issue()).unwrap();
+        let issue: Issue = serde_json::from_slice(&json).unwrap();
+
+        assert_eq!(FieldNames::from_issue(&issue), FieldNames::new())
+    }
+
+    #[test]
+    fn missing_fields() {
+        let json = serde_json::to_vec(&types::issue()).unwrap();
+        let issue: Issue = serde_json::from_slice(&json).unwrap();
+        let names = FieldNames::new();
+
+        let optional: Option<String> =
+            from_field(&issue, &names, FieldRef::Id("nope"), None).unwrap();
+        let required: Result<String, _> = from_field(&issue, &names, FieldRef::Id("nope"), None);
+        let unknown: Result<String, _> = from_field(&issue, &names, FieldRef::Name("Nope"), None);
+
+        assert_eq!(optional, None);
+        assert!(matches!(required, Err(SchemaError::MissingField(_))));
+        assert!(matches!(unknown, Err(SchemaError::UnknownName(_))));
+    }
+
+    #[test]
+    fn split_array_operations() {
+        let mut payload = IssueUpdate::new();
+        let names = FieldNames::new();
+
+        to_field(
+            &mut payload,
+            &names,
+            FieldRef::Id("labels"),
+            Some(Op::Add),
+            &["foo", "bar"],
+        )
+        .unwrap();
+        to_field(
+            &mut payload,
+            &names,
+            FieldRef::Id("labels"),
+            Some(Op::Remove),
+            &None::<u8>,
+        )
+        .unwrap();
+        to_field(
+            &mut payload,
+            &names,
+            FieldRef::Id("cleared"),
+            None,
+            &None::<u8>,
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&payload).unwrap(),
+            json!({
+                "fields": { "cleared": null },
+                "update": { "labels": [{ "add": "foo" }, { "add": "bar" }] },
+            })
+        )
+    }
+}
@@ -0,0 +1,560 @@
+pub use crate::error::UpdateError;
+
+use crate::error::{Problem, ValidationError};
+
+use {
+    super::*,
+    crate::models::issue::{common, Operations},
+    serde::{ser::Error as _, Deserialize, Serializer},
+    std::collections::{BTreeSet, HashMap},
+};
+
+/// The body of an issue create or edit request.
+///
+/// Jira accepts changes to an issue in two forms:
+/// - `fields`: the new value of a field, replacing any existing value
+/// - `update`: a list of operations to apply to a field, e.g adding a label
+///
+/// A field may appear in either form, but not both. This is checked
+/// before the payload is serialized, and can be checked early via
+/// `IssueUpdate::check`.
+///
+/// The same payload is accepted by both `Issues::create` and `Issues::edit`.
+///
+/// ```ignore
+/// let update = IssueUpdate::new().with(|this| {
+///     this.set("summary", "A new summary")
+///         .add("labels", "triaged")
+///         .remove("labels", "needs-triage")
+///         .transition(31)
+/// });
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct IssueUpdate {
+    #[serde(default)]
+    fields: Map<String, Value>,
+    #[serde(default)]
+    update: Map<String, Value>,
+    #[serde(default)]
+    transition: Option<Transition>,
+    #[serde(default)]
+    history_metadata: Option<HistoryMetadata>,
+    #[serde(default)]
+    properties: Vec<EntityProperty>,
+
+    // Any values that failed to serialize, reported by check()
+    #[serde(skip)]
+    invalid: Vec<(String, String)>,
+}
+
+impl IssueUpdate {
+    /// Instantiate a new, empty payload
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the value of the given field, replacing any previous value
+    pub fn set<K, V>(&mut self, field: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Serialize,
+    {
+        let field = field.into();
+
+        match self.serialize_value(&field, value) {
+            Some(value) => self.field(field, value),
+            None => self,
+        }
+    }
+
+    /// Add the given value to a field, e.g a label or component
+    pub fn add<K, V>(&mut self, field: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Serialize,
+    {
+        self.typed_operation(field, Operations::Add, value)
+    }
+
+    /// Remove the given value from a field, e.g a label or component
+    pub fn remove<K, V>(&mut self, field: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Serialize,
+    {
+        self.typed_operation(field, Operations::Remove, value)
+    }
+
+    /// Edit the given field in place, e.g a worklog or time estimate
+    pub fn edit<K, V>(&mut self, field: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Serialize,
+    {
+        self.typed_operation(field, Operations::Edit, value)
+    }
+
+    /// Replace the value of a field via an `update` operation, this is
+    /// equivalent to `set`, except that it may be combined with other
+    /// operations on the same field
+    pub fn update_set<K, V>(&mut self, field: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Serialize,
+    {
+        self.typed_operation(field, Operations::Set, value)
+    }
+
+    /// Set the raw JSON value of the given field, replacing any previous value
+    pub fn field<K>(&mut self, field: K, value: Value) -> &mut Self
+    where
+        K: Into<String>,
+    {
+        self.fields.insert(field.into(), value);
+        self
+    }
+
+    /// Append a raw JSON operation to the given field's `update` list
+    pub fn operation<K>(&mut self, field: K, op: Operations, value: Value) -> &mut Self
+    where
+        K: Into<String>,
+    {
+        let mut operation = Map::new();
+        operation.insert(op.as_str().to_string(), value);
+
+        let ops = self
+            .update
+            .entry(field.into())
+            .or_insert_with(|| Value::Array(Vec::new()));
+
+        match ops {
+            Value::Array(ops) => ops.push(Value::Object(operation)),
+            other => *other = Value::Array(vec![Value::Object(operation)]),
+        }
+
+        self
+    }
+
+    /// Transition the issue to a new status, by transition id, as part of
+    /// this request
+    pub fn transition(&mut self, id: impl Into<Option<u64>>) -> &mut Self {
+        self.transition = id.into().map(|id| Transition { id });
+        self
+    }
+
+    /// Attach metadata about this change to the issue's history
+    pub fn history_metadata(&mut self, metadata: impl Into<Option<HistoryMetadata>>) -> &mut Self {
+        self.history_metadata = metadata.into();
+        self
+    }
+
+    /// Set an entity property on the issue as part of this request
+    pub fn property<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Serialize,
+    {
+        let key = key.into();
+
+        if let Some(value) = self.serialize_value(&key, value) {
+            self.properties.retain(|p| p.key != key);
+            self.properties.push(EntityProperty { key, value });
+        }
+
+        self
+    }
+
+    /// Check this payload for any problems that Jira would reject:
+    /// - values that failed to serialize
+    /// - `update` entries that are not a list of operations
+    /// - fields present in both `fields` and `update`
+    ///
+    /// Every problem found is returned, see `IssueUpdate::problems`
+    pub fn check(&self) -> Result<(), ValidationError> {
+        let problems: Vec<Problem> = self.problems().into_iter().map(Problem::from).collect();
+
+        match problems.is_empty() {
+            true => Ok(()),
+            false => Err(ValidationError { problems }),
+        }
+    }
+
+    /// Every problem `IssueUpdate::check` would report, in the order
+    /// listed there
+    pub fn problems(&self) -> Vec<UpdateError> {
+        let mut problems: Vec<UpdateError> = self
+            .invalid
+            .iter()
+            .map(|(field, reason)| UpdateError::InvalidValue {
+                field: field.clone(),
+                reason: reason.clone(),
+            })
+            .collect();
+
+        problems.extend(
+            self.update
+                .iter()
+                .filter(|(_, ops)| !is_operations(ops))
+                .map(|(field, _)| UpdateError::InvalidOperation(field.clone())),
+        );
+
+        let conflicts: BTreeSet<&String> = self
+            .update
+            .keys()
+            .filter(|field| self.fields.contains_key(*field))
+            .collect();
+
+        if !conflicts.is_empty() {
+            problems.push(UpdateError::Conflict(
+                conflicts.into_iter().cloned().collect(),
+            ));
+        }
+
+        problems
+    }
+
+    /// Does this payload contain any changes?
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+            && self.update.is_empty()
+            && self.transition.is_none()
+            && self.properties.is_empty()
+    }
+
+    /// The `fields` half of this payload
+    pub fn fields(&self) -> &Map<String, Value> {
+        &self.fields
+    }
+
+    /// The `update` half of this payload
+    pub fn updates(&self) -> &Map<String, Value> {
+        &self.update
+    }
+
+    /// Iterate over the operations requested for the given field
+    pub fn operations<'a>(&'a self, field: &str) -> impl Iterator<Item = (Operations, &'a Value)> {
+        self.update
+            .get(field)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_object)
+            .flat_map(|op| op.iter())
+            .filter_map(|(op, value)| Some((Operations::try_new(op)?, value)))
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+
+    fn typed_operation<K, V>(&mut self, field: K, op: Operations, value: V) -> &mut Self
+    where
+        K: Into<String>,
+        V: Serialize,
+    {
+        let field = field.into();
+
+        match self.serialize_value(&field, value) {
+            Some(value) => self.operation(field, op, value),
+            None => self,
+        }
+    }
+
+    fn serialize_value<V>(&mut self, field: &str, value: V) -> Option<Value>
+    where
+        V: Serialize,
+    {
+        serde_json::to_value(value)
+            .map_err(|e| self.invalid.push((field.to_string(), e.to_string())))
+            .ok()
+    }
+}
+
+// Refuse to serialize payloads that Jira would reject
+impl Serialize for IssueUpdate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Repr<'a> {
+            #[serde(skip_serializing_if = "Map::is_empty")]
+            fields: &'a Map<String, Value>,
+            #[serde(skip_serializing_if = "Map::is_empty")]
+            update: &'a Map<String, Value>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            transition: &'a Option<Transition>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            history_metadata: &'a Option<HistoryMetadata>,
+            #[serde(skip_serializing_if = "<[_]>::is_empty")]
+            properties: &'a [EntityProperty],
+        }
+
+        self.check().map_err(S::Error::custom)?;
+
+        Repr {
+            fields: &self.fields,
+            update: &self.update,
+            transition: &self.transition,
+            history_metadata: &self.history_metadata,
+            properties: &self.properties,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// A workflow transition to perform alongside a create or edit
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct Transition {
+    #[serde(with = "common::id")]
+    pub id: u64,
+}
+
+/// Additional information recorded in the issue's history about a change,
+/// typically used by integrations to describe why a change was made
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryMetadata {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity_description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity_description_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_description_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<HistoryParticipant>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generator: Option<HistoryParticipant>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cause: Option<HistoryParticipant>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra_data: HashMap<String, String>,
+}
+
+/// The user, system or integration responsible for a change
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryParticipant {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// An arbitrary key/value pair stored against an issue
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct EntityProperty {
+    pub key: String,
+    pub value: Value,
+}
+
+/// Is the given value a list of `{"<op>": <value>}` objects?
+fn is_operations(ops: &Value) -> bool {
+    match ops.as_array() {
+        Some(ops) => ops.iter().all(|op| match op.as_object() {
+            Some(op) => op.len() == 1 && op.keys().all(|k| Operations::try_new(k).is_some()),
+            None => false,
+        }),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn empty() {
+        let update = IssueUpdate::new();
+
+        assert!(update.is_empty());
+        assert_eq!(serde_json::to_value(&update).unwrap(), json!({}))
+    }
+
+    #[test]
+    fn fields_and_update() {
+        let update = IssueUpdate::new().with(|this| {
+            this.field("summary", json!("foo"))
+                .operation("labels", Operations::Add, json!("bar"))
+                .operation("labels", Operations::Remove, json!("baz"))
+        });
+
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            json!({
+                "fields": { "summary": "foo" },
+                "update": { "labels": [{ "add": "bar" }, { "remove": "baz" }] },
+            })
+        )
+    }
+
+    #[test]
+    fn typed_operations() {
+        let update = IssueUpdate::new().with(|this| {
+            this.set("priority", json!({ "name": "High" }))
+                .add("components", json!({ "name": "foo" }))
+                .remove("labels", "bar")
+                .edit("timetracking", json!({ "remainingEstimate": "1d" }))
+                .update_set("description", "baz")
+        });
+        let labels: Vec<_> = update.operations("labels").collect();
+
+        assert_eq!(labels, vec![(Operations::Remove, &json!("bar"))]);
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            json!({
+                "fields": { "priority": { "name": "High" } },
+                "update": {
+                    "components": [{ "add": { "name": "foo" } }],
+                    "labels": [{ "remove": "bar" }],
+                    "timetracking": [{ "edit": { "remainingEstimate": "1d" } }],
+                    "description": [{ "set": "baz" }],
+                },
+            })
+        )
+    }
+
+    #[test]
+    fn transition_and_metadata() {
+        let metadata = HistoryMetadata {
+            kind: Some("myplugin:type".into()),
+            actor: Some(HistoryParticipant {
+                id: Some("tony".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let update = IssueUpdate::new().with(|this| {
+            this.transition(5)
+                .history_metadata(metadata)
+                .property("foo", json!({ "bar": 1 }))
+        });
+
+        assert_eq!(
+            serde_json::to_value(&update).unwrap(),
+            json!({
+                "transition": { "id": "5" },
+                "historyMetadata": { "type": "myplugin:type", "actor": { "id": "tony" } },
+                "properties": [{ "key": "foo", "value": { "bar": 1 } }],
+            })
+        )
+    }
+
+    #[test]
+    fn conflict() {
+        let update = IssueUpdate::new().with(|this| {
+            this.set("labels", ["foo"])
+                .add("labels", "bar")
+                .set("summary", "baz")
+        });
+
+        assert_eq!(
+            update.problems(),
+            vec![UpdateError::Conflict(vec!["labels".into()])]
+        );
+        assert!(serde_json::to_value(&update).is_err())
+    }
+
+    #[test]
+    fn invalid_value() {
+        let mut bad = HashMap::new();
+        bad.insert(vec![1u8], "keys must be strings");
+
+        let update = IssueUpdate::new().with(|this| this.set("foo", bad));
+
+        assert!(matches!(
+            update.problems().as_slice(),
+            [UpdateError::InvalidValue { .. }]
+        ));
+        assert!(update.check().is_err())
+    }
+
+    #[test]
+    fn every_problem() {
+        let mut bad = HashMap::new();
+        bad.insert(vec![1u8], "keys must be strings");
+
+        let update = IssueUpdate::new().with(|this| {
+            this.set("foo", bad.clone())
+                .set("bar", bad)
+                .set("labels", ["foo"])
+                .add("labels", "bar")
+        });
+        let problems = update.problems();
+
+        assert_eq!(problems.len(), 3);
+        assert!(matches!(
+            problems.as_slice(),
+            [
+                UpdateError::InvalidValue { .. },
+                UpdateError::InvalidValue { .. },
+                UpdateError::Conflict(_)
+            ]
+        ));
+        assert_eq!(
+            update.check().unwrap_err().problems,
+            problems.into_iter().map(Problem::from).collect::<Vec<_>>()
+        )
+    }
+
+    #[test]
+    fn deserialize() {
+        let json = json!({
+            "fields": { "summary": "foo" },
+            "update": { "labels": [{ "add": "bar" }] },
+            "transition": { "id": "5" },
+        });
+
+        let update: IssueUpdate = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(update.check(), Ok(()));
+        assert_eq!(serde_json::to_value(&update).unwrap(), json)
+    }
+
+    #[test]
+    fn deserialize_invalid_operation() {
+        let json = json!({ "update": { "labels": { "add": "bar" } } });
+
+        let update: IssueUpdate = serde_json::from_value(json).unwrap();
+
+        assert_eq!(
+            update.problems(),
+            vec![UpdateError::InvalidOperation("labels".into())]
+        )
+    }
+
+    #[test]
+    fn deserialize_unknown_key() {
+        let json = json!({ "feilds": { "summary": "foo" } });
+
+        let update: Result<IssueUpdate, _> = serde_json::from_value(json);
+
+        assert!(update.is_err())
+    }
+}
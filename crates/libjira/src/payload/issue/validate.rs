@@ -0,0 +1,558 @@
+pub use crate::error::{Problem, ValidationError};
+
+use {
+    super::*,
+    crate::{
+        models::issue::{IssueFieldsMeta, MetaCreate, MetaEdit, Operations},
+        options::issue::MetaCreate as MetaCreateOptions,
+    },
+    serde_json::value::RawValue as RawJson,
+    std::{borrow::Cow, collections::HashMap},
+};
+
+type FieldsMeta<'a> = HashMap<Cow<'a, str>, IssueFieldsMeta<'a>>;
+
+const PROJECT: &str = "project";
+const ISSUE_TYPE: &str = "issuetype";
+const EXPAND_FIELDS: &str = "projects.issuetypes.fields";
+
+impl IssueUpdate {
+    /// Validate this payload against the create metadata of the project and
+    /// issue type it targets, returning every problem found.
+    ///
+    /// The metadata must have been requested with the `projects.issuetypes.fields`
+    /// expand, see `IssueUpdate::meta_create_options`.
+    pub fn validate_create(&self, meta: &MetaCreate<'_>) -> Result<(), ValidationError> {
+        let mut problems = self.payload_problems();
+
+        match create_fields(self, meta) {
+            Ok(fields) => check_fields(self, fields, Mode::Create, &mut problems),
+            Err(problem) => problems.push(problem),
+        }
+
+        finish(problems)
+    }
+
+    /// Validate this payload against an issue's edit metadata, returning
+    /// every problem found
+    pub fn validate_edit(&self, meta: &MetaEdit<'_>) -> Result<(), ValidationError> {
+        let mut problems = self.payload_problems();
+
+        check_fields(self, &meta.fields, Mode::Edit, &mut problems);
+
+        finish(problems)
+    }
+
+    /// The options required to fetch the create metadata this payload
+    /// should be validated against, based on its `project` and `issuetype`
+    pub fn meta_create_options(&self) -> MetaCreateOptions {
+        let mut options = MetaCreateOptions::new();
+
+        if let Some(project) = self.fields().get(PROJECT).map(ObjectRef::new) {
+            match (project.id, project.name) {
+                (Some(id), _) => options.project_ids(Some(id)),
+                (None, Some(key)) => options.project_keys(Some(key)),
+                _ => &mut options,
+            };
+        }
+
+        if let Some(issue_type) = self.fields().get(ISSUE_TYPE).map(ObjectRef::new) {
+            match (issue_type.id, issue_type.name) {
+                (Some(id), _) => options.issuetype_ids(Some(id)),
+                (None, Some(name)) => options.issuetype_keys(Some(name)),
+                _ => &mut options,
+            };
+        }
+
+        options.expand(Some(EXPAND_FIELDS));
+        options
+    }
+
+    fn payload_problems(&self) -> Vec<Problem> {
+        self.problems().into_iter().map(Problem::from).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Create,
+    Edit,
+}
+
+/// Locate the fields of the project & issue type this payload targets
+fn create_fields<'m, 'a>(
+    update: &IssueUpdate,
+    meta: &'m MetaCreate<'a>,
+) -> Result<&'m FieldsMeta<'a>, Problem> {
+    let project_ref = update.fields().get(PROJECT).map(ObjectRef::new);
+    let type_ref = update.fields().get(ISSUE_TYPE).map(ObjectRef::new);
+
+    let (project_ref, type_ref) = match (project_ref, type_ref) {
+        (Some(p), Some(t)) => (p, t),
+        (None, _) => return Err(missing(PROJECT, "Project")),
+        (_, None) => return Err(missing(ISSUE_TYPE, "Issue Type")),
+    };
+
+    let project = meta
+        .projects
+        .iter()
+        .find(|p| project_ref.matches(p.project.id, &p.project.key))
+        .ok_or_else(|| Problem::UnknownProject(project_ref.to_string()))?;
+
+    let issue_type = project
+        .issue_types
+        .iter()
+        .find(|t| type_ref.matches(t.issue_type.id, &t.issue_type.name))
+        .ok_or_else(|| Problem::UnknownIssueType(type_ref.to_string()))?;
+
+    issue_type.fields.as_ref().ok_or(Problem::MissingFieldsMeta)
+}
+
+fn check_fields(
+    update: &IssueUpdate,
+    meta: &FieldsMeta<'_>,
+    mode: Mode,
+    problems: &mut Vec<Problem>,
+) {
+    for (id, value) in update.fields() {
+        match meta.get(id.as_str()) {
+            Some(field) => {
+                if !supports(field, Operations::Set) {
+                    problems.push(unsupported(id, Operations::Set))
+                }
+                check_value(id, field, value, true, problems)
+            }
+            None => problems.push(Problem::UnknownField(id.clone())),
+        }
+    }
+
+    for id in update.updates().keys() {
+        match meta.get(id.as_str()) {
+            Some(field) => {
+                for (op, value) in update.operations(id) {
+                    if !supports(field, op) {
+                        problems.push(unsupported(id, op))
+                    }
+                    let whole = matches!(op, Operations::Set | Operations::Edit);
+                    check_value(id, field, value, whole, problems)
+                }
+            }
+            None => problems.push(Problem::UnknownField(id.clone())),
+        }
+    }
+
+    let mut required: Vec<_> = meta.iter().filter(|(_, f)| f.required).collect();
+    required.sort_by_key(|(id, _)| *id);
+
+    for (id, field) in required {
+        let provided = update.fields().get(id.as_ref()).or_else(|| {
+            update
+                .operations(id)
+                .find(|(op, _)| matches!(op, Operations::Set | Operations::Add))
+                .map(|(_, value)| value)
+        });
+
+        let is_missing = match (provided, mode) {
+            (Some(value), _) => is_empty(value),
+            (None, Mode::Create) => field.default.is_none(),
+            (None, Mode::Edit) => false,
+        };
+
+        if is_missing {
+            problems.push(missing(id, &field.name))
+        }
+    }
+}
+
+/// Check the given value against the field's schema and allowed values,
+/// `whole` is false if the value is a single element of an array field
+fn check_value(
+    id: &str,
+    field: &IssueFieldsMeta<'_>,
+    value: &Value,
+    whole: bool,
+    problems: &mut Vec<Problem>,
+) {
+    if value.is_null() {
+        return;
+    }
+
+    let elements: Vec<&Value> = match (field.schema.as_ref(), whole) {
+        (Some(schema), true) if schema.field_type == "array" => match value.as_array() {
+            Some(values) => values.iter().collect(),
+            None => {
+                problems.push(mismatch(id, "array", value));
+                return;
+            }
+        },
+        (Some(schema), true) => {
+            if !type_matches(&schema.field_type, value) {
+                problems.push(mismatch(id, &schema.field_type, value));
+                return;
+            }
+            vec![value]
+        }
+        _ => vec![value],
+    };
+
+    let item_type = field
+        .schema
+        .as_ref()
+        .and_then(|schema| match schema.field_type.as_ref() {
+            "array" => schema.items.as_deref(),
+            other => Some(other),
+        });
+
+    for element in elements {
+        if let Some(item_type) = item_type {
+            if !type_matches(item_type, element) {
+                problems.push(mismatch(id, item_type, element));
+                continue;
+            }
+        }
+
+        if let Some(allowed) = field.possible_values.as_ref().filter(|v| !v.is_empty()) {
+            if !is_allowed(element, allowed) {
+                problems.push(Problem::NotAllowed {
+                    field: id.to_string(),
+                    value: element.to_string(),
+                })
+            }
+        }
+    }
+}
+
+fn type_matches(field_type: &str, value: &Value) -> bool {
+    match field_type {
+        "string" | "date" | "datetime" => value.is_string(),
+        "number" => value.is_number(),
+        "array" => value.is_array(),
+        "user" | "group" | "option" | "option-with-child" | "priority" | "issuetype"
+        | "project" | "version" | "component" | "resolution" | "securitylevel" | "status"
+        | "timetracking" | "issuelink" => value.is_object(),
+        // 'any' and types we don't know about
+        _ => true,
+    }
+}
+
+/// An element is allowed if any of the allowed values agrees with every
+/// identifying key the element provides
+fn is_allowed(element: &Value, allowed: &[&RawJson]) -> bool {
+    const IDENTIFIERS: [&str; 4] = ["id", "key", "name", "value"];
+
+    let element = match element.as_object() {
+        Some(element) => element,
+        // Only objects can be compared against allowedValues
+        None => return true,
+    };
+
+    let identifiers: Vec<(&str, String)> = IDENTIFIERS
+        .iter()
+        .filter_map(|&k| element.get(k).and_then(scalar).map(|v| (k, v)))
+        .collect();
+
+    if identifiers.is_empty() {
+        return true;
+    }
+
+    allowed
+        .iter()
+        .filter_map(|raw| serde_json::from_str::<Map<String, Value>>(raw.get()).ok())
+        .any(|candidate| {
+            identifiers
+                .iter()
+                .all(|(k, v)| candidate.get(*k).and_then(scalar).as_ref() == Some(v))
+        })
+}
+
+fn supports(field: &IssueFieldsMeta<'_>, op: Operations) -> bool {
+    // Fields without any listed operations give us nothing to check against
+    field.operations.is_empty() || field.operations.contains(&op)
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        Value::Array(a) => a.is_empty(),
+        _ => false,
+    }
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn missing(field: &str, name: &str) -> Problem {
+    Problem::MissingRequired {
+        field: field.to_string(),
+        name: name.to_string(),
+    }
+}
+
+fn unsupported(field: &str, op: Operations) -> Problem {
+    Problem::UnsupportedOperation {
+        field: field.to_string(),
+        operation: op.as_str().to_string(),
+    }
+}
+
+fn mismatch(field: &str, expected: &str, found: &Value) -> Problem {
+    Problem::TypeMismatch {
+        field: field.to_string(),
+        expected: expected.to_string(),
+        found: type_name(found).to_string(),
+    }
+}
+
+fn finish(problems: Vec<Problem>) -> Result<(), ValidationError> {
+    match problems.is_empty() {
+        true => Ok(()),
+        false => Err(ValidationError { problems }),
+    }
+}
+
+/// A reference to a Jira object by id, or by key / name
+struct ObjectRef {
+    id: Option<u64>,
+    name: Option<String>,
+}
+
+impl ObjectRef {
+    fn new(value: &Value) -> Self {
+        let get = |k| value.get(k).and_then(scalar);
+
+        Self {
+            id: get("id").and_then(|id| id.parse().ok()),
+            name: get("key").or_else(|| get("name")),
+        }
+    }
+
+    fn matches(&self, id: u64, name: &str) -> bool {
+        match (self.id, self.name.as_deref()) {
+            (Some(this), _) => this == id,
+            (None, Some(this)) => this == name,
+            (None, None) => false,
+        }
+    }
+}
+
+impl std::fmt::Display for ObjectRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (self.id, self.name.as_deref()) {
+            (Some(id), _) => write!(f, "{}", id),
+            (None, Some(name)) => f.write_str(name),
+            (None, None) => f.write_str("<unknown>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn edit_meta() -> Value {
+        json!({
+            "fields": {
+                "summary": field("Summary", true, json!({ "type": "string", "system": "summary" }), &["set"], None),
+                "labels": field("Labels", false, json!({ "type": "array", "items": "string", "system": "labels" }), &["add", "set", "remove"], None),
+                "priority": field("Priority", true, json!({ "type": "priority", "system": "priority" }), &["set"], Some(json!([
+                    { "id": "1", "name": "High" },
+                    { "id": "2", "name": "Low" },
+                ]))),
+                "customfield_10016": field("Story Points", false, json!({ "type": "number", "custom": "foo", "customId": 10016 }), &["set"], None),
+            }
+        })
+    }
+
+    fn create_meta() -> Value {
+        let mut fields = edit_meta()["fields"].clone();
+        fields["project"] = field(
+            "Project",
+            true,
+            json!({ "type": "project", "system": "project" }),
+            &["set"],
+            None,
+        );
+        fields["issuetype"] = field(
+            "Issue Type",
+            true,
+            json!({ "type": "issuetype", "system": "issuetype" }),
+            &["set"],
+            None,
+        );
+
+        json!({
+            "expand": "projects",
+            "projects": [{
+                "id": "10000",
+                "self": "foo",
+                "key": "FOO",
+                "name": "foo",
+                "issuetypes": [{
+                    "id": "1",
+                    "self": "foo",
+                    "description": "foo",
+                    "iconUrl": "foo",
+                    "name": "Bug",
+                    "subtask": false,
+                    "fields": fields,
+                }],
+            }],
+        })
+    }
+
+    fn field(
+        name: &str,
+        required: bool,
+        schema: Value,
+        ops: &[&str],
+        allowed: Option<Value>,
+    ) -> Value {
+        json!({
+            "required": required,
+            "name": name,
+            "fieldId": name,
+            "schema": schema,
+            "operations": ops,
+            "allowedValues": allowed,
+        })
+    }
+
+    #[test]
+    fn valid_edit() {
+        let raw = serde_json::to_string(&edit_meta()).unwrap();
+        let meta: MetaEdit = serde_json::from_str(&raw).unwrap();
+
+        let update = IssueUpdate::new().with(|this| {
+            this.set("summary", "foo")
+                .set("priority", json!({ "name": "High" }))
+                .add("labels", "bar")
+        });
+
+        assert_eq!(update.validate_edit(&meta), Ok(()))
+    }
+
+    #[test]
+    fn invalid_edit() {
+        let raw = serde_json::to_string(&edit_meta()).unwrap();
+        let meta: MetaEdit = serde_json::from_str(&raw).unwrap();
+
+        let update = IssueUpdate::new().with(|this| {
+            this.set("summary", "")
+                .set("priority", json!({ "name": "Urgent" }))
+                .set("customfield_10016", "three")
+                .set("unknown", 1)
+                .edit("labels", ["bar"])
+        });
+
+        let problems = update.validate_edit(&meta).unwrap_err().problems;
+
+        assert_eq!(problems.len(), 5, "{:#?}", problems);
+        assert!(problems.contains(&Problem::UnknownField("unknown".into())));
+        assert!(problems.contains(&Problem::NotAllowed {
+            field: "priority".into(),
+            value: r#"{"name":"Urgent"}"#.into()
+        }));
+        assert!(problems.contains(&Problem::TypeMismatch {
+            field: "customfield_10016".into(),
+            expected: "number".into(),
+            found: "string".into()
+        }));
+        assert!(problems.contains(&Problem::UnsupportedOperation {
+            field: "labels".into(),
+            operation: "edit".into()
+        }));
+        assert!(problems.contains(&Problem::MissingRequired {
+            field: "summary".into(),
+            name: "Summary".into()
+        }));
+    }
+
+    #[test]
+    fn valid_create() {
+        let raw = serde_json::to_string(&create_meta()).unwrap();
+        let meta: MetaCreate = serde_json::from_str(&raw).unwrap();
+
+        let update = IssueUpdate::new().with(|this| {
+            this.set("project", json!({ "key": "FOO" }))
+                .set("issuetype", json!({ "id": "1" }))
+                .set("summary", "foo")
+                .set("priority", json!({ "id": "2" }))
+        });
+
+        assert_eq!(update.validate_create(&meta), Ok(()))
+    }
+
+    #[test]
+    fn missing_required_create() {
+        let raw = serde_json::to_string(&create_meta()).unwrap();
+        let meta: MetaCreate = serde_json::from_str(&raw).unwrap();
+
+        let update = IssueUpdate::new().with(|this| {
+            this.set("project", json!({ "id": 10000 }))
+                .set("issuetype", json!({ "name": "Bug" }))
+        });
+
+        let problems = update.validate_create(&meta).unwrap_err().problems;
+
+        assert_eq!(
+            problems,
+            vec![
+                missing("priority", "Priority"),
+                missing("summary", "Summary")
+            ]
+        )
+    }
+
+    #[test]
+    fn unknown_project() {
+        let raw = serde_json::to_string(&create_meta()).unwrap();
+        let meta: MetaCreate = serde_json::from_str(&raw).unwrap();
+
+        let update = IssueUpdate::new().with(|this| {
+            this.set("project", json!({ "key": "BAR" }))
+                .set("issuetype", json!({ "name": "Bug" }))
+        });
+
+        let problems = update.validate_create(&meta).unwrap_err().problems;
+
+        assert_eq!(problems, vec![Problem::UnknownProject("BAR".into())])
+    }
+
+    #[test]
+    fn create_options() {
+        let update = IssueUpdate::new().with(|this| {
+            this.set("project", json!({ "key": "FOO" }))
+                .set("issuetype", json!({ "id": "1" }))
+        });
+
+        let req = reqwest::Client::new()
+            .get("http://localhost")
+            .query(&update.meta_create_options())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            req.url().query(),
+            Some("projectKeys=FOO&issuetypeIds=1&expand=projects.issuetypes.fields")
+        )
+    }
+}
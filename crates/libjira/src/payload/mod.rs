@@ -0,0 +1,10 @@
+use {
+    serde::Serialize,
+    serde_json::{json, Map, Value},
+};
+
+pub mod component;
+pub mod filter;
+pub mod issue;
+pub mod version;
+pub mod webhook;
@@ -0,0 +1,177 @@
+use {super::*, serde::Serializer};
+
+/// The body of a version create or edit request. Unset fields are
+/// left unchanged when editing.
+///
+/// ```ignore
+/// let details = VersionDetails::new().with(|this| {
+///     this.name("1.4.0")
+///         .project("FOO")
+///         .release_date("2021-03-04")
+/// });
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    release_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    released: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    archived: Option<bool>,
+}
+
+/// Where to move a version to, in its project's ordering
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionPosition {
+    First,
+    Last,
+    /// One place earlier
+    Earlier,
+    /// One place later
+    Later,
+    /// After the version with the given self link
+    After(String),
+}
+
+impl VersionDetails {
+    /// Instantiate a new, empty payload
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The version's name, required when creating a version
+    pub fn name<T>(&mut self, name: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.name = name.into().map(|s| s.to_string());
+        self
+    }
+
+    pub fn description<T>(&mut self, description: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.description = description.into().map(|s| s.to_string());
+        self
+    }
+
+    /// The key of the version's project. This or `project_id` is
+    /// required when creating a version
+    pub fn project<T>(&mut self, key: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.project = key.into().map(|s| s.to_string());
+        self
+    }
+
+    pub fn project_id(&mut self, id: impl Into<Option<u64>>) -> &mut Self {
+        self.project_id = id.into();
+        self
+    }
+
+    /// The version's start date, as YYYY-MM-DD
+    pub fn start_date<T>(&mut self, date: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.start_date = date.into().map(|s| s.to_string());
+        self
+    }
+
+    /// The version's (planned) release date, as YYYY-MM-DD
+    pub fn release_date<T>(&mut self, date: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.release_date = date.into().map(|s| s.to_string());
+        self
+    }
+
+    pub fn released(&mut self, released: impl Into<Option<bool>>) -> &mut Self {
+        self.released = released.into();
+        self
+    }
+
+    pub fn archived(&mut self, archived: impl Into<Option<bool>>) -> &mut Self {
+        self.archived = archived.into();
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
+
+impl Serialize for VersionPosition {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let body = match self {
+            Self::First => json!({ "position": "First" }),
+            Self::Last => json!({ "position": "Last" }),
+            Self::Earlier => json!({ "position": "Earlier" }),
+            Self::Later => json!({ "position": "Later" }),
+            Self::After(link) => json!({ "after": link }),
+        };
+
+        body.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {pretty_assertions::assert_eq, serde_json as json};
+
+    #[test]
+    fn serialize_details() {
+        let details = VersionDetails::new().with(|this| {
+            this.name("1.4.0")
+                .project("FOO")
+                .release_date("2021-03-04")
+                .released(true)
+        });
+
+        assert_eq!(
+            json::to_value(&details).unwrap(),
+            json!({
+                "name": "1.4.0",
+                "project": "FOO",
+                "releaseDate": "2021-03-04",
+                "released": true
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_positions() {
+        assert_eq!(
+            json::to_value(VersionPosition::First).unwrap(),
+            json!({ "position": "First" })
+        );
+        assert_eq!(
+            json::to_value(VersionPosition::After("foo".into())).unwrap(),
+            json!({ "after": "foo" })
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use super::*;
+
+/// The body of a dynamic webhook registration, available to Connect
+/// and OAuth 2.0 apps on Jira Cloud.
+///
+/// ```ignore
+/// let registration = WebhookRegistration::new().with(|this| {
+///     this.url("https://example.com/webhook").webhook(
+///         WebhookDetails::new().with(|this| {
+///             this.jql_filter("project = FOO")
+///                 .events(["jira:issue_created", "jira:issue_updated"])
+///         }),
+///     )
+/// });
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct WebhookRegistration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    webhooks: Vec<WebhookDetails>,
+}
+
+/// A single webhook of a registration
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    jql_filter: Option<String>,
+    events: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    field_ids_filter: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issue_property_keys_filter: Option<Vec<String>>,
+}
+
+/// The ids of the webhooks to delete or refresh
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) struct WebhookIds<'a> {
+    #[serde(rename = "webhookIds")]
+    pub ids: &'a [u64],
+}
+
+impl WebhookRegistration {
+    /// Instantiate a new, empty payload
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The URL events are delivered to, which must be on the app's
+    /// base URL. Required
+    pub fn url<T>(&mut self, url: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.url = url.into().map(|s| s.to_string());
+        self
+    }
+
+    /// Add a webhook to the registration
+    pub fn webhook(&mut self, webhook: WebhookDetails) -> &mut Self {
+        self.webhooks.push(webhook);
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
+
+impl WebhookDetails {
+    /// Instantiate a new, empty payload
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The JQL an issue must match for the webhook to fire. Required,
+    /// and limited to a subset of JQL by Jira
+    pub fn jql_filter<T>(&mut self, jql: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.jql_filter = jql.into().map(|s| s.to_string());
+        self
+    }
+
+    /// The events to fire on, e.g "jira:issue_created" or "comment_created"
+    pub fn events<I, T>(&mut self, events: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        self.events = events.into_iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// Only fire "jira:issue_updated" when one of these fields changes
+    pub fn field_ids_filter<I, T>(&mut self, fields: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        self.field_ids_filter = Some(fields.into_iter().map(|s| s.to_string()).collect());
+        self
+    }
+
+    /// Only fire "issue_property_set" when one of these properties is set
+    pub fn issue_property_keys_filter<I, T>(&mut self, keys: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: ToString,
+    {
+        self.issue_property_keys_filter = Some(keys.into_iter().map(|s| s.to_string()).collect());
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {pretty_assertions::assert_eq, serde_json as json};
+
+    #[test]
+    fn serialize_registration() {
+        let registration = WebhookRegistration::new().with(|this| {
+            this.url("https://example.com/webhook")
+                .webhook(WebhookDetails::new().with(|this| {
+                    this.jql_filter("project = FOO")
+                        .events(["jira:issue_updated"])
+                        .field_ids_filter(["summary"])
+                }))
+        });
+
+        assert_eq!(
+            json::to_value(&registration).unwrap(),
+            json!({
+                "url": "https://example.com/webhook",
+                "webhooks": [{
+                    "jqlFilter": "project = FOO",
+                    "events": ["jira:issue_updated"],
+                    "fieldIdsFilter": ["summary"]
+                }]
+            })
+        );
+    }
+}
@@ -0,0 +1,60 @@
+pub use crate::{models::screen as models, options::screen as options};
+
+use {
+    self::endpoint::*,
+    crate::{client::Jira, error::JiraError},
+    models::{ScreenFieldListHandle, ScreenTabListHandle, ScreensHandle},
+    reqwest::RequestBuilder,
+};
+
+/// A handle for inspecting JIRA screens, their tabs and fields
+///
+/// It is cheap to clone a handle and may be done liberally
+#[derive(Debug, Clone)]
+pub struct Screens {
+    client: Jira,
+}
+
+impl Screens {
+    /// Create a new handle from a JIRA client
+    pub fn new(client: &Jira) -> Self {
+        Self {
+            client: client.clone(),
+        }
+    }
+
+    /// Retrieve a page of the instance's screens
+    pub async fn list(&self, options: Option<&options::List>) -> Result<ScreensHandle, JiraError> {
+        let handler = |req: RequestBuilder| match options {
+            Some(options) => Ok(req.query(options)),
+            None => Ok(req),
+        };
+
+        self.client.get(&[SCREENS], handler)?.retrieve().await
+    }
+
+    /// Retrieve the tabs of a screen
+    pub async fn tabs(&self, screen: u64) -> Result<ScreenTabListHandle, JiraError> {
+        self.client
+            .get(&[SCREENS, &screen.to_string(), TABS], Ok)?
+            .retrieve()
+            .await
+    }
+
+    /// Retrieve the fields on one of a screen's tabs
+    pub async fn fields(&self, screen: u64, tab: u64) -> Result<ScreenFieldListHandle, JiraError> {
+        self.client
+            .get(
+                &[SCREENS, &screen.to_string(), TABS, &tab.to_string(), FIELDS],
+                Ok,
+            )?
+            .retrieve()
+            .await
+    }
+}
+
+mod endpoint {
+    pub(super) const SCREENS: &str = "screens";
+    pub(super) const TABS: &str = "tabs";
+    pub(super) const FIELDS: &str = "fields";
+}
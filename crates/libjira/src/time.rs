@@ -0,0 +1,351 @@
+//! Parsing and rendering of Jira's date and time formats, requires
+//! the `chrono` feature.
+//!
+//! Jira timestamps are close to, but not quite ISO-8601: they carry
+//! millisecond precision and an offset without a colon, e.g
+//! `2021-03-04T10:11:12.000+0000`. Dates (e.g `duedate`) are plain
+//! `YYYY-MM-DD` calendar dates.
+
+pub use chrono::ParseError;
+pub use chrono_tz::Tz;
+
+use {
+    crate::models::{
+        cow,
+        issue::{Attachment, Comment, Issue, StandardFields, User},
+    },
+    chrono::{DateTime, FixedOffset, NaiveDate, TimeZone},
+    serde::{Deserializer, Serializer},
+};
+
+/// Jira's timestamp format, as used by `created`, `updated` and friends
+pub const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3f%z";
+
+/// Jira's calendar date format, as used by `duedate`
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Parse a Jira timestamp, falling back to RFC 3339 for the
+/// few places Jira emits a standard timestamp
+pub fn parse_datetime(s: &str) -> Result<DateTime<FixedOffset>, ParseError> {
+    DateTime::parse_from_str(s, DATETIME_FORMAT)
+        .or_else(|e| DateTime::parse_from_rfc3339(s).map_err(|_| e))
+}
+
+/// Parse a Jira calendar date
+pub fn parse_date(s: &str) -> Result<NaiveDate, ParseError> {
+    NaiveDate::parse_from_str(s, DATE_FORMAT)
+}
+
+/// Render the given timestamp in Jira's format
+pub fn format_datetime<Z>(datetime: &DateTime<Z>) -> String
+where
+    Z: TimeZone,
+    Z::Offset: std::fmt::Display,
+{
+    datetime.format(DATETIME_FORMAT).to_string()
+}
+
+/// Render the given date in Jira's format
+pub fn format_date(date: &NaiveDate) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+/// Serde adapter for Jira timestamps, for use with
+/// `#[serde(with = "jira_rs::time::datetime")]`
+pub mod datetime {
+    use super::*;
+
+    pub fn serialize<S>(datetime: &DateTime<FixedOffset>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&datetime.format(DATETIME_FORMAT))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<FixedOffset>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = cow::deserialize(deserializer)?;
+
+        parse_datetime(&s).map_err(serde::de::Error::custom)
+    }
+
+    /// Serde adapter for optional Jira timestamps, for use with
+    /// `#[serde(default, with = "jira_rs::time::datetime::option")]`
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<S>(
+            datetime: &Option<DateTime<FixedOffset>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match datetime {
+                Some(datetime) => super::serialize(datetime, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D>(
+            deserializer: D,
+        ) -> Result<Option<DateTime<FixedOffset>>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            cow::deserialize_option(deserializer)?
+                .map(|s| parse_datetime(&s).map_err(serde::de::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+/// Serde adapter for Jira calendar dates, for use with
+/// `#[serde(with = "jira_rs::time::date")]`
+pub mod date {
+    use super::*;
+
+    pub fn serialize<S>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&date.format(DATE_FORMAT))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<NaiveDate, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = cow::deserialize(deserializer)?;
+
+        parse_date(&s).map_err(serde::de::Error::custom)
+    }
+
+    /// Serde adapter for optional Jira calendar dates, for use with
+    /// `#[serde(default, with = "jira_rs::time::date::option")]`
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<S>(date: &Option<NaiveDate>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match date {
+                Some(date) => super::serialize(date, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            cow::deserialize_option(deserializer)?
+                .map(|s| parse_date(&s).map_err(serde::de::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+impl<'a> User<'a> {
+    /// The user's timezone, if they have one set and it is known
+    pub fn tz(&self) -> Option<Tz> {
+        self.timezone.as_deref().and_then(|tz| tz.parse().ok())
+    }
+
+    /// Convert the given timestamp into the user's local time,
+    /// returning None if the user has no known timezone
+    pub fn local_time<Z>(&self, datetime: &DateTime<Z>) -> Option<DateTime<Tz>>
+    where
+        Z: TimeZone,
+    {
+        self.tz().map(|tz| datetime.with_timezone(&tz))
+    }
+
+    /// Render the given timestamp in the user's timezone with the given
+    /// `strftime` format, falling back to the timestamp's own offset
+    /// if the user has no known timezone
+    pub fn render_time(&self, datetime: &DateTime<FixedOffset>, format: &str) -> String {
+        match self.local_time(datetime) {
+            Some(local) => local.format(format).to_string(),
+            None => datetime.format(format).to_string(),
+        }
+    }
+}
+
+impl<'a> Comment<'a> {
+    /// Parsed timestamp of the comment's creation
+    pub fn created_at(&self) -> Result<DateTime<FixedOffset>, ParseError> {
+        parse_datetime(&self.created)
+    }
+
+    /// Parsed timestamp of the comment's latest update
+    pub fn updated_at(&self) -> Result<DateTime<FixedOffset>, ParseError> {
+        parse_datetime(&self.updated)
+    }
+}
+
+impl<'a> Attachment<'a> {
+    /// Parsed timestamp of the attachment's upload
+    pub fn created_at(&self) -> Result<DateTime<FixedOffset>, ParseError> {
+        parse_datetime(&self.created)
+    }
+}
+
+impl<'a> StandardFields<'a> {
+    /// Parsed timestamp of issue creation
+    pub fn created_at(&self) -> Option<Result<DateTime<FixedOffset>, ParseError>> {
+        self.created.as_deref().map(parse_datetime)
+    }
+
+    /// Parsed timestamp of the issue's latest update
+    pub fn updated_at(&self) -> Option<Result<DateTime<FixedOffset>, ParseError>> {
+        self.updated.as_deref().map(parse_datetime)
+    }
+
+    /// Parsed date this issue is due by
+    pub fn due_on(&self) -> Option<Result<NaiveDate, ParseError>> {
+        self.due_date.as_deref().map(parse_date)
+    }
+}
+
+impl<'a> Issue<'a> {
+    /// Issue's parsed creation timestamp
+    pub fn created_at(&self) -> Option<Result<DateTime<FixedOffset>, ParseError>> {
+        self.created().map(parse_datetime)
+    }
+
+    /// Issue's parsed latest update timestamp
+    pub fn updated_at(&self) -> Option<Result<DateTime<FixedOffset>, ParseError>> {
+        self.updated().map(parse_datetime)
+    }
+
+    /// Issue's parsed resolution timestamp
+    pub fn resolved_at(&self) -> Option<Result<DateTime<FixedOffset>, ParseError>> {
+        self.resolution_date().map(parse_datetime)
+    }
+
+    /// Issue's parsed due date
+    pub fn due_on(&self) -> Option<Result<NaiveDate, ParseError>> {
+        self.due_date().map(parse_date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct Dates {
+        #[serde(with = "datetime")]
+        created: DateTime<FixedOffset>,
+        #[serde(default, with = "datetime::option")]
+        resolved: Option<DateTime<FixedOffset>>,
+        #[serde(with = "date")]
+        due: NaiveDate,
+        #[serde(default, with = "date::option")]
+        start: Option<NaiveDate>,
+    }
+
+    #[test]
+    fn jira_datetime() {
+        let parsed = parse_datetime("2021-03-04T10:11:12.345+0100").unwrap();
+
+        assert_eq!(parsed.timestamp_millis(), 1614849072345);
+        assert_eq!(format_datetime(&parsed), "2021-03-04T10:11:12.345+0100");
+    }
+
+    #[test]
+    fn rfc3339_datetime() {
+        let parsed = parse_datetime("2021-03-04T09:11:12.345Z").unwrap();
+
+        assert_eq!(parsed.timestamp_millis(), 1614849072345);
+    }
+
+    #[test]
+    fn invalid_datetime() {
+        assert!(parse_datetime("yesterday").is_err())
+    }
+
+    #[test]
+    fn round_trip_serde() {
+        let json = json!({
+            "created": "2021-03-04T10:11:12.000+0000",
+            "resolved": null,
+            "due": "2021-03-05",
+            "start": "2021-03-01",
+        });
+
+        let dates: Dates = serde_json::from_value(json.clone()).unwrap();
+
+        assert_eq!(dates.due, NaiveDate::from_ymd_opt(2021, 3, 5).unwrap());
+        assert_eq!(serde_json::to_value(&dates).unwrap(), json)
+    }
+
+    #[test]
+    fn issue_timestamps() {
+        let mut json = crate::models::issue::issue::types::issue();
+        json["fields"]["created"] = json!("2021-03-04T10:11:12.000+0000");
+        json["fields"]["resolutiondate"] = json!(null);
+        let json = serde_json::to_string(&json).unwrap();
+        let issue: Issue = serde_json::from_str(&json).unwrap();
+        let fields = issue.standard_fields().unwrap();
+
+        let created = parse_datetime("2021-03-04T10:11:12.000+0000").unwrap();
+        assert_eq!(issue.created_at(), Some(Ok(created)));
+        assert_eq!(issue.created_at(), fields.created_at());
+
+        // Unparsable timestamps are reported, not swallowed
+        assert!(matches!(issue.updated_at(), Some(Err(_))));
+        assert!(matches!(fields.updated_at(), Some(Err(_))));
+
+        assert_eq!(issue.resolved_at(), None);
+        assert_eq!(
+            issue.due_on(),
+            Some(Ok(NaiveDate::from_ymd_opt(2021, 3, 4).unwrap()))
+        );
+        assert_eq!(issue.due_on(), fields.due_on())
+    }
+
+    #[test]
+    fn render_user_time() {
+        let user = r#"{
+            "active": true,
+            "avatarUrls": {},
+            "displayName": "foo",
+            "emailAddress": "foo",
+            "name": "foo",
+            "self": "foo",
+            "timeZone": "Australia/Sydney"
+        }"#;
+        let user: User = serde_json::from_str(user).unwrap();
+        let created = parse_datetime("2021-03-04T10:11:12.000+0000").unwrap();
+
+        assert_eq!(user.tz(), Some(chrono_tz::Australia::Sydney));
+        assert_eq!(
+            user.render_time(&created, "%Y-%m-%d %H:%M %Z"),
+            "2021-03-04 21:11 AEDT"
+        )
+    }
+
+    #[test]
+    fn render_without_timezone() {
+        let user = r#"{
+            "active": true,
+            "avatarUrls": {},
+            "displayName": "foo",
+            "emailAddress": "foo",
+            "name": "foo",
+            "self": "foo"
+        }"#;
+        let user: User = serde_json::from_str(user).unwrap();
+        let created = parse_datetime("2021-03-04T10:11:12.000+0000").unwrap();
+
+        assert_eq!(user.render_time(&created, "%H:%M %z"), "10:11 +0000")
+    }
+}
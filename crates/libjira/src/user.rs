@@ -0,0 +1,35 @@
+pub use crate::models::user as models;
+
+use {
+    self::endpoint::*,
+    crate::{client::Jira, error::JiraError},
+    models::UserHandle,
+};
+
+/// A handle for interacting with JIRA users
+///
+/// It is cheap to clone a handle and may be done liberally
+#[derive(Debug, Clone)]
+pub struct Users {
+    client: Jira,
+}
+
+impl Users {
+    /// Create a new handle from a JIRA client
+    pub fn new(client: &Jira) -> Self {
+        Self {
+            client: client.clone(),
+        }
+    }
+
+    /// Retrieve the user the client is authenticated as
+    ///
+    /// Useful for verifying a set of credentials
+    pub async fn myself(&self) -> Result<UserHandle, JiraError> {
+        self.client.get(&[MYSELF], Ok)?.retrieve().await
+    }
+}
+
+mod endpoint {
+    pub(super) const MYSELF: &str = "myself";
+}
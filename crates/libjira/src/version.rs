@@ -0,0 +1,155 @@
+pub use crate::{
+    models::version as models, options::version as options, payload::version as payload,
+};
+
+use {
+    self::endpoint::*,
+    crate::{client::Jira, error::JiraError, models::empty::Empty},
+    models::{RelatedCounts, UnresolvedCount, VersionHandle, VersionListHandle},
+    payload::{VersionDetails, VersionPosition},
+    reqwest::RequestBuilder,
+};
+
+/// A handle for interacting with JIRA project versions, i.e releases
+///
+/// It is cheap to clone a handle and may be done liberally
+#[derive(Debug, Clone)]
+pub struct Versions {
+    client: Jira,
+}
+
+impl Versions {
+    /// Create a new handle from a JIRA client
+    pub fn new(client: &Jira) -> Self {
+        Self {
+            client: client.clone(),
+        }
+    }
+
+    /// Retrieve every version of a project, by its key or id
+    pub async fn list(&self, project: &str) -> Result<VersionListHandle, JiraError> {
+        self.client
+            .get(&[PROJECT, project, VERSIONS], Ok)?
+            .retrieve()
+            .await
+    }
+
+    /// Retrieve a single version by id
+    pub async fn get(&self, id: u64) -> Result<VersionHandle, JiraError> {
+        self.client
+            .get(&[VERSION, &id.to_string()], Ok)?
+            .retrieve()
+            .await
+    }
+
+    /// Create a new version, the details must include a name and project
+    pub async fn create(&self, details: &VersionDetails) -> Result<VersionHandle, JiraError> {
+        let handler = |req: RequestBuilder| Ok(req.json(details));
+
+        self.client.post(&[VERSION], handler)?.retrieve().await
+    }
+
+    /// Edit an existing version
+    pub async fn edit(
+        &self,
+        id: u64,
+        details: &VersionDetails,
+    ) -> Result<VersionHandle, JiraError> {
+        let handler = |req: RequestBuilder| Ok(req.json(details));
+
+        self.client
+            .put(&[VERSION, &id.to_string()], handler)?
+            .retrieve()
+            .await
+    }
+
+    /// Delete a version, optionally moving its issues to other versions
+    pub async fn delete(
+        &self,
+        id: u64,
+        options: Option<&options::Delete>,
+    ) -> Result<(), JiraError> {
+        let handler = |req: RequestBuilder| match options {
+            Some(options) => Ok(req.query(options)),
+            None => Ok(req),
+        };
+
+        self.client
+            .delete(&[VERSION, &id.to_string()], handler)?
+            .retrieve::<Empty>()
+            .await
+            .map(Into::into)
+    }
+
+    /// Mark a version as released on the given date (YYYY-MM-DD), or
+    /// leave its release date as is if none is given
+    pub async fn release<T>(&self, id: u64, date: Option<T>) -> Result<VersionHandle, JiraError>
+    where
+        T: ToString,
+    {
+        let mut details = VersionDetails::new();
+        details.released(true);
+        if let Some(date) = date {
+            details.release_date(date);
+        }
+
+        self.edit(id, &details).await
+    }
+
+    /// Archive a version, hiding it from most of Jira's UI
+    pub async fn archive(&self, id: u64) -> Result<VersionHandle, JiraError> {
+        let details = VersionDetails::new().with(|this| this.archived(true));
+
+        self.edit(id, &details).await
+    }
+
+    /// Merge a version into another, moving all of its issues before
+    /// deleting it
+    pub async fn merge(&self, id: u64, into: u64) -> Result<(), JiraError> {
+        self.client
+            .put(&[VERSION, &id.to_string(), MERGE_TO, &into.to_string()], Ok)?
+            .retrieve::<Empty>()
+            .await
+            .map(Into::into)
+    }
+
+    /// Move a version within its project's ordering
+    pub async fn move_to(
+        &self,
+        id: u64,
+        position: &VersionPosition,
+    ) -> Result<VersionHandle, JiraError> {
+        let handler = |req: RequestBuilder| Ok(req.json(position));
+
+        self.client
+            .post(&[VERSION, &id.to_string(), MOVE], handler)?
+            .retrieve()
+            .await
+    }
+
+    /// Retrieve how many of a version's fixed issues are unresolved
+    pub async fn unresolved_count(&self, id: u64) -> Result<UnresolvedCount, JiraError> {
+        self.client
+            .get(&[VERSION, &id.to_string(), UNRESOLVED_COUNT], Ok)?
+            .retrieve()
+            .await
+    }
+
+    /// Retrieve how many issues are fixed in, or affected by a version
+    pub async fn related_counts(&self, id: u64) -> Result<RelatedCounts, JiraError> {
+        self.client
+            .get(&[VERSION, &id.to_string(), RELATED_COUNTS], Ok)?
+            .retrieve()
+            .await
+    }
+}
+
+mod endpoint {
+    pub(super) const PROJECT: &str = "project";
+    pub(super) const VERSIONS: &str = "versions";
+    pub(super) const VERSION: &str = "version";
+    pub(super) const MERGE_TO: &str = "mergeto";
+    pub(super) const MOVE: &str = "move";
+    pub(super) const UNRESOLVED_COUNT: &str = "unresolvedIssueCount";
+    pub(super) const RELATED_COUNTS: &str = "relatedIssueCounts";
+}
@@ -0,0 +1,117 @@
+//! Dynamic webhook registration, and the events Jira delivers to
+//! webhooks.
+//!
+//! Enable the `receiver` feature for an embedded HTTP server which
+//! verifies and dispatches events, see `receiver::Receiver`.
+
+pub use crate::{
+    models::webhook as models, options::webhook as options, payload::webhook as payload,
+};
+
+#[cfg(feature = "receiver")]
+pub mod receiver;
+
+use {
+    self::endpoint::*,
+    crate::{client::Jira, error::JiraError, models::empty::Empty},
+    models::{RegisteredWebhooks, WebhookExpiry, WebhooksHandle},
+    payload::{WebhookIds, WebhookRegistration},
+    reqwest::RequestBuilder,
+};
+
+/// A handle for registering dynamic webhooks.
+///
+/// Dynamic webhooks are only available to Connect and OAuth 2.0 apps on
+/// Jira Cloud, and only the calling app's webhooks are visible to it.
+/// They expire after 30 days unless refreshed, see `Webhooks::refresh`
+///
+/// It is cheap to clone a handle and may be done liberally
+#[derive(Debug, Clone)]
+pub struct Webhooks {
+    client: Jira,
+}
+
+impl Webhooks {
+    /// Create a new handle from a JIRA client
+    pub fn new(client: &Jira) -> Self {
+        Self {
+            client: client.clone(),
+        }
+    }
+
+    /// Retrieve a page of the app's webhooks
+    pub async fn list(&self, options: Option<&options::List>) -> Result<WebhooksHandle, JiraError> {
+        let handler = |req: RequestBuilder| match options {
+            Some(options) => Ok(req.query(options)),
+            None => Ok(req),
+        };
+
+        self.client.get(&[WEBHOOK], handler)?.retrieve().await
+    }
+
+    /// Register webhooks. Each webhook is registered (or rejected)
+    /// independently, check the results for any errors
+    pub async fn register(
+        &self,
+        registration: &WebhookRegistration,
+    ) -> Result<RegisteredWebhooks, JiraError> {
+        let handler = |req: RequestBuilder| Ok(req.json(registration));
+
+        self.client.post(&[WEBHOOK], handler)?.retrieve().await
+    }
+
+    /// Delete webhooks by id
+    pub async fn delete(&self, ids: &[u64]) -> Result<(), JiraError> {
+        let handler = |req: RequestBuilder| Ok(req.json(&WebhookIds { ids }));
+
+        self.client
+            .delete(&[WEBHOOK], handler)?
+            .retrieve::<Empty>()
+            .await
+            .map(Into::into)
+    }
+
+    /// Extend the life of webhooks by another 30 days, returning their
+    /// new expiry
+    pub async fn refresh(&self, ids: &[u64]) -> Result<WebhookExpiry, JiraError> {
+        let handler = |req: RequestBuilder| Ok(req.json(&WebhookIds { ids }));
+
+        self.client
+            .put(&[WEBHOOK, REFRESH], handler)?
+            .retrieve()
+            .await
+    }
+
+    /// Refresh every webhook of the app, returning their new expiry or
+    /// `None` if the app has no webhooks.
+    ///
+    /// Apps should call this periodically, e.g daily, to keep their
+    /// webhooks alive
+    pub async fn refresh_all(&self) -> Result<Option<WebhookExpiry>, JiraError> {
+        let mut ids = Vec::new();
+        let mut start = 0;
+
+        loop {
+            let options = options::List::new().with(|this| this.start_at(start));
+            let page = self.list(Some(&options)).await?;
+            let page = page.data();
+
+            ids.extend(page.values.iter().map(|webhook| webhook.id));
+            start += page.values.len() as u32;
+
+            if page.values.is_empty() || page.is_last.unwrap_or(true) {
+                break;
+            }
+        }
+
+        match ids.is_empty() {
+            true => Ok(None),
+            false => self.refresh(&ids).await.map(Some),
+        }
+    }
+}
+
+mod endpoint {
+    pub(super) const WEBHOOK: &str = "webhook";
+    pub(super) const REFRESH: &str = "refresh";
+}
@@ -0,0 +1,378 @@
+//! An embedded HTTP server for receiving webhook events, requires the
+//! `receiver` feature.
+//!
+//! Events are parsed into an `EventHandle` and passed to every handler
+//! registered for their kind. When a secret is set, events must carry a
+//! valid `X-Hub-Signature` (a HMAC-SHA256 of the body) or are rejected.
+//!
+//! ```ignore
+//! let receiver = Receiver::new().with(|this| {
+//!     this.secret("It's a Secret to Everybody")
+//!         .on(EventKind::IssueCreated, |event| {
+//!             println!("created {}", event.data().issue.as_ref().unwrap().key)
+//!         })
+//! });
+//!
+//! receiver.serve(([127, 0, 0, 1], 8080).into()).await?;
+//! ```
+
+pub use crate::error::ReceiveError;
+
+use {
+    super::models::{EventHandle, EventKind},
+    hyper::{
+        body::HttpBody,
+        header,
+        service::{make_service_fn, service_fn},
+        Body, Method, Request, Response, Server, StatusCode,
+    },
+    ring::hmac,
+    serde_json::{self as json, value::RawValue as RawJson},
+    std::{convert::Infallible, fmt, future::Future, net::SocketAddr, sync::Arc},
+    tracing::{debug, warn},
+};
+
+/// The header Jira signs events with
+pub const SIGNATURE_HEADER: &str = "X-Hub-Signature";
+
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+/// The largest request body accepted by default, 1 MiB
+pub const DEFAULT_BODY_LIMIT: usize = 1024 * 1024;
+
+type Handler = Box<dyn Fn(&EventHandle) + Send + Sync>;
+
+/// Verifies and dispatches webhook events to handlers
+pub struct Receiver {
+    secret: Option<hmac::Key>,
+    handlers: Vec<(Option<EventKind>, Handler)>,
+    body_limit: usize,
+}
+
+impl Receiver {
+    /// Instantiate a new receiver, with no secret or handlers
+    pub fn new() -> Self {
+        Self {
+            secret: None,
+            handlers: Vec::new(),
+            body_limit: DEFAULT_BODY_LIMIT,
+        }
+    }
+
+    /// The secret the webhook was registered with. If set, events
+    /// without a valid signature are rejected
+    pub fn secret<T>(&mut self, secret: impl Into<Option<T>>) -> &mut Self
+    where
+        T: AsRef<[u8]>,
+    {
+        self.secret = secret
+            .into()
+            .map(|s| hmac::Key::new(hmac::HMAC_SHA256, s.as_ref()));
+        self
+    }
+
+    /// The largest request body the server will read, in bytes. Bodies are
+    /// read before their signature can be checked, so larger requests are
+    /// rejected with 413 Payload Too Large
+    pub fn body_limit(&mut self, bytes: usize) -> &mut Self {
+        self.body_limit = bytes;
+        self
+    }
+
+    /// Call the handler for every event of the given kind
+    pub fn on<F>(&mut self, kind: EventKind, handler: F) -> &mut Self
+    where
+        F: Fn(&EventHandle) + Send + Sync + 'static,
+    {
+        self.handlers.push((Some(kind), Box::new(handler)));
+        self
+    }
+
+    /// Call the handler for every event
+    pub fn on_any<F>(&mut self, handler: F) -> &mut Self
+    where
+        F: Fn(&EventHandle) + Send + Sync + 'static,
+    {
+        self.handlers.push((None, Box::new(handler)));
+        self
+    }
+
+    /// Helper function for emulating a builder pattern
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+
+    /// Check an event's signature, the value of its `X-Hub-Signature`
+    /// header. Always succeeds if no secret is set
+    pub fn verify(&self, signature: Option<&str>, body: &[u8]) -> Result<(), ReceiveError> {
+        let key = match self.secret {
+            Some(ref key) => key,
+            None => return Ok(()),
+        };
+
+        let digest = signature
+            .ok_or(ReceiveError::MissingSignature)?
+            .strip_prefix(SIGNATURE_PREFIX)
+            .and_then(|hex| hex::decode(hex).ok())
+            .ok_or(ReceiveError::MalformedSignature)?;
+
+        hmac::verify(key, body, &digest).map_err(|_| ReceiveError::BadSignature)
+    }
+
+    /// Verify and parse an event, passing it to each matching handler.
+    /// This is what the server calls for every request, and may be used
+    /// to embed the receiver in another server
+    pub fn receive(
+        &self,
+        signature: Option<&str>,
+        body: &[u8],
+    ) -> Result<EventHandle, ReceiveError> {
+        self.verify(signature, body)?;
+
+        let event = EventHandle::try_new(json::from_slice::<Box<RawJson>>(body)?)?;
+        let kind = event.data().kind();
+        debug!(event = %event.data().webhook_event, "received webhook event");
+
+        self.handlers
+            .iter()
+            .filter(|(on, _)| on.is_none_or(|on| on == kind))
+            .for_each(|(_, handler)| handler(&event));
+
+        Ok(event)
+    }
+
+    /// Listen for events on the given address, until an error occurs
+    pub async fn serve(self, addr: SocketAddr) -> Result<(), ReceiveError> {
+        self.serve_until(addr, std::future::pending()).await
+    }
+
+    /// Listen for events on the given address until the `shutdown`
+    /// future completes, then finish any in flight requests
+    pub async fn serve_until<F>(self, addr: SocketAddr, shutdown: F) -> Result<(), ReceiveError>
+    where
+        F: Future<Output = ()>,
+    {
+        let this = Arc::new(self);
+        let service = make_service_fn(move |_| {
+            let this = this.clone();
+
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let this = this.clone();
+
+                    async move { Ok::<_, Infallible>(this.respond(request).await) }
+                }))
+            }
+        });
+
+        Server::try_bind(&addr)?
+            .serve(service)
+            .with_graceful_shutdown(shutdown)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn respond(&self, request: Request<Body>) -> Response<Body> {
+        if request.method() != Method::POST {
+            return status(StatusCode::METHOD_NOT_ALLOWED);
+        }
+
+        let signature = request
+            .headers()
+            .get(SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = match self.read_body(request).await {
+            Ok(body) => body,
+            Err(code) => return status(code),
+        };
+
+        match self.receive(signature.as_deref(), &body) {
+            Ok(_) => status(StatusCode::NO_CONTENT),
+            Err(e) => {
+                warn!(error = %e, "rejected webhook request");
+
+                match e {
+                    ReceiveError::Payload(_) => status(StatusCode::BAD_REQUEST),
+                    _ => status(StatusCode::UNAUTHORIZED),
+                }
+            }
+        }
+    }
+
+    /// Read a request's body, up to the body limit
+    async fn read_body(&self, request: Request<Body>) -> Result<Vec<u8>, StatusCode> {
+        let too_large = |size: u64| {
+            warn!(
+                size,
+                limit = self.body_limit,
+                "rejected oversized webhook request"
+            );
+            StatusCode::PAYLOAD_TOO_LARGE
+        };
+
+        let length = request
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok()?.parse::<u64>().ok());
+        if let Some(length) = length.filter(|l| *l > self.body_limit as u64) {
+            return Err(too_large(length));
+        }
+
+        // The Content-Length may be missing, or a lie
+        let mut body = request.into_body();
+        let mut data = Vec::new();
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk.map_err(|e| {
+                warn!(error = %e, "unable to read webhook request");
+                StatusCode::BAD_REQUEST
+            })?;
+
+            if data.len() + chunk.len() > self.body_limit {
+                return Err(too_large((data.len() + chunk.len()) as u64));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        Ok(data)
+    }
+}
+
+impl Default for Receiver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Receiver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("secret", &self.secret.as_ref().map(|_| "<redacted>"))
+            .field("handlers", &self.handlers.len())
+            .field("body_limit", &self.body_limit)
+            .finish()
+    }
+}
+
+fn status(code: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = code;
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::models::webhook::types,
+        std::sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    const SECRET: &str = "It's a Secret to Everybody";
+
+    fn sign(body: &[u8]) -> String {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, SECRET.as_bytes());
+
+        format!("sha256={}", hex::encode(hmac::sign(&key, body)))
+    }
+
+    #[test]
+    fn dispatch_by_kind() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let any = Arc::new(AtomicUsize::new(0));
+        let receiver = Receiver::new().with(|this| {
+            let (created, any) = (created.clone(), any.clone());
+
+            this.on(EventKind::IssueCreated, move |_| {
+                created.fetch_add(1, Ordering::SeqCst);
+            })
+            .on_any(move |_| {
+                any.fetch_add(1, Ordering::SeqCst);
+            })
+        });
+
+        for event in [types::issue_updated(), types::comment_created()].iter() {
+            let body = json::to_vec(event).unwrap();
+            receiver.receive(None, &body).expect("an unsigned event");
+        }
+
+        assert_eq!(created.load(Ordering::SeqCst), 0);
+        assert_eq!(any.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn verify_signatures() {
+        let receiver = Receiver::new().with(|this| this.secret(SECRET));
+        let body = json::to_vec(&types::issue_updated()).unwrap();
+
+        assert!(receiver.receive(Some(&sign(&body)), &body).is_ok());
+        assert!(matches!(
+            receiver.receive(None, &body),
+            Err(ReceiveError::MissingSignature)
+        ));
+        assert!(matches!(
+            receiver.receive(Some("md5=abc"), &body),
+            Err(ReceiveError::MalformedSignature)
+        ));
+        assert!(matches!(
+            receiver.receive(Some(&sign(b"{}")), &body),
+            Err(ReceiveError::BadSignature)
+        ));
+    }
+
+    fn post(body: Body) -> Request<Body> {
+        Request::post("/").body(body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn limit_body_size() {
+        let receiver = Receiver::new().with(|this| this.body_limit(64));
+        let event = json::to_vec(&types::issue_updated()).unwrap();
+        let respond = |request| receiver.respond(request);
+
+        // Rejected by its Content-Length, before it is read
+        let request = Request::post("/")
+            .header(header::CONTENT_LENGTH, event.len())
+            .body(Body::empty())
+            .unwrap();
+        let response = respond(request).await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // Without a Content-Length, it is cut off as it is read
+        let response = respond(post(Body::from(event.clone()))).await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // Including when it arrives in chunks
+        let (mut sender, body) = Body::channel();
+        let chunks: Vec<Vec<u8>> = event.chunks(16).map(<[u8]>::to_vec).collect();
+        tokio::spawn(async move {
+            for chunk in chunks {
+                if sender.send_data(chunk.into()).await.is_err() {
+                    break;
+                }
+            }
+        });
+        let response = respond(post(body)).await;
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let receiver = Receiver::new().with(|this| this.body_limit(event.len()));
+        let response = receiver.respond(post(Body::from(event))).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[test]
+    fn reject_invalid_payloads() {
+        let receiver = Receiver::new();
+
+        assert!(matches!(
+            receiver.receive(None, br#"{"timestamp": 1}"#),
+            Err(ReceiveError::Payload(_))
+        ));
+    }
+}
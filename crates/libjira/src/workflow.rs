@@ -0,0 +1,65 @@
+pub use crate::{models::workflow as models, options::workflow as options};
+
+use {
+    self::endpoint::*,
+    crate::{client::Jira, error::JiraError},
+    models::{WorkflowSchemeProjectsHandle, WorkflowSearchHandle},
+    reqwest::RequestBuilder,
+};
+
+/// A handle for inspecting JIRA workflows and workflow schemes
+///
+/// It is cheap to clone a handle and may be done liberally
+#[derive(Debug, Clone)]
+pub struct Workflows {
+    client: Jira,
+}
+
+impl Workflows {
+    /// Create a new handle from a JIRA client
+    pub fn new(client: &Jira) -> Self {
+        Self {
+            client: client.clone(),
+        }
+    }
+
+    /// Search the instance's workflows
+    ///
+    /// Note that Jira only returns the id and description of each
+    /// workflow unless more are requested via the options' expands
+    pub async fn search(
+        &self,
+        options: Option<&options::Search>,
+    ) -> Result<WorkflowSearchHandle, JiraError> {
+        let handler = |req: RequestBuilder| match options {
+            Some(options) => Ok(req.query(options)),
+            None => Ok(req),
+        };
+
+        self.client
+            .get(&[WORKFLOW, SEARCH], handler)?
+            .retrieve()
+            .await
+    }
+
+    /// Retrieve the workflow scheme used by a project, by its id
+    pub async fn scheme_for_project(
+        &self,
+        project_id: u64,
+    ) -> Result<WorkflowSchemeProjectsHandle, JiraError> {
+        let handler = |req: RequestBuilder| Ok(req.query(&[(PROJECT_ID, project_id)]));
+
+        self.client
+            .get(&[WORKFLOW_SCHEME, PROJECT], handler)?
+            .retrieve()
+            .await
+    }
+}
+
+mod endpoint {
+    pub(super) const WORKFLOW: &str = "workflow";
+    pub(super) const SEARCH: &str = "search";
+    pub(super) const WORKFLOW_SCHEME: &str = "workflowscheme";
+    pub(super) const PROJECT: &str = "project";
+    pub(super) const PROJECT_ID: &str = "projectId";
+}
@@ -0,0 +1,179 @@
+// The generated code must not warn in user crates
+#![deny(unused_imports)]
+
+use {
+    jira_rs::issue::{
+        models::IssueHandle,
+        payload::{FieldNames, JiraIssue as _, SchemaError},
+    },
+    jira_rs_derive::JiraIssue,
+    pretty_assertions::assert_eq,
+    serde_json::{json, Value as Json},
+};
+
+#[derive(Debug, PartialEq, JiraIssue)]
+struct Story {
+    #[jira(key)]
+    key: String,
+    #[jira(id)]
+    id: u64,
+    summary: String,
+    #[jira(name = "Story Points")]
+    points: Option<f64>,
+    #[jira(field = "labels", update = "add")]
+    labels: Vec<String>,
+    #[jira(field = "customfield_10020", default)]
+    sprint: Vec<u64>,
+    #[jira(read_only)]
+    status: Json,
+    #[jira(skip)]
+    local: bool,
+}
+
+#[derive(Debug, PartialEq, JiraIssue)]
+struct Reference {
+    #[jira(key)]
+    key: String,
+    #[jira(id)]
+    id: u64,
+    #[jira(skip)]
+    local: bool,
+}
+
+#[derive(Debug, PartialEq, JiraIssue)]
+struct Local {
+    #[jira(skip)]
+    seen: bool,
+}
+
+fn handle(issue: Json) -> IssueHandle {
+    serde_json::from_value(issue).expect("a valid issue")
+}
+
+fn issue() -> Json {
+    json!({
+        "self": "foo",
+        "id": "42",
+        "key": "FOO-1",
+        "expand": "names",
+        "fields": {
+            "summary": "foo",
+            "customfield_10016": 3.0,
+            "labels": ["bar", "baz"],
+            "status": { "name": "Done" },
+        },
+        "names": {
+            "summary": "Summary",
+            "customfield_10016": "Story Points",
+        },
+    })
+}
+
+fn story() -> Story {
+    Story {
+        key: "FOO-1".into(),
+        id: 42,
+        summary: "foo".into(),
+        points: Some(3.0),
+        labels: vec!["bar".into(), "baz".into()],
+        sprint: Vec::new(),
+        status: json!({ "name": "Done" }),
+        local: false,
+    }
+}
+
+#[test]
+fn from_issue() {
+    let story: Story = handle(issue()).parse().expect("a valid story");
+
+    assert_eq!(story, self::story())
+}
+
+#[test]
+fn from_issue_missing_names() {
+    let mut issue = issue();
+    issue.as_object_mut().unwrap().remove("names");
+
+    let story: Result<Story, _> = handle(issue).parse();
+
+    assert!(matches!(story, Err(SchemaError::UnknownName(_))))
+}
+
+#[test]
+fn from_issue_missing_required() {
+    let mut issue = issue();
+    issue["fields"].as_object_mut().unwrap().remove("summary");
+
+    let story: Result<Story, _> = handle(issue).parse();
+
+    assert!(matches!(story, Err(SchemaError::MissingField(_))))
+}
+
+#[test]
+fn to_payload() {
+    let names: FieldNames = Some(("Story Points", "customfield_10016"))
+        .into_iter()
+        .collect();
+
+    let payload = story().to_payload(&names).expect("a valid payload");
+
+    assert_eq!(
+        serde_json::to_value(&payload).unwrap(),
+        json!({
+            "fields": {
+                "summary": "foo",
+                "customfield_10016": 3.0,
+                "customfield_10020": [],
+            },
+            "update": {
+                "labels": [{ "add": "bar" }, { "add": "baz" }],
+            },
+        })
+    )
+}
+
+#[test]
+fn to_payload_clears() {
+    let names: FieldNames = Some(("Story Points", "customfield_10016"))
+        .into_iter()
+        .collect();
+    let story = Story {
+        points: None,
+        labels: Vec::new(),
+        ..story()
+    };
+
+    let payload = story.to_payload(&names).expect("a valid payload");
+
+    assert_eq!(
+        serde_json::to_value(&payload).unwrap(),
+        json!({
+            "fields": {
+                "summary": "foo",
+                "customfield_10016": null,
+                "customfield_10020": [],
+            },
+        })
+    )
+}
+
+#[test]
+fn reference_only() {
+    let reference: Reference = handle(issue()).parse().expect("a valid reference");
+
+    assert_eq!(
+        reference,
+        Reference {
+            key: "FOO-1".into(),
+            id: 42,
+            local: false,
+        }
+    );
+    assert!(reference
+        .to_payload(&FieldNames::default())
+        .unwrap()
+        .is_empty());
+
+    let local: Local = handle(issue()).parse().expect("a valid local");
+    assert_eq!(local, Local { seen: false })
+}
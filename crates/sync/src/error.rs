@@ -0,0 +1,15 @@
+use {crate::jql::JqlError, jira_rs::client::JiraError, thiserror::Error};
+
+#[derive(Debug, Error)]
+pub enum SyncError {
+    #[error(transparent)]
+    Jira(#[from] JiraError),
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Jql(#[from] JqlError),
+    #[error("Unsupported mirror schema version {}, expected at most {}", .found, .supported)]
+    SchemaVersion { found: i64, supported: i64 },
+}
@@ -0,0 +1,1044 @@
+//! A subset of JQL, evaluated against mirrored issues.
+//!
+//! Supported are `AND`, `OR`, `NOT` and parentheses over clauses of the
+//! form `field <op> value`, where op is one of `=`, `!=`, `~`, `!~`, `>`,
+//! `>=`, `<`, `<=`, `IN`, `NOT IN`, `IS` and `IS NOT`, followed by an
+//! optional `ORDER BY`.
+//!
+//! Values may be quoted strings, bare words, `EMPTY`/`NULL`, relative
+//! dates (e.g `-7d`, `2w`) and the functions `currentUser()`, `now()`,
+//! `startOfDay()` and `endOfDay()`. Dates are in the local timezone.
+//!
+//! Fields are matched by id (e.g `summary`, `customfield_10000`), with
+//! the usual aliases (`type`, `component`, `fixVersion`, `cf[10000]`...).
+//! Object valued fields (statuses, users, versions...) match on any of
+//! their names, keys or ids, case insensitively. As in Jira, negated
+//! operators never match empty fields.
+
+use {
+    chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveTime, TimeZone, Utc},
+    jira_rs::{issue::models::Issue, time::parse_datetime},
+    serde_json::{self as json, Value},
+    std::{cmp::Ordering, fmt},
+    thiserror::Error,
+};
+
+/// The object keys an object valued field matches on
+const OBJECT_KEYS: [&str; 7] = [
+    "key",
+    "name",
+    "value",
+    "displayName",
+    "emailAddress",
+    "accountId",
+    "id",
+];
+
+const DATE_FIELDS: [&str; 5] = [
+    "created",
+    "updated",
+    "resolutiondate",
+    "duedate",
+    "lastViewed",
+];
+
+/// A JQL query which can't be evaluated offline
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("Unable to evaluate JQL offline, at column {}: {}", .position + 1, .message)]
+pub struct JqlError {
+    /// The offset into the query of the problem
+    pub position: usize,
+    pub message: String,
+}
+
+/// A parsed JQL query
+#[derive(Debug, Clone, PartialEq)]
+pub struct Jql {
+    filter: Option<Expr>,
+    order: Vec<(String, Direction)>,
+}
+
+/// What JQL functions may need to know
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    /// The name of the user `currentUser()` refers to
+    pub current_user: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Clause(Clause),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Clause {
+    field: String,
+    op: Op,
+    operand: Operand,
+    position: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Like,
+    NotLike,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    In,
+    NotIn,
+    Is,
+    IsNot,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Empty,
+    Value(Term),
+    List(Vec<Term>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    Text(String),
+    Function(String, Vec<String>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    Asc,
+    Desc,
+}
+
+impl Jql {
+    /// Parse the given query, failing on anything outside the supported subset
+    pub fn parse(jql: &str) -> Result<Self, JqlError> {
+        let tokens = lex(jql)?;
+        let mut parser = Parser {
+            tokens,
+            next: 0,
+            end: jql.len(),
+        };
+
+        let filter = match parser.peek() {
+            None => None,
+            Some(t) if t.is_word("order") => None,
+            Some(_) => Some(parser.or()?),
+        };
+        let order = parser.order_by()?;
+
+        match parser.peek_token() {
+            Some(token) => Err(error(token.position, "expected AND, OR or ORDER BY")),
+            None => Ok(Self { filter, order }),
+        }
+    }
+
+    /// Does the issue match this query's filter?
+    pub fn matches(&self, issue: &Issue<'_>, context: &Context) -> Result<bool, JqlError> {
+        match self.filter {
+            Some(ref filter) => filter.eval(issue, context),
+            None => Ok(true),
+        }
+    }
+
+    /// Sort issues by this query's ORDER BY, or by descending id (i.e
+    /// newest first) if it has none
+    pub fn compare(&self, a: &Issue<'_>, b: &Issue<'_>) -> Ordering {
+        for (field, direction) in self.order.iter() {
+            let ordering = compare_values(field, &values(a, field), &values(b, field));
+            let ordering = match direction {
+                Direction::Asc => ordering,
+                Direction::Desc => ordering.reverse(),
+            };
+
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+
+        b.id.cmp(&a.id)
+    }
+}
+
+impl Expr {
+    fn eval(&self, issue: &Issue<'_>, context: &Context) -> Result<bool, JqlError> {
+        Ok(match self {
+            Expr::And(a, b) => a.eval(issue, context)? && b.eval(issue, context)?,
+            Expr::Or(a, b) => a.eval(issue, context)? || b.eval(issue, context)?,
+            Expr::Not(a) => !a.eval(issue, context)?,
+            Expr::Clause(clause) => clause.eval(issue, context)?,
+        })
+    }
+}
+
+impl Clause {
+    fn eval(&self, issue: &Issue<'_>, context: &Context) -> Result<bool, JqlError> {
+        let found = values(issue, &self.field);
+
+        let terms = match (&self.op, &self.operand) {
+            (Op::Eq, Operand::Empty) | (Op::Is, _) => return Ok(found.is_empty()),
+            (Op::Ne, Operand::Empty) | (Op::IsNot, _) => return Ok(!found.is_empty()),
+            (_, Operand::Empty) => {
+                return Err(self.error("EMPTY can only be used with =, != and IS"))
+            }
+            (Op::In, Operand::List(terms)) | (Op::NotIn, Operand::List(terms)) => terms.as_slice(),
+            // List functions, e.g membersOf(), are left to resolve() to reject
+            (Op::In, Operand::Value(Term::Text(_)))
+            | (Op::NotIn, Operand::Value(Term::Text(_))) => {
+                return Err(self.error("IN expects a list of values"))
+            }
+            (_, Operand::List(_)) => return Err(self.error("a list of values requires IN")),
+            (_, Operand::Value(term)) => std::slice::from_ref(term),
+        };
+
+        let mut expected = Vec::with_capacity(terms.len());
+        for term in terms {
+            expected.push(self.resolve(term, context)?);
+        }
+
+        let equals = |value: &str| expected.iter().any(|e| self.equal(value, e));
+
+        Ok(match self.op {
+            Op::Eq | Op::In => found.iter().any(|v| equals(v)),
+            Op::Ne | Op::NotIn => !found.is_empty() && !found.iter().any(|v| equals(v)),
+            Op::Like => found.iter().any(|v| contains(v, &expected[0])),
+            Op::NotLike => !found.is_empty() && !found.iter().any(|v| contains(v, &expected[0])),
+            Op::Gt | Op::Ge | Op::Lt | Op::Le => {
+                let mut matched = false;
+                for value in found.iter() {
+                    let ordering = self.order(value, &expected[0])?;
+                    matched |= match self.op {
+                        Op::Gt => ordering == Ordering::Greater,
+                        Op::Ge => ordering != Ordering::Less,
+                        Op::Lt => ordering == Ordering::Less,
+                        _ => ordering != Ordering::Greater,
+                    };
+                }
+                matched
+            }
+            Op::Is | Op::IsNot => unreachable!("handled above"),
+        })
+    }
+
+    /// Resolve a term to the text it compares as
+    fn resolve(&self, term: &Term, context: &Context) -> Result<String, JqlError> {
+        let (name, args) = match term {
+            Term::Text(text) => return Ok(text.clone()),
+            Term::Function(name, args) => (name.to_ascii_lowercase(), args.as_slice()),
+        };
+        let offset = match args {
+            [] => Duration::zero(),
+            [offset] => relative(offset)
+                .ok_or_else(|| self.error(format!("invalid offset '{}'", offset)))?,
+            _ => return Err(self.error(format!("too many arguments to {}()", name))),
+        };
+
+        let today = Local::now().date_naive();
+        let instant = match name.as_str() {
+            "currentuser" => {
+                return context
+                    .current_user
+                    .clone()
+                    .ok_or_else(|| self.error("currentUser() requires a configured user"))
+            }
+            "now" => Local::now().fixed_offset(),
+            "startofday" => local_instant(today, NaiveTime::MIN),
+            "endofday" => local_instant(today, NaiveTime::MIN) + Duration::days(1),
+            _ => return Err(self.error(format!("unsupported function {}()", name))),
+        };
+
+        instant
+            .checked_add_signed(offset)
+            .map(|instant| instant.to_rfc3339())
+            .ok_or_else(|| self.error(format!("{}() is out of range", name)))
+    }
+
+    fn equal(&self, value: &str, expected: &str) -> bool {
+        if is_date_field(&self.field) {
+            if let (Some(a), Some(b)) = (instant(value), instant(expected)) {
+                return a == b;
+            }
+        }
+
+        value.eq_ignore_ascii_case(expected)
+            || matches!((number(value), number(expected)), (Some(a), Some(b)) if a == b)
+    }
+
+    fn order(&self, value: &str, expected: &str) -> Result<Ordering, JqlError> {
+        if is_date_field(&self.field) {
+            let expected = instant(expected)
+                .ok_or_else(|| self.error(format!("'{}' is not a date", expected)))?;
+
+            return Ok(instant(value)
+                .map(|value| value.cmp(&expected))
+                .unwrap_or(Ordering::Less));
+        }
+
+        if self.field == "key" {
+            return Ok(compare_keys(value, expected));
+        }
+
+        match (number(value), number(expected)) {
+            (Some(a), Some(b)) => Ok(a.partial_cmp(&b).unwrap_or(Ordering::Equal)),
+            _ => Err(self.error(format!(
+                "'{}' can only be compared with =, !=, IN and ~ offline",
+                self.field
+            ))),
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> JqlError {
+        error(self.position, message)
+    }
+}
+
+/// The textual values of a field, empty if the field is
+fn values(issue: &Issue<'_>, field: &str) -> Vec<String> {
+    let mut found = Vec::new();
+
+    match field {
+        "key" => found.push(issue.key.to_string()),
+        "id" => found.push(issue.id.to_string()),
+        "text" => {
+            for field in ["summary", "description", "environment"].iter() {
+                found.extend(values(issue, field))
+            }
+        }
+        field => {
+            if let Some(raw) = issue.fields.get(field) {
+                if let Ok(value) = json::from_str::<Value>(raw.get()) {
+                    flatten(&value, &mut found);
+                }
+            }
+        }
+    }
+
+    found.retain(|v| !v.is_empty());
+    found
+}
+
+fn flatten(value: &Value, into: &mut Vec<String>) {
+    match value {
+        Value::Null => {}
+        Value::String(s) => into.push(s.clone()),
+        Value::Number(n) => into.push(n.to_string()),
+        Value::Bool(b) => into.push(b.to_string()),
+        Value::Array(values) => values.iter().for_each(|v| flatten(v, into)),
+        Value::Object(object) => {
+            for key in OBJECT_KEYS.iter() {
+                match object.get(*key) {
+                    Some(Value::String(s)) => into.push(s.clone()),
+                    Some(Value::Number(n)) => into.push(n.to_string()),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Resolve the aliases JQL allows for field ids
+fn field_id(name: &str) -> String {
+    let lower = name.to_ascii_lowercase();
+
+    if let Some(id) = lower
+        .strip_prefix("cf[")
+        .and_then(|rest| rest.strip_suffix(']'))
+    {
+        return format!("customfield_{}", id);
+    }
+
+    match lower.as_str() {
+        "issue" | "issuekey" | "key" => "key",
+        "id" => "id",
+        "type" | "issuetype" => "issuetype",
+        "component" | "components" => "components",
+        "fixversion" | "fixversions" => "fixVersions",
+        "affectedversion" | "versions" => "versions",
+        "due" | "duedate" => "duedate",
+        "createddate" | "created" => "created",
+        "updateddate" | "updated" => "updated",
+        "resolved" | "resolutiondate" => "resolutiondate",
+        "label" | "labels" => "labels",
+        "project" | "status" | "priority" | "resolution" | "assignee" | "reporter" | "creator"
+        | "summary" | "description" | "environment" | "parent" | "text" => lower.as_str(),
+        _ => return name.to_string(),
+    }
+    .to_string()
+}
+
+fn is_date_field(field: &str) -> bool {
+    DATE_FIELDS.contains(&field)
+}
+
+fn contains(value: &str, expected: &str) -> bool {
+    let value = value.to_lowercase();
+
+    // Every word must appear, approximating Jira's full text search
+    expected
+        .to_lowercase()
+        .split_whitespace()
+        .map(|word| word.trim_matches('*'))
+        .all(|word| value.contains(word))
+}
+
+fn number(s: &str) -> Option<f64> {
+    s.parse().ok()
+}
+
+/// Order issue keys by project, then number
+fn compare_keys(a: &str, b: &str) -> Ordering {
+    let split = |key: &str| match key.rsplit_once('-') {
+        Some((project, n)) => (project.to_ascii_uppercase(), n.parse::<u64>().ok()),
+        None => (key.to_ascii_uppercase(), None),
+    };
+
+    split(a).cmp(&split(b))
+}
+
+fn compare_values(field: &str, a: &[String], b: &[String]) -> Ordering {
+    // Empty values sort last
+    let (a, b) = match (a.first(), b.first()) {
+        (Some(a), Some(b)) => (a, b),
+        (Some(_), None) => return Ordering::Less,
+        (None, Some(_)) => return Ordering::Greater,
+        (None, None) => return Ordering::Equal,
+    };
+
+    if field == "key" {
+        return compare_keys(a, b);
+    }
+
+    if let (Some(a), Some(b)) = (instant(a), instant(b)) {
+        return a.cmp(&b);
+    }
+
+    match (number(a), number(b)) {
+        (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+/// Parse an absolute or relative date, as found in JQL or an issue
+fn instant(s: &str) -> Option<DateTime<FixedOffset>> {
+    if let Ok(datetime) = parse_datetime(s) {
+        return Some(datetime);
+    }
+
+    for format in ["%Y-%m-%d %H:%M", "%Y/%m/%d %H:%M"].iter() {
+        if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(s, format) {
+            return Local
+                .from_local_datetime(&naive)
+                .earliest()
+                .map(|t| t.fixed_offset());
+        }
+    }
+
+    for format in ["%Y-%m-%d", "%Y/%m/%d"].iter() {
+        if let Ok(date) = NaiveDate::parse_from_str(s, format) {
+            return Some(local_instant(date, NaiveTime::MIN));
+        }
+    }
+
+    relative(s).and_then(|offset| Local::now().fixed_offset().checked_add_signed(offset))
+}
+
+fn local_instant(date: NaiveDate, time: NaiveTime) -> DateTime<FixedOffset> {
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|t| t.fixed_offset())
+        .unwrap_or_else(|| Utc.from_utc_datetime(&date.and_time(time)).fixed_offset())
+}
+
+/// Parse a relative duration, e.g '-7d', '2w', '+4h', '-30m'. None if
+/// malformed or too large to represent
+fn relative(s: &str) -> Option<Duration> {
+    let (sign, rest) = match s.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, s.strip_prefix('+').unwrap_or(s)),
+    };
+
+    let split = rest.find(|c: char| !c.is_ascii_digit())?;
+    let (n, unit) = rest.split_at(split);
+    let n: i64 = n.parse().ok()?;
+
+    let duration = match unit {
+        "w" => Duration::try_weeks(n),
+        "d" => Duration::try_days(n),
+        "h" => Duration::try_hours(n),
+        "m" => Duration::try_minutes(n),
+        _ => None,
+    };
+
+    duration.map(|duration| duration * sign)
+}
+
+fn error(position: usize, message: impl Into<String>) -> JqlError {
+    JqlError {
+        position,
+        message: message.into(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: Kind,
+    position: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Word(String),
+    Quoted(String),
+    Op(&'static str),
+    Open,
+    Close,
+    Comma,
+}
+
+impl Token {
+    fn is_word(&self, word: &str) -> bool {
+        matches!(self.kind, Kind::Word(ref w) if w.eq_ignore_ascii_case(word))
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Word(w) => write!(f, "'{}'", w),
+            Kind::Quoted(q) => write!(f, "\"{}\"", q),
+            Kind::Op(op) => write!(f, "'{}'", op),
+            Kind::Open => f.write_str("'('"),
+            Kind::Close => f.write_str("')'"),
+            Kind::Comma => f.write_str("','"),
+        }
+    }
+}
+
+fn lex(jql: &str) -> Result<Vec<Token>, JqlError> {
+    const OPS: [&str; 11] = ["!=", "!~", ">=", "<=", "&&", "||", "=", "~", ">", "<", "!"];
+
+    let mut tokens = Vec::new();
+    let mut chars = jql.char_indices().peekable();
+
+    while let Some(&(position, c)) = chars.peek() {
+        let kind = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '(' => Kind::Open,
+            ')' => Kind::Close,
+            ',' => Kind::Comma,
+            '"' | '\'' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, escaped)) => text.push(escaped),
+                            None => break,
+                        },
+                        Some((_, q)) if q == c => break,
+                        Some((_, other)) => text.push(other),
+                        None => return Err(error(position, "unterminated string")),
+                    }
+                }
+                tokens.push(Token {
+                    kind: Kind::Quoted(text),
+                    position,
+                });
+                continue;
+            }
+            _ => match OPS.iter().find(|op| jql[position..].starts_with(*op)) {
+                Some(op) => {
+                    for _ in 0..op.len() {
+                        chars.next();
+                    }
+                    tokens.push(Token {
+                        kind: Kind::Op(op),
+                        position,
+                    });
+                    continue;
+                }
+                None if is_word_char(c) => {
+                    let mut word = String::new();
+                    while let Some(&(_, c)) = chars.peek() {
+                        // Allow a '-' inside a word (e.g FOO-1, -7d, 2021-03-04)
+                        if !is_word_char(c) && c != '-' {
+                            break;
+                        }
+                        word.push(c);
+                        chars.next();
+                    }
+                    tokens.push(Token {
+                        kind: Kind::Word(word),
+                        position,
+                    });
+                    continue;
+                }
+                None if c == '-' || c == '+' => {
+                    let mut word = c.to_string();
+                    chars.next();
+                    while let Some(&(_, c)) = chars.peek() {
+                        if !is_word_char(c) {
+                            break;
+                        }
+                        word.push(c);
+                        chars.next();
+                    }
+                    tokens.push(Token {
+                        kind: Kind::Word(word),
+                        position,
+                    });
+                    continue;
+                }
+                None => return Err(error(position, format!("unexpected character '{}'", c))),
+            },
+        };
+
+        chars.next();
+        tokens.push(Token { kind, position });
+    }
+
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | ':' | '@' | '[' | ']' | '/' | '*')
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    next: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn peek_token(&self) -> Option<Token> {
+        self.peek().cloned()
+    }
+
+    fn bump(&mut self) -> Option<Token> {
+        let token = self.peek_token();
+        self.next += 1;
+        token
+    }
+
+    fn position(&self) -> usize {
+        self.peek().map(|t| t.position).unwrap_or(self.end)
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        match self.peek() {
+            Some(t) if t.is_word(word) => {
+                self.next += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn eat_op(&mut self, ops: &[&str]) -> bool {
+        match self.peek() {
+            Some(Token {
+                kind: Kind::Op(op), ..
+            }) if ops.contains(op) => {
+                self.next += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, JqlError> {
+        let mut expr = self.and()?;
+        while self.eat_word("or") || self.eat_op(&["||"]) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, JqlError> {
+        let mut expr = self.unary()?;
+        while self.eat_word("and") || self.eat_op(&["&&"]) {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, JqlError> {
+        if self.eat_word("not") || self.eat_op(&["!"]) {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+
+        if let Some(Token {
+            kind: Kind::Open, ..
+        }) = self.peek()
+        {
+            self.next += 1;
+            let expr = self.or()?;
+            return match self.bump() {
+                Some(Token {
+                    kind: Kind::Close, ..
+                }) => Ok(expr),
+                _ => Err(error(self.position(), "expected ')'")),
+            };
+        }
+
+        self.clause().map(Expr::Clause)
+    }
+
+    fn clause(&mut self) -> Result<Clause, JqlError> {
+        let position = self.position();
+        let field = match self.bump() {
+            Some(Token {
+                kind: Kind::Word(w),
+                ..
+            })
+            | Some(Token {
+                kind: Kind::Quoted(w),
+                ..
+            }) => field_id(&w),
+            Some(t) => {
+                return Err(error(
+                    t.position,
+                    format!("expected a field, found {}", t.kind),
+                ))
+            }
+            None => return Err(error(position, "expected a field")),
+        };
+
+        let op_position = self.position();
+        let op = match self.bump() {
+            Some(Token {
+                kind: Kind::Op(op), ..
+            }) => match op {
+                "=" => Op::Eq,
+                "!=" => Op::Ne,
+                "~" => Op::Like,
+                "!~" => Op::NotLike,
+                ">" => Op::Gt,
+                ">=" => Op::Ge,
+                "<" => Op::Lt,
+                "<=" => Op::Le,
+                op => return Err(error(op_position, format!("unexpected '{}'", op))),
+            },
+            Some(t) if t.is_word("in") => Op::In,
+            Some(t) if t.is_word("not") && self.eat_word("in") => Op::NotIn,
+            Some(t) if t.is_word("is") => match self.eat_word("not") {
+                true => Op::IsNot,
+                false => Op::Is,
+            },
+            Some(t) if t.is_word("was") || t.is_word("changed") => {
+                return Err(error(
+                    op_position,
+                    "history operators (WAS, CHANGED) are not supported offline",
+                ))
+            }
+            _ => return Err(error(op_position, "expected an operator")),
+        };
+
+        let operand = self.operand()?;
+        if matches!(op, Op::Is | Op::IsNot) && operand != Operand::Empty {
+            return Err(error(op_position, "IS expects EMPTY or NULL"));
+        }
+
+        Ok(Clause {
+            field,
+            op,
+            operand,
+            position,
+        })
+    }
+
+    fn operand(&mut self) -> Result<Operand, JqlError> {
+        if self.eat_word("empty") || self.eat_word("null") {
+            return Ok(Operand::Empty);
+        }
+
+        if let Some(Token {
+            kind: Kind::Open, ..
+        }) = self.peek()
+        {
+            self.next += 1;
+            let mut terms = vec![self.term()?];
+            loop {
+                match self.bump() {
+                    Some(Token {
+                        kind: Kind::Comma, ..
+                    }) => terms.push(self.term()?),
+                    Some(Token {
+                        kind: Kind::Close, ..
+                    }) => return Ok(Operand::List(terms)),
+                    _ => return Err(error(self.position(), "expected ',' or ')'")),
+                }
+            }
+        }
+
+        self.term().map(Operand::Value)
+    }
+
+    fn term(&mut self) -> Result<Term, JqlError> {
+        let position = self.position();
+
+        match self.bump() {
+            Some(Token {
+                kind: Kind::Quoted(text),
+                ..
+            }) => Ok(Term::Text(text)),
+            Some(Token {
+                kind: Kind::Word(word),
+                ..
+            }) => match self.peek() {
+                Some(Token {
+                    kind: Kind::Open, ..
+                }) => {
+                    self.next += 1;
+                    let mut args = Vec::new();
+                    loop {
+                        match self.bump() {
+                            Some(Token {
+                                kind: Kind::Close, ..
+                            }) => break,
+                            Some(Token {
+                                kind: Kind::Comma, ..
+                            }) => {}
+                            Some(Token {
+                                kind: Kind::Word(arg),
+                                ..
+                            })
+                            | Some(Token {
+                                kind: Kind::Quoted(arg),
+                                ..
+                            }) => args.push(arg),
+                            _ => return Err(error(position, "unterminated function call")),
+                        }
+                    }
+                    Ok(Term::Function(word, args))
+                }
+                _ => Ok(Term::Text(word)),
+            },
+            Some(t) => Err(error(
+                t.position,
+                format!("expected a value, found {}", t.kind),
+            )),
+            None => Err(error(position, "expected a value")),
+        }
+    }
+
+    fn order_by(&mut self) -> Result<Vec<(String, Direction)>, JqlError> {
+        let mut order = Vec::new();
+
+        if !self.eat_word("order") {
+            return Ok(order);
+        }
+        if !self.eat_word("by") {
+            return Err(error(self.position(), "expected BY"));
+        }
+
+        loop {
+            let field = match self.bump() {
+                Some(Token {
+                    kind: Kind::Word(w),
+                    ..
+                })
+                | Some(Token {
+                    kind: Kind::Quoted(w),
+                    ..
+                }) => field_id(&w),
+                _ => return Err(error(self.position(), "expected a field to order by")),
+            };
+            let direction = match () {
+                _ if self.eat_word("desc") => Direction::Desc,
+                _ => {
+                    self.eat_word("asc");
+                    Direction::Asc
+                }
+            };
+            order.push((field, direction));
+
+            match self.peek() {
+                Some(Token {
+                    kind: Kind::Comma, ..
+                }) => self.next += 1,
+                _ => return Ok(order),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jira_rs::issue::models::IssueHandle;
+    use serde_json::{json, value::RawValue as RawJson};
+
+    fn issue(id: u64, key: &str, fields: Value) -> IssueHandle {
+        let raw = json!({
+            "self": "x",
+            "id": id.to_string(),
+            "key": key,
+            "expand": "",
+            "fields": fields,
+        });
+
+        IssueHandle::try_new(RawJson::from_string(raw.to_string()).unwrap()).unwrap()
+    }
+
+    fn sample() -> Vec<IssueHandle> {
+        vec![
+            issue(
+                1,
+                "FOO-1",
+                json!({
+                    "summary": "Fix the login page",
+                    "status": { "name": "In Progress", "id": "3" },
+                    "project": { "key": "FOO", "name": "Foo" },
+                    "assignee": { "name": "ann", "displayName": "Ann" },
+                    "labels": ["backend", "urgent"],
+                    "customfield_10000": 5,
+                    "created": "2021-03-04T10:00:00.000+0000",
+                }),
+            ),
+            issue(
+                2,
+                "FOO-10",
+                json!({
+                    "summary": "Write docs",
+                    "status": { "name": "Done", "id": "5" },
+                    "project": { "key": "FOO", "name": "Foo" },
+                    "assignee": null,
+                    "labels": [],
+                    "customfield_10000": 2,
+                    "created": "2021-03-06T10:00:00.000+0000",
+                }),
+            ),
+            issue(
+                3,
+                "BAR-2",
+                json!({
+                    "summary": "Login times out",
+                    "status": { "name": "To Do", "id": "1" },
+                    "project": { "key": "BAR", "name": "Bar" },
+                    "assignee": { "name": "bob", "displayName": "Bob" },
+                    "labels": ["backend"],
+                    "created": "2021-03-05T10:00:00.000+0000",
+                }),
+            ),
+        ]
+    }
+
+    fn search(jql: &str) -> Vec<String> {
+        let context = Context {
+            current_user: Some("bob".into()),
+        };
+        let query = Jql::parse(jql).unwrap();
+        let issues = sample();
+        let mut matched: Vec<_> = issues
+            .iter()
+            .map(IssueHandle::data)
+            .filter(|issue| query.matches(issue, &context).unwrap())
+            .collect();
+        matched.sort_by(|a, b| query.compare(a, b));
+
+        matched.iter().map(|issue| issue.key.to_string()).collect()
+    }
+
+    #[test]
+    fn equality_and_lists() {
+        assert_eq!(search("project = foo"), ["FOO-10", "FOO-1"]);
+        assert_eq!(
+            search("status in ('To Do', \"in progress\")"),
+            ["BAR-2", "FOO-1"]
+        );
+        assert_eq!(search("labels = backend ORDER BY key"), ["BAR-2", "FOO-1"]);
+        assert_eq!(search("key = FOO-10"), ["FOO-10"]);
+        assert_eq!(search("cf[10000] = 5"), ["FOO-1"]);
+    }
+
+    #[test]
+    fn negation_skips_empty_fields() {
+        assert_eq!(search("assignee != ann"), ["BAR-2"]);
+        assert_eq!(search("assignee not in (ann)"), ["BAR-2"]);
+        assert_eq!(search("NOT assignee = ann"), ["BAR-2", "FOO-10"]);
+    }
+
+    #[test]
+    fn empty_values() {
+        assert_eq!(search("assignee is EMPTY"), ["FOO-10"]);
+        assert_eq!(
+            search("labels is not empty ORDER BY key"),
+            ["BAR-2", "FOO-1"]
+        );
+        assert_eq!(search("assignee = currentUser()"), ["BAR-2"]);
+    }
+
+    #[test]
+    fn text_and_ranges() {
+        assert_eq!(search("summary ~ login ORDER BY key"), ["BAR-2", "FOO-1"]);
+        assert_eq!(search("text ~ \"login page\""), ["FOO-1"]);
+        assert_eq!(
+            search("created >= 2021-03-05 ORDER BY created DESC"),
+            ["FOO-10", "BAR-2"]
+        );
+        assert_eq!(search("created < \"2021-03-05\""), ["FOO-1"]);
+        assert_eq!(search("created > -1d"), Vec::<String>::new());
+        assert_eq!(search("key > FOO-2"), ["FOO-10"]);
+        assert_eq!(search("cf[10000] <= 2"), ["FOO-10"]);
+    }
+
+    #[test]
+    fn boolean_logic_and_order() {
+        assert_eq!(
+            search("(project = BAR OR labels = urgent) && status != Done order by key desc"),
+            ["FOO-1", "BAR-2"]
+        );
+        assert_eq!(search("ORDER BY key ASC"), ["BAR-2", "FOO-1", "FOO-10"]);
+        assert_eq!(search(""), ["BAR-2", "FOO-10", "FOO-1"]);
+    }
+
+    #[test]
+    fn unsupported_queries() {
+        let cases = [
+            ("status WAS Done", 7),
+            ("project = FOO AND", 17),
+            ("status = Done extra", 14),
+            ("sprint in openSprints()", 0),
+            ("summary ~ \"open", 10),
+            ("summary > abc", 0),
+            ("created > -100000000d", 0),
+            ("created > -9999999999999999d", 0),
+            ("created < now(\"-100000000d\")", 0),
+        ];
+
+        for (jql, position) in cases.iter() {
+            let error = Jql::parse(jql)
+                .and_then(|query| {
+                    let issues = sample();
+                    issues
+                        .iter()
+                        .try_for_each(|i| query.matches(i.data(), &Context::default()).map(drop))
+                        .map(|_| query)
+                })
+                .expect_err(jql);
+
+            assert_eq!(error.position, *position, "{}: {}", jql, error);
+        }
+
+        let error = Jql::parse("sprint in openSprints()")
+            .and_then(|query| query.matches(sample()[0].data(), &Context::default()))
+            .unwrap_err();
+        assert!(error.message.contains("opensprints"), "{}", error);
+    }
+}
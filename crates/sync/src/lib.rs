@@ -0,0 +1,28 @@
+//! Mirror the issues matched by JQL queries into a local SQLite database.
+//!
+//! The first sync of a query fetches every matching issue, later syncs
+//! only fetch issues updated since the newest issue already mirrored.
+//! Issues which are deleted, or stop matching the query, are found by
+//! periodically reconciling the mirrored keys against the server's.
+//!
+//! Each issue is stored as the raw JSON Jira returned, alongside
+//! normalized columns for the standard fields so the mirror can be
+//! queried with plain SQL, see `store::SCHEMA`. Mirrored issues can
+//! also be searched with a subset of JQL, see `jql`.
+//!
+//! The same polling can instead report how issues change as they are
+//! updated, without a mirror, see `watch`.
+
+mod error;
+
+pub mod jql;
+pub mod store;
+pub mod sync;
+pub mod watch;
+
+pub use {
+    error::SyncError,
+    store::{LocalOptions, Store},
+    sync::{sync, Report, SyncOptions},
+    watch::{Change, ChangeKind, WatchOptions, Watcher},
+};
@@ -0,0 +1,704 @@
+use {
+    crate::{
+        error::SyncError,
+        jql::{Context, Jql},
+    },
+    chrono::{DateTime, FixedOffset, SecondsFormat, Utc},
+    jira_rs::{
+        issue::models::{Issue, IssueHandle, SearchHandle},
+        time::parse_datetime,
+    },
+    rusqlite::{params, Connection, OptionalExtension, Transaction},
+    serde::Serialize,
+    serde_json::{self as json, value::RawValue as RawJson},
+    std::{collections::HashMap, path::Path},
+    tracing::warn,
+};
+
+/// The page size Jira uses when none is requested
+const DEFAULT_MAX_RESULTS: u32 = 50;
+
+/// The version of `SCHEMA`, stored in the database's `user_version`
+const SCHEMA_VERSION: i64 = 1;
+
+/// The mirror's tables.
+///
+/// `issues` holds one row per mirrored issue: its raw JSON in `raw`, and
+/// the standard fields in their own columns. Timestamps are normalized
+/// to UTC RFC 3339 so they sort correctly, and list fields are JSON
+/// arrays of names.
+///
+/// `queries` tracks each synced JQL query, and `matches` which issues
+/// each query matched. An issue is removed once no query matches it.
+pub const SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS issues (
+    id INTEGER PRIMARY KEY,
+    key TEXT NOT NULL UNIQUE,
+    project TEXT,
+    issue_type TEXT,
+    status TEXT,
+    summary TEXT,
+    priority TEXT,
+    resolution TEXT,
+    assignee TEXT,
+    reporter TEXT,
+    labels TEXT,
+    components TEXT,
+    fix_versions TEXT,
+    created TEXT,
+    updated TEXT,
+    due_date TEXT,
+    parent TEXT,
+    raw TEXT NOT NULL,
+    synced_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS queries (
+    jql TEXT PRIMARY KEY,
+    watermark TEXT,
+    last_sync TEXT,
+    last_reconcile TEXT
+);
+
+CREATE TABLE IF NOT EXISTS matches (
+    jql TEXT NOT NULL REFERENCES queries(jql) ON DELETE CASCADE,
+    issue_id INTEGER NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    PRIMARY KEY (jql, issue_id)
+);
+
+CREATE INDEX IF NOT EXISTS issues_project ON issues(project);
+CREATE INDEX IF NOT EXISTS issues_updated ON issues(updated);
+"#;
+
+/// A local mirror of Jira issues
+#[derive(Debug)]
+pub struct Store {
+    conn: Connection,
+}
+
+/// A synced JQL query, and how far it has been synced
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Query {
+    pub jql: String,
+    /// The latest update of any issue fetched for this query
+    pub watermark: Option<DateTime<FixedOffset>>,
+    pub last_sync: Option<DateTime<Utc>>,
+    /// When the mirrored keys were last checked against the server's
+    pub last_reconcile: Option<DateTime<Utc>>,
+    /// The number of mirrored issues matching this query
+    pub issues: u64,
+}
+
+/// Options for answering searches and lookups from the mirror, the
+/// counterparts of the `Get` and `Search` options
+#[derive(Debug, Clone, Default)]
+pub struct LocalOptions {
+    start_at: u32,
+    max_results: Option<u32>,
+    fields: Vec<String>,
+    current_user: Option<String>,
+}
+
+impl Store {
+    /// Open (or create) the mirror at the given path
+    pub fn open<P>(path: P) -> Result<Self, SyncError>
+    where
+        P: AsRef<Path>,
+    {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// Open a new, empty mirror kept in memory
+    pub fn in_memory() -> Result<Self, SyncError> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self, SyncError> {
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version > SCHEMA_VERSION {
+            return Err(SyncError::SchemaVersion {
+                found: version,
+                supported: SCHEMA_VERSION,
+            });
+        }
+
+        conn.pragma_update(None, "foreign_keys", true)?;
+        conn.execute_batch(SCHEMA)?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+
+        Ok(Self { conn })
+    }
+
+    /// The underlying database connection, for running arbitrary
+    /// (e.g reporting) queries against the mirror
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Lookup a mirrored issue by its key
+    pub fn issue(&self, key: &str) -> Result<Option<IssueHandle>, SyncError> {
+        self.conn
+            .query_row("SELECT raw FROM issues WHERE key = ?1", [key], |row| {
+                row.get::<_, String>(0)
+            })
+            .optional()?
+            .map(to_handle)
+            .transpose()
+    }
+
+    /// Every mirrored issue, ordered by id
+    pub fn issues(&self) -> Result<Vec<IssueHandle>, SyncError> {
+        let mut statement = self.conn.prepare("SELECT raw FROM issues ORDER BY id")?;
+        let rows = statement.query_map([], |row| row.get::<_, String>(0))?;
+
+        rows.map(|raw| to_handle(raw?)).collect()
+    }
+
+    /// Lookup a mirrored issue by key or id, in the shape `Issues::get`
+    /// returns
+    pub fn get(&self, key: &str, options: &LocalOptions) -> Result<Option<IssueHandle>, SyncError> {
+        let raw = self
+            .conn
+            .query_row(
+                "SELECT raw FROM issues WHERE key = ?1 COLLATE NOCASE OR CAST(id AS TEXT) = ?1",
+                [key],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?;
+
+        match raw {
+            Some(raw) => {
+                let issue = to_handle(raw)?;
+                let projected = options.project(issue.data())?;
+
+                Ok(Some(to_handle(json::to_string(&projected)?)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Search the mirrored issues with a subset of JQL, in the shape
+    /// `Issues::search` returns. See `jql` for what is supported
+    pub fn search(&self, jql: &str, options: &LocalOptions) -> Result<SearchHandle, SyncError> {
+        let query = Jql::parse(jql)?;
+        let context = Context {
+            current_user: options.current_user.clone(),
+        };
+
+        let issues = self.issues()?;
+        let mut matched = Vec::new();
+        for issue in issues.iter().map(IssueHandle::data) {
+            if query.matches(issue, &context)? {
+                matched.push(issue);
+            }
+        }
+        matched.sort_by(|a, b| query.compare(a, b));
+
+        let max_results = options.max_results.unwrap_or(DEFAULT_MAX_RESULTS);
+        let page = matched
+            .iter()
+            .skip(options.start_at as usize)
+            .take(max_results as usize)
+            .map(|issue| options.project(issue))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let search = json::json!({
+            "expand": "",
+            "startAt": options.start_at,
+            "maxResults": max_results,
+            "total": matched.len(),
+            "issues": page,
+        });
+
+        Ok(SearchHandle::try_new(RawJson::from_string(
+            search.to_string(),
+        )?)?)
+    }
+
+    /// Lookup a synced query
+    pub fn query(&self, jql: &str) -> Result<Option<Query>, SyncError> {
+        Ok(self.queries()?.into_iter().find(|query| query.jql == jql))
+    }
+
+    /// Every synced query
+    pub fn queries(&self) -> Result<Vec<Query>, SyncError> {
+        let mut statement = self.conn.prepare(
+            "SELECT q.jql, q.watermark, q.last_sync, q.last_reconcile, count(m.issue_id)
+            FROM queries q LEFT JOIN matches m ON m.jql = q.jql
+            GROUP BY q.jql ORDER BY q.jql",
+        )?;
+
+        let rows = statement.query_map([], |row| {
+            Ok(Query {
+                jql: row.get(0)?,
+                watermark: row
+                    .get::<_, Option<String>>(1)?
+                    .and_then(|s| DateTime::parse_from_rfc3339(&s).ok()),
+                last_sync: row.get::<_, Option<String>>(2)?.and_then(parse_utc),
+                last_reconcile: row.get::<_, Option<String>>(3)?.and_then(parse_utc),
+                issues: row.get(4)?,
+            })
+        })?;
+
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Remove a synced query, and any issues only it matched
+    pub fn forget(&mut self, jql: &str) -> Result<usize, SyncError> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM queries WHERE jql = ?1", [jql])?;
+        let removed = prune(&tx)?;
+        tx.commit()?;
+
+        Ok(removed)
+    }
+
+    /// The ids of the issues matched by the given query, and the
+    /// (normalized) time each was last updated
+    pub(crate) fn matched(&self, jql: &str) -> Result<HashMap<u64, Option<String>>, SyncError> {
+        let mut statement = self.conn.prepare(
+            "SELECT i.id, i.updated FROM issues i
+            JOIN matches m ON m.issue_id = i.id WHERE m.jql = ?1",
+        )?;
+        let rows = statement.query_map([jql], |row| Ok((row.get(0)?, row.get(1)?)))?;
+
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Store the given issues as matches of the query, returning the
+    /// latest update of any of them
+    pub(crate) fn upsert(
+        &mut self,
+        jql: &str,
+        issues: &[Issue<'_>],
+    ) -> Result<Option<DateTime<FixedOffset>>, SyncError> {
+        let now = timestamp(&Utc::now());
+        let mut latest = None;
+
+        let tx = self.conn.transaction()?;
+        ensure_query(&tx, jql)?;
+
+        for issue in issues {
+            let row = Normalized::new(issue);
+            latest = latest.max(row.updated_at);
+
+            tx.execute(
+                "INSERT INTO issues (
+                    id, key, project, issue_type, status, summary, priority, resolution,
+                    assignee, reporter, labels, components, fix_versions, created, updated,
+                    due_date, parent, raw, synced_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)
+                ON CONFLICT(id) DO UPDATE SET
+                    key = excluded.key, project = excluded.project,
+                    issue_type = excluded.issue_type, status = excluded.status,
+                    summary = excluded.summary, priority = excluded.priority,
+                    resolution = excluded.resolution, assignee = excluded.assignee,
+                    reporter = excluded.reporter, labels = excluded.labels,
+                    components = excluded.components, fix_versions = excluded.fix_versions,
+                    created = excluded.created, updated = excluded.updated,
+                    due_date = excluded.due_date, parent = excluded.parent,
+                    raw = excluded.raw, synced_at = excluded.synced_at",
+                params![
+                    issue.id as i64,
+                    issue.key.as_ref(),
+                    row.project,
+                    row.issue_type,
+                    row.status,
+                    row.summary,
+                    row.priority,
+                    row.resolution,
+                    row.assignee,
+                    row.reporter,
+                    row.labels,
+                    row.components,
+                    row.fix_versions,
+                    row.created,
+                    row.updated,
+                    row.due_date,
+                    row.parent,
+                    json::to_string(issue)?,
+                    now,
+                ],
+            )?;
+            tx.execute(
+                "INSERT OR IGNORE INTO matches (jql, issue_id) VALUES (?1, ?2)",
+                params![jql, issue.id as i64],
+            )?;
+        }
+
+        tx.commit()?;
+
+        Ok(latest)
+    }
+
+    /// Remove the given issues from the query's matches, deleting any
+    /// issues no longer matched by any query. Returns the number of
+    /// issues deleted
+    pub(crate) fn unmatch(&mut self, jql: &str, ids: &[u64]) -> Result<usize, SyncError> {
+        let tx = self.conn.transaction()?;
+
+        for id in ids {
+            tx.execute(
+                "DELETE FROM matches WHERE jql = ?1 AND issue_id = ?2",
+                params![jql, *id as i64],
+            )?;
+        }
+        let removed = prune(&tx)?;
+        tx.commit()?;
+
+        Ok(removed)
+    }
+
+    /// Record a completed sync of the query
+    pub(crate) fn synced(
+        &mut self,
+        jql: &str,
+        watermark: Option<DateTime<FixedOffset>>,
+        at: &DateTime<Utc>,
+        reconciled: bool,
+    ) -> Result<(), SyncError> {
+        let tx = self.conn.transaction()?;
+        ensure_query(&tx, jql)?;
+        tx.execute(
+            "UPDATE queries SET
+                watermark = coalesce(?2, watermark),
+                last_sync = ?3,
+                last_reconcile = CASE WHEN ?4 THEN ?3 ELSE last_reconcile END
+            WHERE jql = ?1",
+            params![
+                jql,
+                watermark.map(|w| w.to_rfc3339_opts(SecondsFormat::Millis, true)),
+                timestamp(at),
+                reconciled,
+            ],
+        )?;
+        tx.commit()?;
+
+        Ok(())
+    }
+}
+
+impl LocalOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only return results starting from the given number
+    pub fn start_at(&mut self, start_at: impl Into<Option<u32>>) -> &mut Self {
+        self.start_at = start_at.into().unwrap_or_default();
+        self
+    }
+
+    /// The number of results returned, 50 by default
+    pub fn max_results(&mut self, max_results: impl Into<Option<u32>>) -> &mut Self {
+        self.max_results = max_results.into().filter(|u| *u != 0);
+        self
+    }
+
+    /// Narrow the fields returned, as Jira does. '*all' and '*navigable'
+    /// both return every mirrored field, and a '-' prefix omits a field
+    pub fn fields<I, T>(&mut self, fields: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        self.fields
+            .extend(fields.into_iter().map(|f| f.as_ref().to_string()));
+        self
+    }
+
+    /// The user `currentUser()` refers to
+    pub fn current_user<T>(&mut self, user: impl Into<Option<T>>) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.current_user = user.into().map(|u| u.to_string());
+        self
+    }
+
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+
+    /// Serialize the issue with only the requested fields
+    fn project(&self, issue: &Issue<'_>) -> Result<json::Value, SyncError> {
+        let mut value = json::to_value(issue)?;
+
+        let (omitted, included): (Vec<_>, Vec<_>) =
+            self.fields.iter().partition(|f| f.starts_with('-'));
+        let every = included.is_empty()
+            || included
+                .iter()
+                .any(|f| f.as_str() == "*all" || f.as_str() == "*navigable");
+
+        if let Some(json::Value::Object(fields)) = value.get_mut("fields") {
+            fields.retain(|name, _| {
+                let kept = every || included.iter().any(|f| f.as_str() == name);
+                kept && !omitted.iter().any(|f| &f[1..] == name)
+            });
+        }
+
+        Ok(value)
+    }
+}
+
+/// The standard fields of an issue, as stored in their own columns
+#[derive(Debug, Default)]
+struct Normalized {
+    project: Option<String>,
+    issue_type: Option<String>,
+    status: Option<String>,
+    summary: Option<String>,
+    priority: Option<String>,
+    resolution: Option<String>,
+    assignee: Option<String>,
+    reporter: Option<String>,
+    labels: Option<String>,
+    components: Option<String>,
+    fix_versions: Option<String>,
+    created: Option<String>,
+    updated: Option<String>,
+    due_date: Option<String>,
+    parent: Option<String>,
+    updated_at: Option<DateTime<FixedOffset>>,
+}
+
+impl Normalized {
+    /// Extract the standard fields of an issue, leaving any which fail to
+    /// parse empty. They are still available from the raw JSON
+    fn new(issue: &Issue<'_>) -> Self {
+        let fields = match issue.standard_fields() {
+            Ok(fields) => fields,
+            Err(e) => {
+                warn!(key = %issue.key, error = %e, "unable to normalize issue fields");
+                return Self::default();
+            }
+        };
+
+        let names = |names: Vec<&str>| json::to_string(&names).ok();
+        let updated_at = fields.updated_at().and_then(Result::ok);
+
+        Self {
+            project: issue.field::<String>("project.key").and_then(Result::ok),
+            issue_type: fields.issue_type.as_ref().map(|t| t.name.to_string()),
+            status: fields.status.as_ref().map(|s| s.name.to_string()),
+            summary: fields.summary.as_deref().map(String::from),
+            priority: fields.priority.as_ref().map(|p| p.name.to_string()),
+            resolution: fields.resolution.as_ref().map(|r| r.name.to_string()),
+            assignee: fields.assignee.as_ref().map(|u| u.name.to_string()),
+            reporter: fields.reporter.as_ref().map(|u| u.name.to_string()),
+            labels: fields
+                .labels
+                .as_ref()
+                .and_then(|l| names(l.iter().map(AsRef::as_ref).collect())),
+            components: fields
+                .components
+                .as_ref()
+                .and_then(|c| names(c.iter().map(|c| c.name.as_ref()).collect())),
+            fix_versions: fields
+                .fix_versions
+                .as_ref()
+                .and_then(|v| names(v.iter().map(|v| v.name.as_ref()).collect())),
+            created: fields
+                .created_at()
+                .and_then(Result::ok)
+                .map(|t| timestamp(&t)),
+            updated: updated_at.map(|t| timestamp(&t)),
+            due_date: fields.due_date.as_deref().map(String::from),
+            parent: fields.parent.as_ref().map(|p| p.key.to_string()),
+            updated_at,
+        }
+    }
+}
+
+/// Normalize an issue's `updated` field, as stored in the `updated` column
+pub(crate) fn normalized_updated(issue: &Issue<'_>) -> Option<String> {
+    issue
+        .field::<String>("updated")
+        .and_then(Result::ok)
+        .and_then(|updated| parse_datetime(&updated).ok())
+        .map(|t| timestamp(&t))
+}
+
+fn ensure_query(tx: &Transaction<'_>, jql: &str) -> Result<(), SyncError> {
+    tx.execute("INSERT OR IGNORE INTO queries (jql) VALUES (?1)", [jql])?;
+
+    Ok(())
+}
+
+/// Delete every issue not matched by any query
+fn prune(tx: &Transaction<'_>) -> Result<usize, SyncError> {
+    Ok(tx.execute(
+        "DELETE FROM issues WHERE id NOT IN (SELECT issue_id FROM matches)",
+        [],
+    )?)
+}
+
+fn timestamp<Z>(datetime: &DateTime<Z>) -> String
+where
+    Z: chrono::TimeZone,
+{
+    datetime
+        .with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn parse_utc(s: String) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&s)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+fn to_handle(raw: String) -> Result<IssueHandle, SyncError> {
+    Ok(IssueHandle::try_new(RawJson::from_string(raw)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jira_rs::issue::models::SearchHandle;
+    use serde_json::json;
+
+    fn issue(id: u64, key: &str, updated: &str) -> json::Value {
+        json!({
+            "self": format!("https://jira.example.com/rest/api/2/issue/{}", id),
+            "id": id.to_string(),
+            "key": key,
+            "expand": "",
+            "fields": {
+                "summary": format!("Summary of {}", key),
+                "project": { "key": "FOO" },
+                "labels": ["a", "b"],
+                "created": "2021-03-04T10:11:12.000+0100",
+                "updated": updated,
+            }
+        })
+    }
+
+    fn search(issues: Vec<json::Value>) -> SearchHandle {
+        let raw = json!({
+            "expand": "",
+            "maxResults": 50,
+            "startAt": 0,
+            "total": issues.len(),
+            "issues": issues,
+        });
+
+        SearchHandle::try_new(RawJson::from_string(raw.to_string()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn upsert_normalizes_fields() {
+        let mut store = Store::in_memory().unwrap();
+        let page = search(vec![issue(1, "FOO-1", "2021-03-05T10:00:00.000+0000")]);
+
+        let latest = store.upsert("project = FOO", &page.data().issues).unwrap();
+
+        let (project, labels, created): (String, String, String) = store
+            .connection()
+            .query_row(
+                "SELECT project, labels, created FROM issues WHERE key = 'FOO-1'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap();
+
+        assert_eq!(project, "FOO");
+        assert_eq!(labels, r#"["a","b"]"#);
+        assert_eq!(created, "2021-03-04T09:11:12.000Z");
+        assert_eq!(latest, parse_datetime("2021-03-05T10:00:00.000+0000").ok());
+        assert_eq!(store.issue("FOO-1").unwrap().unwrap().data().key, "FOO-1");
+    }
+
+    #[test]
+    fn upsert_replaces_by_id() {
+        let mut store = Store::in_memory().unwrap();
+
+        let old = search(vec![issue(1, "FOO-1", "2021-03-05T10:00:00.000+0000")]);
+        store.upsert("q", &old.data().issues).unwrap();
+        // Moving an issue between projects changes its key, but not its id
+        let moved = search(vec![issue(1, "BAR-1", "2021-03-06T10:00:00.000+0000")]);
+        store.upsert("q", &moved.data().issues).unwrap();
+
+        assert!(store.issue("FOO-1").unwrap().is_none());
+        assert!(store.issue("BAR-1").unwrap().is_some());
+        assert_eq!(store.issues().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn unmatched_issues_are_pruned() {
+        let mut store = Store::in_memory().unwrap();
+        let page = search(vec![
+            issue(1, "FOO-1", "2021-03-05T10:00:00.000+0000"),
+            issue(2, "FOO-2", "2021-03-05T10:00:00.000+0000"),
+        ]);
+        store.upsert("a", &page.data().issues).unwrap();
+        store.upsert("b", &page.data().issues[..1]).unwrap();
+
+        // FOO-1 is still matched by 'b'
+        assert_eq!(store.unmatch("a", &[1, 2]).unwrap(), 1);
+        assert!(store.issue("FOO-1").unwrap().is_some());
+        assert!(store.issue("FOO-2").unwrap().is_none());
+
+        assert_eq!(store.forget("b").unwrap(), 1);
+        assert!(store.issues().unwrap().is_empty());
+    }
+
+    #[test]
+    fn local_search_and_get() {
+        let mut store = Store::in_memory().unwrap();
+        let page = search(vec![
+            issue(1, "FOO-1", "2021-03-05T10:00:00.000+0000"),
+            issue(2, "FOO-2", "2021-03-06T10:00:00.000+0000"),
+            issue(3, "FOO-3", "2021-03-07T10:00:00.000+0000"),
+        ]);
+        store.upsert("q", &page.data().issues).unwrap();
+
+        let options = LocalOptions::new().with(|this| {
+            this.start_at(1)
+                .max_results(1)
+                .fields(vec!["summary", "updated", "-updated"])
+        });
+        let found = store
+            .search("project = FOO ORDER BY updated DESC", &options)
+            .unwrap();
+
+        assert_eq!(found.data().total, 3);
+        assert_eq!(found.data().issues.len(), 1);
+        assert_eq!(found.data().issues[0].key, "FOO-2");
+        assert_eq!(
+            found.data().issues[0].fields.keys().collect::<Vec<_>>(),
+            ["summary"]
+        );
+
+        let by_id = store.get("3", &LocalOptions::new()).unwrap().unwrap();
+        assert_eq!(by_id.data().key, "FOO-3");
+        assert!(store.get("foo-2", &LocalOptions::new()).unwrap().is_some());
+        assert!(store.get("FOO-4", &LocalOptions::new()).unwrap().is_none());
+    }
+
+    #[test]
+    fn synced_queries_are_tracked() {
+        let mut store = Store::in_memory().unwrap();
+        let page = search(vec![issue(1, "FOO-1", "2021-03-05T10:00:00.000+0000")]);
+        let watermark = store.upsert("q", &page.data().issues).unwrap();
+        let now = Utc::now();
+
+        store.synced("q", watermark, &now, true).unwrap();
+        store.synced("q", None, &now, false).unwrap();
+        let query = store.query("q").unwrap().unwrap();
+
+        assert_eq!(query.watermark, watermark);
+        assert!(query.last_reconcile.is_some());
+        assert_eq!(query.issues, 1);
+        assert_eq!(
+            store.matched("q").unwrap().get(&1).cloned().flatten(),
+            Some("2021-03-05T10:00:00.000Z".to_string())
+        );
+    }
+}
@@ -0,0 +1,411 @@
+use {
+    crate::{
+        error::SyncError,
+        store::{normalized_updated, Store},
+    },
+    chrono::{DateTime, Duration as Span, FixedOffset, Utc},
+    jira_rs::{
+        client::Jira,
+        issue::{
+            models::{SearchHandle, User},
+            options::Search,
+        },
+    },
+    serde::Serialize,
+    std::{
+        collections::{HashMap, HashSet},
+        time::Duration,
+    },
+    tracing::{debug, info},
+};
+
+const DEFAULT_RECONCILE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const DEFAULT_PAGE_SIZE: u32 = 100;
+
+/// Options controlling how a query is synced
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    page_size: u32,
+    fields: Vec<String>,
+    full: bool,
+    reconcile: bool,
+    reconcile_interval: Duration,
+}
+
+/// What a sync changed in the mirror
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Report {
+    pub jql: String,
+    /// The number of issues fetched and stored
+    pub fetched: usize,
+    /// The number of issues deleted from the mirror
+    pub removed: usize,
+    /// Whether every matching issue was fetched, rather than only
+    /// those updated since the last sync
+    pub full: bool,
+    /// Whether the mirrored keys were reconciled with the server's
+    pub reconciled: bool,
+}
+
+impl SyncOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of issues requested per page
+    pub fn page_size(&mut self, page_size: impl Into<Option<u32>>) -> &mut Self {
+        self.page_size = page_size
+            .into()
+            .filter(|p| *p != 0)
+            .unwrap_or(DEFAULT_PAGE_SIZE);
+        self
+    }
+
+    /// The fields stored for each issue, by default Jira's navigable
+    /// fields. Fields needed by the mirror's columns should be included
+    pub fn fields<I, T>(&mut self, fields: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        self.fields = fields.into_iter().map(|f| f.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Fetch every matching issue, rather than only those updated since
+    /// the last sync. This always reconciles the mirror
+    pub fn full(&mut self, full: bool) -> &mut Self {
+        self.full = full;
+        self
+    }
+
+    /// Reconcile the mirror, regardless of when it was last reconciled
+    pub fn reconcile(&mut self, reconcile: bool) -> &mut Self {
+        self.reconcile = reconcile;
+        self
+    }
+
+    /// How often incremental syncs reconcile the mirrored keys with the
+    /// server's, to find deleted issues. Defaults to daily
+    pub fn reconcile_interval(&mut self, interval: Duration) -> &mut Self {
+        self.reconcile_interval = interval;
+        self
+    }
+
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+}
+
+impl Default for SyncOptions {
+    fn default() -> Self {
+        Self {
+            page_size: DEFAULT_PAGE_SIZE,
+            fields: Vec::new(),
+            full: false,
+            reconcile: false,
+            reconcile_interval: DEFAULT_RECONCILE_INTERVAL,
+        }
+    }
+}
+
+/// Sync the issues matched by the given JQL into the store.
+///
+/// The first sync of a query (or a `full` one) fetches every matching
+/// issue. Later syncs only fetch those updated since the latest update
+/// already mirrored, reconciling the mirror's keys with the server's
+/// every `reconcile_interval` to remove issues which were deleted or
+/// no longer match the query.
+pub async fn sync(
+    client: &Jira,
+    store: &mut Store,
+    jql: &str,
+    options: &SyncOptions,
+) -> Result<Report, SyncError> {
+    let started = Utc::now();
+    let filter = strip_order_by(jql);
+    let query = store.query(jql)?;
+    let mut report = Report {
+        jql: jql.to_string(),
+        ..Report::default()
+    };
+
+    let watermark = query.as_ref().and_then(|q| q.watermark);
+    let watermark = match watermark.filter(|_| !options.full) {
+        Some(since) => {
+            let since = since_clause(client, &since).await?;
+            let search = incremental(filter, &since);
+            debug!(jql = %search, "fetching updated issues");
+
+            let (fetched, latest) = fetch(client, store, jql, &search, options).await?;
+            report.fetched = fetched.len();
+
+            latest.max(watermark)
+        }
+        None => {
+            report.full = true;
+            let search = ordered(filter);
+            debug!(jql = %search, "fetching every issue");
+
+            let (fetched, latest) = fetch(client, store, jql, &search, options).await?;
+            report.fetched = fetched.len();
+
+            // A full fetch saw every match, so anything else is stale
+            let stale: Vec<_> = store
+                .matched(jql)?
+                .into_keys()
+                .filter(|id| !fetched.contains(id))
+                .collect();
+            report.removed = store.unmatch(jql, &stale)?;
+            report.reconciled = true;
+
+            latest
+        }
+    };
+
+    let due = query
+        .and_then(|q| q.last_reconcile)
+        .map(|last| {
+            let interval = Span::from_std(options.reconcile_interval).unwrap_or(Span::MAX);
+            started.signed_duration_since(last) >= interval
+        })
+        .unwrap_or(true);
+
+    if !report.reconciled && (options.reconcile || due) {
+        let (fetched, removed) = reconcile(client, store, jql, filter, options).await?;
+        report.fetched += fetched;
+        report.removed += removed;
+        report.reconciled = true;
+    }
+
+    store.synced(jql, watermark, &started, report.reconciled)?;
+    info!(
+        jql,
+        fetched = report.fetched,
+        removed = report.removed,
+        "sync complete"
+    );
+
+    Ok(report)
+}
+
+/// Compare the ids and update times of the issues the server matches
+/// with the mirror's, refetching any which are missing or out of date
+/// and removing any the server no longer matches
+async fn reconcile(
+    client: &Jira,
+    store: &mut Store,
+    jql: &str,
+    filter: &str,
+    options: &SyncOptions,
+) -> Result<(usize, usize), SyncError> {
+    debug!(jql, "reconciling mirror");
+
+    let mut remote = HashMap::new();
+    let light = options.clone().with(|this| this.fields(Some("updated")));
+    each_page(client, &ordered(filter), &light, |page| {
+        for issue in page.data().issues.iter() {
+            remote.insert(issue.id, normalized_updated(issue));
+        }
+        Ok(())
+    })
+    .await?;
+
+    let local = store.matched(jql)?;
+    let stale: Vec<_> = local
+        .keys()
+        .filter(|id| !remote.contains_key(id))
+        .copied()
+        .collect();
+    let outdated: Vec<_> = remote
+        .iter()
+        .filter(|(id, updated)| local.get(id) != Some(updated))
+        .map(|(id, _)| *id)
+        .collect();
+
+    let mut fetched = 0;
+    for chunk in outdated.chunks(options.page_size as usize) {
+        let ids: Vec<_> = chunk.iter().map(u64::to_string).collect();
+        let search = format!("id in ({})", ids.join(","));
+
+        fetched += fetch(client, store, jql, &search, options).await?.0.len();
+    }
+
+    let removed = store.unmatch(jql, &stale)?;
+
+    Ok((fetched, removed))
+}
+
+/// Fetch and store every page of the search, returning the ids of the
+/// issues fetched and the latest update of any of them
+async fn fetch(
+    client: &Jira,
+    store: &mut Store,
+    jql: &str,
+    search: &str,
+    options: &SyncOptions,
+) -> Result<(HashSet<u64>, Option<DateTime<FixedOffset>>), SyncError> {
+    let mut fetched = HashSet::new();
+    let mut latest = None;
+
+    each_page(client, search, options, |page| {
+        let issues = &page.data().issues;
+        fetched.extend(issues.iter().map(|issue| issue.id));
+        latest = latest.max(store.upsert(jql, issues)?);
+
+        Ok(())
+    })
+    .await?;
+
+    Ok((fetched, latest))
+}
+
+pub(crate) async fn each_page<F>(
+    client: &Jira,
+    jql: &str,
+    options: &SyncOptions,
+    mut f: F,
+) -> Result<(), SyncError>
+where
+    F: FnMut(&SearchHandle) -> Result<(), SyncError>,
+{
+    let mut start = 0;
+
+    loop {
+        let search = Search::new().with(|this| {
+            this.jql(jql)
+                .start_at(start)
+                .max_results(options.page_size)
+                .fields(&options.fields)
+        });
+        let page = client.issues().search(Some(&search)).await?;
+        f(&page)?;
+
+        let data = page.data();
+        start += data.issues.len() as u32;
+        if data.issues.is_empty() || start as u64 >= data.total {
+            return Ok(());
+        }
+    }
+}
+
+/// Render the watermark for use in JQL, for the calling user
+async fn since_clause(client: &Jira, since: &DateTime<FixedOffset>) -> Result<String, SyncError> {
+    let user = client.users().myself().await?;
+
+    Ok(render_since(user.data(), since))
+}
+
+/// Render the watermark for use in JQL.
+///
+/// JQL dates have minute precision and are interpreted in the user's
+/// timezone, so the watermark is converted to it and truncated, which
+/// refetches the issues updated in the watermark's minute. If the user
+/// has no known timezone the window is widened by a day to cover any
+/// offset the server may use instead.
+pub(crate) fn render_since(user: &User<'_>, since: &DateTime<FixedOffset>) -> String {
+    const FORMAT: &str = "%Y-%m-%d %H:%M";
+
+    match user.local_time(since) {
+        Some(local) => local.format(FORMAT).to_string(),
+        None => (since.with_timezone(&Utc) - Span::days(1))
+            .format(FORMAT)
+            .to_string(),
+    }
+}
+
+pub(crate) fn incremental(filter: &str, since: &str) -> String {
+    match filter {
+        "" => format!(r#"updated >= "{}" ORDER BY updated ASC"#, since),
+        filter => format!(
+            r#"({}) AND updated >= "{}" ORDER BY updated ASC"#,
+            filter, since
+        ),
+    }
+}
+
+/// Order by key, so pages are stable while being fetched
+pub(crate) fn ordered(filter: &str) -> String {
+    match filter {
+        "" => "ORDER BY key ASC".to_string(),
+        filter => format!("({}) ORDER BY key ASC", filter),
+    }
+}
+
+/// Remove any ORDER BY clause from the JQL, so it can be combined with
+/// other clauses
+pub(crate) fn strip_order_by(jql: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+
+    for (i, c) in jql.char_indices() {
+        match (quote, c) {
+            _ if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, _) if is_order_by(&jql[i..]) && jql[..i].ends_with(char::is_whitespace) => {
+                return jql[..i].trim();
+            }
+            (None, _) if i == 0 && is_order_by(jql) => return "",
+            _ => {}
+        }
+    }
+
+    jql.trim()
+}
+
+fn is_order_by(s: &str) -> bool {
+    let mut words = s.split_whitespace();
+
+    matches!(
+        (words.next(), words.next()),
+        (Some(order), Some(by)) if order.eq_ignore_ascii_case("order") && by.eq_ignore_ascii_case("by")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_order_by() {
+        let cases = [
+            ("project = FOO", "project = FOO"),
+            ("project = FOO ORDER BY key", "project = FOO"),
+            ("project = FOO order  by created DESC", "project = FOO"),
+            ("ORDER BY updated", ""),
+            (
+                r#"summary ~ "order by" ORDER BY key"#,
+                r#"summary ~ "order by""#,
+            ),
+            (
+                r#"summary ~ 'a \' order by'"#,
+                r#"summary ~ 'a \' order by'"#,
+            ),
+            ("reorder = 1", "reorder = 1"),
+        ];
+
+        for (jql, expected) in cases.iter() {
+            assert_eq!(strip_order_by(jql), *expected, "{}", jql)
+        }
+    }
+
+    #[test]
+    fn incremental_queries() {
+        assert_eq!(
+            incremental("project = FOO", "2021-03-04 10:11"),
+            r#"(project = FOO) AND updated >= "2021-03-04 10:11" ORDER BY updated ASC"#
+        );
+        assert_eq!(
+            incremental("", "2021-03-04 10:11"),
+            r#"updated >= "2021-03-04 10:11" ORDER BY updated ASC"#
+        );
+        assert_eq!(ordered("a = b"), "(a = b) ORDER BY key ASC");
+    }
+}
@@ -0,0 +1,491 @@
+use {
+    crate::{
+        error::SyncError,
+        sync::{each_page, incremental, ordered, render_since, strip_order_by, SyncOptions},
+    },
+    chrono::{DateTime, FixedOffset, Utc},
+    jira_rs::{client::Jira, issue::models::Issue, user::models::UserHandle},
+    serde::Serialize,
+    serde_json::{self as json, Map, Value},
+    std::collections::{HashMap, HashSet},
+    tracing::debug,
+};
+
+/// Fields which change on most updates, or per viewer, and are never
+/// reported as changed
+const IGNORED: [&str; 3] = ["updated", "lastViewed", "statuscategorychangedate"];
+const STATUS: &str = "status";
+const COMMENT: &str = "comment";
+
+/// Options controlling what a watcher fetches
+#[derive(Debug, Clone, Default)]
+pub struct WatchOptions {
+    page_size: Option<u32>,
+    fields: Vec<String>,
+}
+
+impl WatchOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of issues requested per page
+    pub fn page_size(&mut self, page_size: impl Into<Option<u32>>) -> &mut Self {
+        self.page_size = page_size.into();
+        self
+    }
+
+    /// The fields compared for changes, by default Jira's navigable
+    /// fields. The status, comments and update time are always fetched
+    pub fn fields<I, T>(&mut self, fields: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        self.fields = fields.into_iter().map(|f| f.as_ref().to_string()).collect();
+        self
+    }
+
+    pub fn with<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut Self) -> &mut Self,
+    {
+        let mut this = self;
+        f(&mut this);
+        this
+    }
+
+    fn search_options(&self) -> SyncOptions {
+        SyncOptions::new().with(|this| this.page_size(self.page_size).fields(self.search_fields()))
+    }
+
+    fn search_fields(&self) -> Vec<String> {
+        let mut fields = match self.fields.is_empty() {
+            true => vec!["*navigable".to_string()],
+            false => self.fields.clone(),
+        };
+        for required in ["updated", STATUS, COMMENT].iter() {
+            if !fields.iter().any(|f| f == required) {
+                fields.push(required.to_string())
+            }
+        }
+
+        fields
+    }
+}
+
+/// A change to an issue matched by a watched query
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Change {
+    pub id: u64,
+    pub key: String,
+    /// When the issue was updated, as Jira reported it
+    pub updated: Option<String>,
+    #[serde(flatten)]
+    pub kind: ChangeKind,
+}
+
+/// What changed about an issue
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// An issue created since the watch started
+    Created,
+    /// An existing issue which now matches the query, e.g because it
+    /// was moved into the watched project
+    Matched,
+    /// A field other than the status or comments changed. The values
+    /// are Jira's JSON representation of the field, null if unset
+    FieldChanged {
+        field: String,
+        from: Value,
+        to: Value,
+    },
+    /// A comment was added
+    Commented {
+        comment: u64,
+        author: Option<String>,
+        body: String,
+    },
+    /// The issue moved between statuses, by name
+    Transitioned { from: String, to: String },
+}
+
+/// Polls a JQL query for changes to the issues it matches.
+///
+/// The first poll records every matching issue, later polls fetch
+/// those updated since and compare them to the version last seen.
+/// Changes made between polls are seen together, so a field changed
+/// twice is reported once, with its original and latest values.
+///
+/// This is a fallback for when webhooks can't reach the caller, see
+/// `jira_rs::webhook`
+#[derive(Debug)]
+pub struct Watcher {
+    client: Jira,
+    jql: String,
+    options: SyncOptions,
+    started: DateTime<FixedOffset>,
+    user: Option<UserHandle>,
+    /// The latest update seen, None until the first poll
+    watermark: Option<DateTime<FixedOffset>>,
+    seen: HashMap<u64, Snapshot>,
+}
+
+/// What one poll has seen so far
+#[derive(Debug, Default)]
+struct Pass {
+    latest: Option<DateTime<FixedOffset>>,
+    changes: Vec<Change>,
+    snapshots: HashMap<u64, Snapshot>,
+}
+
+/// The version of an issue last seen
+#[derive(Debug, Clone, PartialEq)]
+struct Snapshot {
+    updated: Option<DateTime<FixedOffset>>,
+    fields: Map<String, Value>,
+}
+
+impl Watcher {
+    pub fn new(client: &Jira, jql: &str, options: &WatchOptions) -> Self {
+        Self {
+            client: client.clone(),
+            jql: jql.to_string(),
+            options: options.search_options(),
+            started: Utc::now().into(),
+            user: None,
+            watermark: None,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Fetch the issues updated since the last poll, returning their
+    /// changes in the order they were updated. The first poll only
+    /// records the matching issues, returning no changes
+    pub async fn poll(&mut self) -> Result<Vec<Change>, SyncError> {
+        let filter = strip_order_by(&self.jql);
+        let search = match self.watermark {
+            Some(ref since) => {
+                if self.user.is_none() {
+                    self.user = Some(self.client.users().myself().await?);
+                }
+                let user = self.user.as_ref().expect("fetched above");
+
+                incremental(filter, &render_since(user.data(), since))
+            }
+            None => ordered(filter),
+        };
+        debug!(jql = %search, "polling for changes");
+
+        // Nothing is recorded until every page is fetched, so a failed
+        // poll leaves the changes it saw for the next
+        let mut pass = Pass::default();
+        each_page(&self.client, &search, &self.options, |page| {
+            page.data()
+                .issues
+                .iter()
+                .try_for_each(|issue| self.observe(&mut pass, issue))
+        })
+        .await?;
+
+        Ok(self.finish(pass))
+    }
+
+    /// Compare an issue to the version last seen, in this pass or before
+    fn observe(&self, pass: &mut Pass, issue: &Issue<'_>) -> Result<(), SyncError> {
+        let snapshot = Snapshot::new(issue)?;
+        pass.latest = pass.latest.max(snapshot.updated);
+
+        let previous = pass
+            .snapshots
+            .get(&issue.id)
+            .or_else(|| self.seen.get(&issue.id));
+        let kinds = match previous {
+            Some(previous) if previous.updated == snapshot.updated => return Ok(()),
+            Some(previous) => previous.diff(&snapshot),
+            None if self.watermark.is_none() => Vec::new(),
+            None => match issue.created_at() {
+                Some(Ok(created)) if created >= self.started => vec![ChangeKind::Created],
+                _ => vec![ChangeKind::Matched],
+            },
+        };
+
+        let updated = issue.field::<String>("updated").and_then(Result::ok);
+        pass.changes.extend(kinds.into_iter().map(|kind| Change {
+            id: issue.id,
+            key: issue.key.to_string(),
+            updated: updated.clone(),
+            kind,
+        }));
+        pass.snapshots.insert(issue.id, snapshot);
+
+        Ok(())
+    }
+
+    /// Record a completed pass, returning its changes
+    fn finish(&mut self, pass: Pass) -> Vec<Change> {
+        self.watermark = self.watermark.max(pass.latest).or(Some(self.started));
+        self.seen.extend(pass.snapshots);
+        debug!(
+            changes = pass.changes.len(),
+            watching = self.seen.len(),
+            "poll complete"
+        );
+
+        pass.changes
+    }
+}
+
+impl Snapshot {
+    fn new(issue: &Issue<'_>) -> Result<Self, SyncError> {
+        let fields = issue
+            .fields
+            .iter()
+            .map(|(name, raw)| Ok((name.to_string(), json::from_str(raw.get())?)))
+            .collect::<Result<_, json::Error>>()?;
+
+        Ok(Self {
+            updated: issue.updated_at().and_then(Result::ok),
+            fields,
+        })
+    }
+
+    /// The changes between this and a later version of the issue
+    fn diff(&self, later: &Snapshot) -> Vec<ChangeKind> {
+        let mut changes = Vec::new();
+
+        let (from, to) = (self.status(), later.status());
+        if from != to {
+            changes.push(ChangeKind::Transitioned {
+                from: from.unwrap_or_default().to_string(),
+                to: to.unwrap_or_default().to_string(),
+            })
+        }
+
+        let fields: HashSet<_> = self.fields.keys().chain(later.fields.keys()).collect();
+        let mut fields: Vec<_> = fields
+            .into_iter()
+            .filter(|f| ![STATUS, COMMENT].contains(&f.as_str()) && !IGNORED.contains(&f.as_str()))
+            .collect();
+        fields.sort();
+
+        for field in fields {
+            let from = self.fields.get(field).unwrap_or(&Value::Null);
+            let to = later.fields.get(field).unwrap_or(&Value::Null);
+
+            if from != to {
+                changes.push(ChangeKind::FieldChanged {
+                    field: field.clone(),
+                    from: from.clone(),
+                    to: to.clone(),
+                })
+            }
+        }
+
+        let known: HashSet<_> = self.comments().map(|(id, _)| id).collect();
+        for (id, comment) in later.comments().filter(|(id, _)| !known.contains(id)) {
+            let text = |field: &str| comment.get(field).and_then(Value::as_str);
+
+            changes.push(ChangeKind::Commented {
+                comment: id,
+                author: comment
+                    .get("author")
+                    .and_then(|author| author.get("displayName"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                body: text("body").unwrap_or_default().to_string(),
+            })
+        }
+
+        changes
+    }
+
+    fn status(&self) -> Option<&str> {
+        self.fields
+            .get(STATUS)
+            .and_then(|status| status.get("name"))
+            .and_then(Value::as_str)
+    }
+
+    /// The issue's comments, by id. Search results hold at most the
+    /// first page of an issue's comments
+    fn comments(&self) -> impl Iterator<Item = (u64, &Value)> {
+        let comments = self
+            .fields
+            .get(COMMENT)
+            .and_then(|comment| comment.get("comments"))
+            .and_then(Value::as_array);
+
+        comments.into_iter().flatten().filter_map(|comment| {
+            let id = comment.get("id")?;
+            let id = id.as_str().and_then(|id| id.parse().ok()).or(id.as_u64())?;
+
+            Some((id, comment))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        jira_rs::{client::Authentication, issue::models::IssueHandle},
+        serde_json::{json, value::RawValue as RawJson},
+    };
+
+    fn issue(fields: Value) -> IssueHandle {
+        let raw = json!({
+            "self": "https://jira.example.com/rest/api/2/issue/1",
+            "id": "1",
+            "key": "FOO-1",
+            "fields": fields,
+        });
+
+        IssueHandle::try_new(RawJson::from_string(raw.to_string()).unwrap()).unwrap()
+    }
+
+    fn snapshot(fields: Value) -> Snapshot {
+        Snapshot::new(issue(fields).data()).unwrap()
+    }
+
+    fn comment(id: &str, body: &str) -> Value {
+        json!({ "id": id, "author": { "displayName": "Ann" }, "body": body })
+    }
+
+    #[test]
+    fn diff_changes() {
+        let before = snapshot(json!({
+            "summary": "Fix the thing",
+            "labels": ["a"],
+            "status": { "name": "Open" },
+            "comment": { "comments": [comment("10", "first")] },
+            "updated": "2021-03-04T10:11:12.000+0000",
+        }));
+        let after = snapshot(json!({
+            "summary": "Fix the thing",
+            "labels": ["a", "b"],
+            "assignee": { "displayName": "Bob" },
+            "status": { "name": "In Progress" },
+            "comment": { "comments": [comment("10", "first"), comment("11", "second")] },
+            "updated": "2021-03-04T10:20:00.000+0000",
+        }));
+
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                ChangeKind::Transitioned {
+                    from: "Open".into(),
+                    to: "In Progress".into()
+                },
+                ChangeKind::FieldChanged {
+                    field: "assignee".into(),
+                    from: Value::Null,
+                    to: json!({ "displayName": "Bob" })
+                },
+                ChangeKind::FieldChanged {
+                    field: "labels".into(),
+                    from: json!(["a"]),
+                    to: json!(["a", "b"])
+                },
+                ChangeKind::Commented {
+                    comment: 11,
+                    author: Some("Ann".into()),
+                    body: "second".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_ignores_noise() {
+        let before = snapshot(json!({
+            "summary": "a",
+            "lastViewed": "2021-03-04T10:11:12.000+0000",
+            "updated": "2021-03-04T10:11:12.000+0000",
+        }));
+        let after = snapshot(json!({
+            "summary": "a",
+            "lastViewed": "2021-03-05T10:11:12.000+0000",
+            "updated": "2021-03-05T10:11:12.000+0000",
+        }));
+
+        assert!(before.diff(&after).is_empty());
+        assert!(after.updated > before.updated);
+    }
+
+    #[test]
+    fn failed_polls_keep_changes() {
+        let client = Jira::new("http://127.0.0.1:1", Authentication::basic("me", "x")).unwrap();
+        let mut watcher = Watcher::new(&client, "project = FOO", &WatchOptions::new());
+        let (before, after) = (
+            issue(json!({ "summary": "a", "updated": "2021-03-04T10:11:12.000+0000" })),
+            issue(json!({ "summary": "b", "updated": "2021-03-05T10:11:12.000+0000" })),
+        );
+
+        let mut baseline = Pass::default();
+        watcher.observe(&mut baseline, before.data()).unwrap();
+        assert!(watcher.finish(baseline).is_empty());
+
+        // A pass which saw the change, but failed on a later page
+        let mut failed = Pass::default();
+        watcher.observe(&mut failed, after.data()).unwrap();
+        assert_eq!(failed.changes.len(), 1);
+        drop(failed);
+
+        let mut retry = Pass::default();
+        watcher.observe(&mut retry, after.data()).unwrap();
+        let changes = watcher.finish(retry);
+        assert_eq!(
+            changes.iter().map(|c| &c.kind).collect::<Vec<_>>(),
+            [&ChangeKind::FieldChanged {
+                field: "summary".into(),
+                from: json!("a"),
+                to: json!("b")
+            }]
+        );
+
+        let mut unchanged = Pass::default();
+        watcher.observe(&mut unchanged, after.data()).unwrap();
+        assert!(watcher.finish(unchanged).is_empty());
+    }
+
+    #[test]
+    fn serialize_change() {
+        let change = Change {
+            id: 1,
+            key: "FOO-1".into(),
+            updated: None,
+            kind: ChangeKind::Transitioned {
+                from: "Open".into(),
+                to: "Done".into(),
+            },
+        };
+
+        assert_eq!(
+            json::to_value(&change).unwrap(),
+            json!({
+                "id": 1,
+                "key": "FOO-1",
+                "updated": null,
+                "change": "transitioned",
+                "from": "Open",
+                "to": "Done"
+            })
+        );
+    }
+
+    #[test]
+    fn required_fields() {
+        let options = WatchOptions::new().with(|this| this.fields(["summary", "status"]));
+
+        assert_eq!(
+            options.search_fields(),
+            ["summary", "status", "updated", "comment"]
+        );
+        assert_eq!(
+            WatchOptions::new().search_fields(),
+            ["*navigable", "updated", "status", "comment"]
+        );
+    }
+}